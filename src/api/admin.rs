@@ -0,0 +1,152 @@
+//! Admin endpoints for operational actions: peer management, mempool
+//! flushing, snapshots, key rotation, and runtime log-level changes.
+//!
+//! Authentication is enforced by the privileged-route middleware in
+//! [`super::auth`]; every request reaching these handlers has already
+//! presented a valid API key or the admin bearer token.
+
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::json;
+
+use super::ApiState;
+use crate::errors::{ErrorCode, ErrorEnvelope};
+use crate::security::{PendingKey, PENDING_KEY_FILE};
+
+/// The admin scope, mounted inside the privileged section of `/api`.
+pub fn scope() -> actix_web::Scope {
+    web::scope("/admin")
+        .route("/peers", web::post().to(add_peer))
+        .route("/peers/{id}", web::delete().to(remove_peer))
+        .route("/peers/ban", web::post().to(ban_peer))
+        .route("/mempool/flush", web::post().to(flush_mempool))
+        .route("/snapshot", web::post().to(trigger_snapshot))
+        .route("/validator-key/rotate", web::post().to(rotate_key))
+        .route("/log-level", web::put().to(set_log_level))
+}
+
+#[derive(Debug, Deserialize)]
+struct AddPeer {
+    address: String,
+}
+
+async fn add_peer(data: web::Data<ApiState>, body: web::Json<AddPeer>) -> impl Responder {
+    match data.connections.connect(&body.address).await {
+        Ok(()) => HttpResponse::Accepted().json(json!({ "dialing": body.address })),
+        Err(err) => HttpResponse::BadGateway().json(ErrorEnvelope::new(
+            ErrorCode::NetIo,
+            format!("dial failed: {err}"),
+        )),
+    }
+}
+
+async fn remove_peer(data: web::Data<ApiState>, path: web::Path<String>) -> impl Responder {
+    let peer_id = path.into_inner();
+    if data.connections.disconnect(&peer_id).await {
+        HttpResponse::Ok().json(json!({ "disconnected": peer_id }))
+    } else {
+        HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NetPeerNotFound,
+            "peer not connected",
+        ))
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct BanPeer {
+    /// A single address or a CIDR range such as `10.0.0.0/8`.
+    address: String,
+    #[serde(default = "default_ban_secs")]
+    duration_secs: u64,
+    /// A permanent ban ignores `duration_secs` and survives restarts.
+    #[serde(default)]
+    permanent: bool,
+}
+
+fn default_ban_secs() -> u64 {
+    3_600
+}
+
+async fn ban_peer(data: web::Data<ApiState>, body: web::Json<BanPeer>) -> impl Responder {
+    if body.address.contains('/') {
+        let duration = (!body.permanent).then_some(body.duration_secs);
+        return match data.network_security.ban_range(&body.address, duration).await {
+            Ok(()) => HttpResponse::Ok().json(json!({
+                "banned": body.address,
+                "permanent": body.permanent,
+            })),
+            Err(err) => HttpResponse::BadRequest()
+                .json(ErrorEnvelope::new(ErrorCode::InvalidRequest, err)),
+        };
+    }
+    if body.permanent {
+        data.network_security
+            .ban_peer_permanently(&body.address)
+            .await;
+        return HttpResponse::Ok().json(json!({
+            "banned": body.address,
+            "permanent": true,
+        }));
+    }
+    data.network_security
+        .ban_peer(&body.address, body.duration_secs)
+        .await;
+    HttpResponse::Ok().json(json!({
+        "banned": body.address,
+        "duration_secs": body.duration_secs,
+    }))
+}
+
+async fn flush_mempool(data: web::Data<ApiState>) -> impl Responder {
+    let removed = data.pool.clear().await;
+    HttpResponse::Ok().json(json!({ "removed": removed }))
+}
+
+/// Seal a state snapshot at the current height, so pruning can advance
+/// and a new sync source exists without waiting for the next block.
+async fn trigger_snapshot(data: web::Data<ApiState>) -> impl Responder {
+    let height = data.engine.height().await;
+    data.state.commit_version(height).await;
+    HttpResponse::Ok().json(json!({ "snapshot_height": height }))
+}
+
+/// First phase of validator key rotation: generate a fresh key and
+/// persist it next to the active one. The live key keeps signing —
+/// the validator set still lists its public key, so swapping it now
+/// would leave every subsequent vote unverifiable. The operator
+/// registers the new public key via a staking transaction, then
+/// restarts the node to activate it.
+async fn rotate_key(data: web::Data<ApiState>) -> impl Responder {
+    let pending = PendingKey::generate();
+    let path = data.keys_dir.join(PENDING_KEY_FILE);
+    let written = serde_json::to_vec_pretty(&pending)
+        .map_err(|err| err.to_string())
+        .and_then(|bytes| std::fs::write(&path, bytes).map_err(|err| err.to_string()));
+    match written {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "public_key": pending.public_key,
+            "address": pending.address,
+            "status": "pending",
+            "activation": "register the new public key via staking, then restart the node",
+        })),
+        Err(err) => HttpResponse::InternalServerError().json(ErrorEnvelope::new(
+            ErrorCode::Internal,
+            format!("cannot persist pending key: {err}"),
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LogLevel {
+    level: String,
+}
+
+async fn set_log_level(body: web::Json<LogLevel>) -> impl Responder {
+    match crate::telemetry::set_log_level(&body.level) {
+        Ok(()) => HttpResponse::Ok().json(json!({ "level": body.level })),
+        Err(err) => HttpResponse::BadRequest().json(ErrorEnvelope::new(
+            ErrorCode::InvalidRequest,
+            format!("invalid filter: {err}"),
+        )),
+    }
+}
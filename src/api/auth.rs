@@ -0,0 +1,79 @@
+//! Authentication for privileged API routes.
+//!
+//! Public query routes stay open; anything that mutates node state
+//! (faucet, admin scope) sits behind [`require_privileged`], which
+//! accepts either a configured API key in `X-Api-Key` or the admin
+//! bearer token. With neither configured the privileged routes are
+//! disabled entirely.
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::middleware::Next;
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+
+use super::ApiState;
+use crate::errors::{ErrorCode, ErrorEnvelope};
+
+/// Middleware gate for privileged routes: passes the request through
+/// when it carries valid credentials, answers 401 otherwise.
+pub async fn require_privileged<B: MessageBody + 'static>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let authorized = req.app_data::<web::Data<ApiState>>().is_some_and(|data| {
+        credentials_match(req.request(), &data.api_keys, data.admin_token.as_deref())
+    });
+    if !authorized {
+        let response = HttpResponse::Unauthorized().json(ErrorEnvelope::new(
+            ErrorCode::Unauthorized,
+            "api key or admin token required",
+        ));
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+    next.call(req).await.map(|res| res.map_into_left_body())
+}
+
+/// Whether the request carries a configured API key (`X-Api-Key`) or
+/// the admin bearer token (`Authorization: Bearer ...`).
+fn credentials_match(req: &HttpRequest, api_keys: &[String], admin_token: Option<&str>) -> bool {
+    if let Some(key) = req.headers().get("x-api-key").and_then(|v| v.to_str().ok()) {
+        if api_keys.iter().any(|configured| configured == key) {
+            return true;
+        }
+    }
+    req.headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .is_some_and(|token| admin_token == Some(token))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn accepts_api_key_or_bearer_token_only() {
+        let keys = vec!["key-1".to_string()];
+
+        let keyed = TestRequest::default()
+            .insert_header(("x-api-key", "key-1"))
+            .to_http_request();
+        assert!(credentials_match(&keyed, &keys, Some("secret")));
+
+        let bearer = TestRequest::default()
+            .insert_header(("authorization", "Bearer secret"))
+            .to_http_request();
+        assert!(credentials_match(&bearer, &keys, Some("secret")));
+
+        let wrong = TestRequest::default()
+            .insert_header(("x-api-key", "key-2"))
+            .to_http_request();
+        assert!(!credentials_match(&wrong, &keys, Some("secret")));
+
+        // Nothing configured means nothing authenticates.
+        let bare = TestRequest::default().to_http_request();
+        assert!(!credentials_match(&bare, &[], None));
+    }
+}
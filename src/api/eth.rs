@@ -0,0 +1,118 @@
+//! Minimal Ethereum JSON-RPC compatibility shim.
+//!
+//! Implements just enough of the `eth` namespace — `eth_blockNumber`,
+//! `eth_getBalance`, `eth_sendRawTransaction`, and
+//! `eth_getTransactionReceipt` — for common wallet tooling to do simple
+//! transfers. Raw transactions are hex-encoded Artha transaction JSON,
+//! not RLP; addresses and hashes accept an optional `0x` prefix.
+
+use actix_web::{web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use super::ApiState;
+use crate::types::{Transaction, TxStatus};
+
+#[derive(Debug, Deserialize)]
+pub struct RpcRequest {
+    #[serde(default)]
+    id: Value,
+    method: String,
+    #[serde(default)]
+    params: Vec<Value>,
+}
+
+/// `POST /api/rpc` JSON-RPC 2.0 handler.
+pub async fn handle(data: web::Data<ApiState>, body: web::Json<RpcRequest>) -> impl Responder {
+    let RpcRequest { id, method, params } = body.into_inner();
+    let outcome = match method.as_str() {
+        "eth_blockNumber" => Ok(hex_u64(data.engine.height().await)),
+        "eth_getBalance" => get_balance(&data, &params).await,
+        "eth_sendRawTransaction" => send_raw_transaction(&data, &params).await,
+        "eth_getTransactionReceipt" => get_transaction_receipt(&data, &params).await,
+        _ => Err((-32601, format!("method {method} not supported"))),
+    };
+    let response = match outcome {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": code, "message": message },
+        }),
+    };
+    HttpResponse::Ok().json(response)
+}
+
+type RpcResult = Result<Value, (i64, String)>;
+
+fn hex_u64(value: u64) -> Value {
+    Value::String(format!("0x{value:x}"))
+}
+
+/// First positional parameter as a string with any `0x` prefix removed.
+fn hex_param(params: &[Value], index: usize) -> Result<String, (i64, String)> {
+    params
+        .get(index)
+        .and_then(Value::as_str)
+        .map(|value| value.trim_start_matches("0x").to_string())
+        .ok_or((-32602, format!("missing string parameter {index}")))
+}
+
+async fn get_balance(data: &ApiState, params: &[Value]) -> RpcResult {
+    let address = hex_param(params, 0)?;
+    let balance = data
+        .state
+        .get_account(&address)
+        .await
+        .map_or(0, |account| account.balance);
+    Ok(hex_u64(balance))
+}
+
+async fn send_raw_transaction(data: &ApiState, params: &[Value]) -> RpcResult {
+    let raw = hex_param(params, 0)?;
+    let bytes = hex::decode(&raw).map_err(|err| (-32602, format!("invalid hex: {err}")))?;
+    let tx: Transaction = serde_json::from_slice(&bytes)
+        .map_err(|err| (-32602, format!("invalid transaction payload: {err}")))?;
+    let hash = tx.hash();
+    data.tracker.record(&hash, TxStatus::Received).await;
+    match data.pool.add_transaction(tx).await {
+        Ok(()) => {
+            data.tracker.record(&hash, TxStatus::Checked).await;
+            Ok(Value::String(format!("0x{hash}")))
+        }
+        Err(err) => {
+            data.tracker
+                .record(
+                    &hash,
+                    TxStatus::Failed {
+                        reason: err.to_string(),
+                    },
+                )
+                .await;
+            Err((-32000, err.to_string()))
+        }
+    }
+}
+
+async fn get_transaction_receipt(data: &ApiState, params: &[Value]) -> RpcResult {
+    let hash = hex_param(params, 0)?;
+    let state = data.engine.state.read().await;
+    for results in state.results.iter().rev() {
+        if let Some(receipt) = results.receipts.iter().find(|r| r.tx_hash == hash) {
+            let block_hash = state
+                .blocks
+                .iter()
+                .find(|block| block.header.height == results.height)
+                .map(|block| format!("0x{}", hex::encode(block.hash())));
+            return Ok(json!({
+                "transactionHash": format!("0x{hash}"),
+                "blockNumber": hex_u64(results.height),
+                "blockHash": block_hash,
+                "gasUsed": hex_u64(receipt.gas_used),
+                "status": "0x1",
+            }));
+        }
+    }
+    // Unknown or still pending: null, as Ethereum clients expect.
+    Ok(Value::Null)
+}
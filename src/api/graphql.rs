@@ -0,0 +1,288 @@
+//! GraphQL query endpoint over chain data.
+//!
+//! One `POST /api/graphql` request can resolve nested blocks,
+//! transactions, accounts, and validators, so explorer frontends do not
+//! have to stitch several REST calls together. Queries only read
+//! retained in-memory state; mutations go through the REST routes.
+
+use std::sync::OnceLock;
+
+use actix_web::web;
+use async_graphql::{Context, EmptyMutation, EmptySubscription, Object, Schema};
+use async_graphql_actix_web::{GraphQLRequest, GraphQLResponse};
+
+use super::ApiState;
+use crate::types::{Block, Transaction};
+
+/// Most blocks or transactions one query page returns.
+const MAX_PAGE: usize = 100;
+
+/// A block, with its transactions resolvable as a nested field.
+pub struct GqlBlock(Block);
+
+#[Object(name = "Block")]
+impl GqlBlock {
+    async fn height(&self) -> u64 {
+        self.0.header.height
+    }
+
+    async fn timestamp(&self) -> u64 {
+        self.0.header.timestamp
+    }
+
+    async fn hash(&self) -> String {
+        hex::encode(self.0.hash())
+    }
+
+    async fn prev_hash(&self) -> String {
+        hex::encode(&self.0.header.prev_hash)
+    }
+
+    async fn state_root(&self) -> String {
+        hex::encode(&self.0.header.state_root)
+    }
+
+    async fn proposer(&self) -> String {
+        self.0.header.proposer.clone()
+    }
+
+    async fn transaction_count(&self) -> usize {
+        self.0.transactions.len()
+    }
+
+    async fn transactions(&self) -> Vec<GqlTransaction> {
+        self.0
+            .transactions
+            .iter()
+            .map(|tx| GqlTransaction {
+                tx: tx.clone(),
+                height: Some(self.0.header.height),
+            })
+            .collect()
+    }
+}
+
+/// A transaction, with the containing block resolvable when committed.
+pub struct GqlTransaction {
+    tx: Transaction,
+    height: Option<u64>,
+}
+
+#[Object(name = "Transaction")]
+impl GqlTransaction {
+    async fn hash(&self) -> String {
+        self.tx.hash()
+    }
+
+    async fn sender(&self) -> String {
+        self.tx.sender.clone()
+    }
+
+    async fn recipient(&self) -> String {
+        self.tx.recipient.clone()
+    }
+
+    async fn amount(&self) -> u64 {
+        self.tx.amount
+    }
+
+    async fn denom(&self) -> String {
+        self.tx.denom.clone()
+    }
+
+    async fn nonce(&self) -> u64 {
+        self.tx.nonce
+    }
+
+    async fn gas_limit(&self) -> u64 {
+        self.tx.gas_limit
+    }
+
+    async fn gas_price(&self) -> u64 {
+        self.tx.gas_price
+    }
+
+    /// Height of the committed block containing this transaction;
+    /// absent while it is still pending.
+    async fn height(&self) -> Option<u64> {
+        self.height
+    }
+
+    async fn block(&self, ctx: &Context<'_>) -> Option<GqlBlock> {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        let height = self.height?;
+        let state = data.engine.state.read().await;
+        state
+            .blocks
+            .iter()
+            .find(|block| block.header.height == height)
+            .cloned()
+            .map(GqlBlock)
+    }
+}
+
+pub struct GqlAccount {
+    address: String,
+    balance: u64,
+    nonce: u64,
+    frozen: bool,
+}
+
+#[Object(name = "Account")]
+impl GqlAccount {
+    async fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    async fn balance(&self) -> u64 {
+        self.balance
+    }
+
+    async fn nonce(&self) -> u64 {
+        self.nonce
+    }
+
+    async fn frozen(&self) -> bool {
+        self.frozen
+    }
+}
+
+pub struct GqlValidator {
+    address: String,
+    public_key: String,
+    voting_power: u64,
+    proposer_priority: i64,
+    jailed: bool,
+}
+
+#[Object(name = "Validator")]
+impl GqlValidator {
+    async fn address(&self) -> String {
+        self.address.clone()
+    }
+
+    async fn public_key(&self) -> String {
+        self.public_key.clone()
+    }
+
+    async fn voting_power(&self) -> u64 {
+        self.voting_power
+    }
+
+    async fn proposer_priority(&self) -> i64 {
+        self.proposer_priority
+    }
+
+    async fn jailed(&self) -> bool {
+        self.jailed
+    }
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The latest committed height.
+    async fn height(&self, ctx: &Context<'_>) -> u64 {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        data.engine.height().await
+    }
+
+    /// A retained block by height.
+    async fn block(&self, ctx: &Context<'_>, height: u64) -> Option<GqlBlock> {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        let state = data.engine.state.read().await;
+        state
+            .blocks
+            .iter()
+            .find(|block| block.header.height == height)
+            .cloned()
+            .map(GqlBlock)
+    }
+
+    /// Retained blocks, newest first, starting at or below `before`.
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        before: Option<u64>,
+        limit: Option<usize>,
+    ) -> Vec<GqlBlock> {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        let limit = limit.unwrap_or(20).min(MAX_PAGE);
+        let state = data.engine.state.read().await;
+        state
+            .blocks
+            .iter()
+            .rev()
+            .filter(|block| before.is_none_or(|cap| block.header.height <= cap))
+            .take(limit)
+            .cloned()
+            .map(GqlBlock)
+            .collect()
+    }
+
+    /// A transaction by hash: committed blocks first, then the mempool.
+    async fn transaction(&self, ctx: &Context<'_>, hash: String) -> Option<GqlTransaction> {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        {
+            let state = data.engine.state.read().await;
+            for block in state.blocks.iter().rev() {
+                if let Some(tx) = block.transactions.iter().find(|tx| tx.hash() == hash) {
+                    return Some(GqlTransaction {
+                        tx: tx.clone(),
+                        height: Some(block.header.height),
+                    });
+                }
+            }
+        }
+        data.pool
+            .pending()
+            .await
+            .into_iter()
+            .find(|tx| tx.hash() == hash)
+            .map(|tx| GqlTransaction { tx, height: None })
+    }
+
+    /// An account's balance and nonce.
+    async fn account(&self, ctx: &Context<'_>, address: String) -> Option<GqlAccount> {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        let account = data.state.get_account(&address).await?;
+        Some(GqlAccount {
+            address,
+            balance: account.balance,
+            nonce: account.nonce,
+            frozen: account.frozen,
+        })
+    }
+
+    /// The current validator set.
+    async fn validators(&self, ctx: &Context<'_>) -> Vec<GqlValidator> {
+        let data = ctx.data_unchecked::<web::Data<ApiState>>();
+        let set = data.engine.validators.read().await.clone();
+        let liveness = data.engine.liveness.read().await;
+        set.validators
+            .iter()
+            .map(|validator| GqlValidator {
+                address: validator.address.clone(),
+                public_key: hex::encode(&validator.public_key),
+                voting_power: validator.voting_power,
+                proposer_priority: validator.proposer_priority,
+                jailed: liveness.is_jailed(&validator.address),
+            })
+            .collect()
+    }
+}
+
+type ChainSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+/// The schema is stateless; handles reach the node through
+/// request-scoped data, so one instance serves every request.
+fn schema() -> &'static ChainSchema {
+    static SCHEMA: OnceLock<ChainSchema> = OnceLock::new();
+    SCHEMA.get_or_init(|| Schema::new(QueryRoot, EmptyMutation, EmptySubscription))
+}
+
+/// `POST /api/graphql` handler.
+pub async fn handle(data: web::Data<ApiState>, request: GraphQLRequest) -> GraphQLResponse {
+    schema().execute(request.into_inner().data(data)).await.into()
+}
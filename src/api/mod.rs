@@ -0,0 +1,732 @@
+pub mod admin;
+pub mod auth;
+pub mod eth;
+pub mod graphql;
+pub mod proxy;
+
+use std::sync::Arc;
+
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::consensus::ConsensusEngine;
+use crate::errors::{ErrorCode, ErrorEnvelope};
+use crate::metrics::Metrics;
+use crate::network::connection::ConnectionManager;
+use crate::network::health::HealthMonitor;
+use crate::network::NetworkManager;
+use crate::security::network::NetworkSecurityManager;
+use crate::security::state::{MultisigCollector, MultisigParams, StateSecurityManager};
+use crate::types::{Transaction, TransactionPool, TxStatus, TxTracker};
+
+/// Shared handles the API handlers operate on.
+pub struct ApiState {
+    pub engine: Arc<ConsensusEngine>,
+    pub pool: Arc<TransactionPool>,
+    pub state: Arc<StateSecurityManager>,
+    pub network: Arc<NetworkManager>,
+    pub metrics: Arc<Metrics>,
+    pub tracker: Arc<TxTracker>,
+    pub health: Arc<HealthMonitor>,
+    /// Multisig transactions awaiting partial signatures.
+    pub multisig: Arc<MultisigCollector>,
+    /// Directory holding the validator key files, where rotation
+    /// persists the pending key.
+    pub keys_dir: std::path::PathBuf,
+    pub connections: Arc<ConnectionManager>,
+    pub network_security: Arc<NetworkSecurityManager>,
+    /// Bearer token for the admin scope; `None` disables it.
+    pub admin_token: Option<String>,
+    /// API keys accepted by the privileged-route middleware.
+    pub api_keys: Vec<String>,
+    /// Tokens granted per faucet request; `None` disables the faucet.
+    pub faucet_amount: Option<u64>,
+}
+
+/// Register all API routes.
+pub fn routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api")
+            .route("/status", web::get().to(get_status))
+            .route("/checkpoint", web::get().to(get_checkpoint))
+            .route("/block/{height}", web::get().to(get_block))
+            .route("/block/{height}/results", web::get().to(get_block_results))
+            .route("/transaction", web::post().to(submit_transaction))
+            .route("/tx/raw", web::post().to(submit_raw_transaction))
+            .route("/transaction/{id}", web::get().to(get_transaction))
+            .route("/account/{address}", web::get().to(get_account))
+            .route("/account/{address}/proof", web::get().to(get_account_proof))
+            .route("/tx/{hash}/status", web::get().to(get_tx_status))
+            .route("/tx/{hash}/wait", web::get().to(wait_for_tx))
+            .route("/mempool", web::get().to(get_mempool))
+            .route("/mempool/content", web::get().to(get_mempool_content))
+            .route("/ws/tx", web::get().to(ws_tx_updates))
+            .route("/ws/events", web::get().to(ws_chain_events))
+            .route("/multisig/account", web::post().to(create_multisig_account))
+            .route("/multisig/transaction", web::post().to(stage_multisig))
+            .route("/multisig/transaction/{hash}", web::get().to(get_multisig))
+            .route(
+                "/multisig/transaction/{hash}/sign",
+                web::post().to(sign_multisig),
+            )
+            .route("/validators", web::get().to(get_validators))
+            .route("/graphql", web::post().to(graphql::handle))
+            .route("/rpc", web::post().to(eth::handle))
+            .route("/tokens", web::get().to(get_tokens))
+            .route("/tokens/{denom}", web::get().to(get_token))
+            .route("/slashes", web::get().to(get_slashes))
+            .route("/metrics", web::get().to(get_metrics))
+            // Privileged routes: anything past this point mutates node
+            // state and requires an API key or the admin token.
+            .service(
+                web::scope("")
+                    .wrap(actix_web::middleware::from_fn(auth::require_privileged))
+                    .route("/faucet", web::post().to(faucet))
+                    .service(admin::scope()),
+            ),
+    );
+}
+
+async fn get_status(data: web::Data<ApiState>) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "height": data.engine.height().await,
+        "mempool_size": data.pool.len().await,
+        "peer_count": data.network.peer_count().await,
+        "network_id": data.network.config.network_id,
+        "genesis_hash": data.network.genesis_hash,
+        "network_health": data.health.report().await,
+    }))
+}
+
+/// The latest finalized checkpoint: nothing at or below this height can
+/// change, so consumers may treat it as irreversible.
+async fn get_checkpoint(data: web::Data<ApiState>) -> impl Responder {
+    let committed = data.engine.committed_state().await;
+    HttpResponse::Ok().json(json!({
+        "height": committed.height,
+        "block_hash": hex::encode(&committed.block_hash),
+    }))
+}
+
+async fn get_block(data: web::Data<ApiState>, path: web::Path<u64>) -> impl Responder {
+    let height = path.into_inner();
+    let state = data.engine.state.read().await;
+    match state.blocks.iter().find(|b| b.header.height == height) {
+        Some(block) => HttpResponse::Ok().json(block),
+        None => HttpResponse::NotFound()
+            .json(ErrorEnvelope::new(ErrorCode::NotFound, "block not found")),
+    }
+}
+
+async fn get_block_results(data: web::Data<ApiState>, path: web::Path<u64>) -> impl Responder {
+    let height = path.into_inner();
+    let state = data.engine.state.read().await;
+    match state.results.iter().find(|r| r.height == height) {
+        Some(results) => HttpResponse::Ok().json(results),
+        None => HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NotFound,
+            "block results not found",
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct SubmitTransaction {
+    sender: String,
+    recipient: String,
+    amount: u64,
+    #[serde(default)]
+    denom: String,
+    nonce: u64,
+    #[serde(default)]
+    gas_limit: u64,
+    #[serde(default)]
+    gas_price: u64,
+    #[serde(default)]
+    fee_denom: String,
+    #[serde(default)]
+    fee_payer: String,
+    #[serde(default)]
+    fee_payer_signature: Vec<u8>,
+    #[serde(default)]
+    data: Vec<u8>,
+    #[serde(default)]
+    scheme: crate::security::scheme::SignatureScheme,
+    #[serde(default)]
+    signature: Vec<u8>,
+    #[serde(default)]
+    id: String,
+}
+
+async fn submit_transaction(
+    data: web::Data<ApiState>,
+    body: web::Json<SubmitTransaction>,
+) -> impl Responder {
+    let body = body.into_inner();
+    let mut tx = Transaction::new(
+        body.sender,
+        body.recipient,
+        body.amount,
+        body.nonce,
+        body.gas_limit,
+        body.gas_price,
+        body.data,
+    );
+    tx.denom = body.denom;
+    tx.scheme = body.scheme;
+    tx.signature = body.signature;
+    tx.id = body.id;
+    tx.fee_denom = body.fee_denom;
+    tx.fee_payer = body.fee_payer;
+    tx.fee_payer_signature = body.fee_payer_signature;
+    let hash = tx.hash();
+    data.tracker.record(&hash, TxStatus::Received).await;
+    match data.pool.add_transaction(tx).await {
+        Ok(()) => {
+            data.tracker.record(&hash, TxStatus::Checked).await;
+            // The pool canonicalizes the id to the hash on admission.
+            HttpResponse::Ok().json(json!({ "id": hash, "hash": hash }))
+        }
+        Err(err) => {
+            data.tracker
+                .record(
+                    &hash,
+                    TxStatus::Failed {
+                        reason: err.to_string(),
+                    },
+                )
+                .await;
+            HttpResponse::BadRequest().json(ErrorEnvelope::from_err(&err))
+        }
+    }
+}
+
+async fn get_tx_status(data: web::Data<ApiState>, path: web::Path<String>) -> impl Responder {
+    match data.tracker.get(&path.into_inner()).await {
+        Some(record) => HttpResponse::Ok().json(record),
+        None => HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NotFound,
+            "transaction not tracked",
+        )),
+    }
+}
+
+/// Longest wait `/tx/{hash}/wait` will hold a connection open.
+const MAX_WAIT_SECS: u64 = 120;
+
+#[derive(Deserialize)]
+struct WaitQuery {
+    #[serde(default = "default_wait_secs")]
+    timeout_secs: u64,
+}
+
+fn default_wait_secs() -> u64 {
+    30
+}
+
+/// Long-poll until a transaction is committed, answering with its block
+/// height and execution receipt. Resolves immediately when the tx is
+/// already final; answers 408 when the timeout elapses first and 422
+/// when the tx fails or is evicted instead of committing.
+async fn wait_for_tx(
+    data: web::Data<ApiState>,
+    path: web::Path<String>,
+    query: web::Query<WaitQuery>,
+) -> impl Responder {
+    let hash = path.into_inner();
+    // Subscribe before the initial check so a commit landing in between
+    // cannot be missed.
+    let mut updates = data.tracker.subscribe();
+    if let Some(record) = data.tracker.get(&hash).await {
+        match record.status {
+            TxStatus::Committed { height } => return committed_response(&data, &hash, height).await,
+            TxStatus::Failed { reason } => return failed_response(&reason),
+            TxStatus::Evicted => return failed_response("evicted from mempool"),
+            _ => {}
+        }
+    }
+    let deadline = std::time::Instant::now()
+        + std::time::Duration::from_secs(query.timeout_secs.clamp(1, MAX_WAIT_SECS));
+    while let Some(remaining) = deadline.checked_duration_since(std::time::Instant::now()) {
+        let update = match tokio::time::timeout(remaining, updates.recv()).await {
+            Ok(Ok(update)) => update,
+            // Lagged subscribers lose updates; re-check the record.
+            Ok(Err(tokio::sync::broadcast::error::RecvError::Lagged(_))) => {
+                match data.tracker.get(&hash).await.map(|record| record.status) {
+                    Some(TxStatus::Committed { height }) => {
+                        return committed_response(&data, &hash, height).await
+                    }
+                    Some(TxStatus::Failed { reason }) => return failed_response(&reason),
+                    Some(TxStatus::Evicted) => return failed_response("evicted from mempool"),
+                    _ => continue,
+                }
+            }
+            _ => break,
+        };
+        if update.hash != hash {
+            continue;
+        }
+        match update.status {
+            TxStatus::Committed { height } => return committed_response(&data, &hash, height).await,
+            TxStatus::Failed { reason } => return failed_response(&reason),
+            TxStatus::Evicted => return failed_response("evicted from mempool"),
+            _ => {}
+        }
+    }
+    HttpResponse::RequestTimeout().json(ErrorEnvelope::new(
+        ErrorCode::Timeout,
+        "transaction not committed within the wait window",
+    ))
+}
+
+/// Accept a pre-signed transaction blob produced offline by
+/// `artha tx sign`, after checking it was signed for this chain.
+async fn submit_raw_transaction(data: web::Data<ApiState>, body: web::Bytes) -> impl Responder {
+    let raw = String::from_utf8_lossy(&body);
+    let blob = match crate::client::SignedTxBlob::decode(&raw) {
+        Ok(blob) => blob,
+        Err(err) => {
+            return HttpResponse::BadRequest()
+                .json(ErrorEnvelope::new(ErrorCode::InvalidRequest, err.to_string()))
+        }
+    };
+    if blob.chain_id != data.network.config.network_id {
+        return HttpResponse::BadRequest().json(ErrorEnvelope::new(
+            ErrorCode::InvalidRequest,
+            format!(
+                "blob signed for chain {} but this node runs {}",
+                blob.chain_id, data.network.config.network_id
+            ),
+        ));
+    }
+    let tx = blob.tx;
+    let hash = tx.hash();
+    data.tracker.record(&hash, TxStatus::Received).await;
+    match data.pool.add_transaction(tx).await {
+        Ok(()) => {
+            data.tracker.record(&hash, TxStatus::Checked).await;
+            // The pool canonicalizes the id to the hash on admission.
+            HttpResponse::Ok().json(json!({ "id": hash, "hash": hash }))
+        }
+        Err(err) => {
+            data.tracker
+                .record(
+                    &hash,
+                    TxStatus::Failed {
+                        reason: err.to_string(),
+                    },
+                )
+                .await;
+            HttpResponse::BadRequest().json(ErrorEnvelope::from_err(&err))
+        }
+    }
+}
+
+async fn committed_response(data: &ApiState, hash: &str, height: u64) -> HttpResponse {
+    let state = data.engine.state.read().await;
+    let receipt = state
+        .results
+        .iter()
+        .find(|results| results.height == height)
+        .and_then(|results| results.receipts.iter().find(|r| r.tx_hash == hash));
+    HttpResponse::Ok().json(json!({
+        "hash": hash,
+        "height": height,
+        "receipt": receipt,
+    }))
+}
+
+fn failed_response(reason: &str) -> HttpResponse {
+    HttpResponse::UnprocessableEntity().json(ErrorEnvelope::new(ErrorCode::TxInvalid, reason))
+}
+
+/// Mempool summary: occupancy and the pending gas-price distribution,
+/// so operators and wallets can gauge congestion without downloading
+/// every pending transaction.
+async fn get_mempool(data: web::Data<ApiState>) -> impl Responder {
+    let pending = data.pool.pending().await;
+    let bytes: usize = pending
+        .iter()
+        .map(|tx| serde_json::to_vec(tx).map(|b| b.len()).unwrap_or(0))
+        .sum();
+    let mut prices: Vec<u64> = pending.iter().map(|tx| tx.gas_price).collect();
+    prices.sort_unstable();
+    let fees = (!prices.is_empty()).then(|| {
+        json!({
+            "min_gas_price": prices[0],
+            "median_gas_price": prices[prices.len() / 2],
+            "max_gas_price": prices[prices.len() - 1],
+        })
+    });
+    HttpResponse::Ok().json(json!({
+        "count": pending.len(),
+        "bytes": bytes,
+        "fees": fees,
+    }))
+}
+
+/// Page size for `/mempool/content`.
+const MEMPOOL_PAGE_SIZE: usize = 50;
+
+#[derive(Deserialize)]
+struct PageQuery {
+    #[serde(default)]
+    page: usize,
+}
+
+/// One page of pending transactions, in the same deterministic fee
+/// order the next block would include them.
+async fn get_mempool_content(
+    data: web::Data<ApiState>,
+    query: web::Query<PageQuery>,
+) -> impl Responder {
+    let pending = data.pool.pending().await;
+    let total = pending.len();
+    let transactions: Vec<&Transaction> = pending
+        .iter()
+        .skip(query.page * MEMPOOL_PAGE_SIZE)
+        .take(MEMPOOL_PAGE_SIZE)
+        .collect();
+    HttpResponse::Ok().json(json!({
+        "page": query.page,
+        "page_size": MEMPOOL_PAGE_SIZE,
+        "total": total,
+        "transactions": transactions,
+    }))
+}
+
+/// WebSocket stream of chain events (blocks, commits, validator set
+/// changes, evidence) straight off the consensus event bus.
+async fn ws_chain_events(
+    req: HttpRequest,
+    body: web::Payload,
+    data: web::Data<ApiState>,
+) -> actix_web::Result<HttpResponse> {
+    let (response, mut session, mut msg_stream) = actix_ws::handle(&req, body)?;
+    let mut events = data.engine.events.subscribe();
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                event = events.recv() => match event {
+                    Ok(event) => {
+                        let payload = serde_json::to_string(&event).unwrap_or_default();
+                        if session.text(payload).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(_) => break,
+                },
+                message = msg_stream.next() => match message {
+                    Some(Ok(actix_ws::Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) => break,
+                },
+            }
+        }
+        let _ = session.close(None).await;
+    });
+    Ok(response)
+}
+
+/// WebSocket stream of transaction status updates.
+async fn ws_tx_updates(
+    req: HttpRequest,
+    body: web::Payload,
+    data: web::Data<ApiState>,
+) -> actix_web::Result<HttpResponse> {
+    let (response, mut session, mut msg_stream) = actix_ws::handle(&req, body)?;
+    let mut updates = data.tracker.subscribe();
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                update = updates.recv() => match update {
+                    Ok(update) => {
+                        let payload = serde_json::to_string(&update).unwrap_or_default();
+                        if session.text(payload).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(_) => break,
+                },
+                message = msg_stream.next() => match message {
+                    Some(Ok(actix_ws::Message::Close(_))) | None => break,
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) => break,
+                },
+            }
+        }
+        let _ = session.close(None).await;
+    });
+    Ok(response)
+}
+
+async fn get_transaction(data: web::Data<ApiState>, path: web::Path<String>) -> impl Responder {
+    match data.pool.get_transaction(&path.into_inner()).await {
+        Some(tx) => HttpResponse::Ok().json(tx),
+        None => HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NotFound,
+            "transaction not found",
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct AccountQuery {
+    /// When set, return the account's state as of this height.
+    height: Option<u64>,
+}
+
+async fn get_account(
+    data: web::Data<ApiState>,
+    path: web::Path<String>,
+    query: web::Query<AccountQuery>,
+) -> impl Responder {
+    let address = path.into_inner();
+    let account = match query.height {
+        Some(height) => data.state.get_account_at(&address, height).await,
+        None => data.state.get_account(&address).await,
+    };
+    match account {
+        Some(account) => HttpResponse::Ok().json(account),
+        None => HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NotFound,
+            "account not found",
+        )),
+    }
+}
+
+#[derive(Deserialize)]
+struct FaucetRequest {
+    address: String,
+}
+
+/// Credit devnet test tokens to an account. Behind the privileged-route
+/// middleware, only active when a faucet amount is configured, and
+/// never on a network whose id marks it as mainnet — on such networks
+/// the route answers 404 as if absent.
+async fn faucet(data: web::Data<ApiState>, body: web::Json<FaucetRequest>) -> impl Responder {
+    let amount = match data.faucet_amount {
+        Some(amount) if !data.network.config.network_id.contains("mainnet") => amount,
+        _ => {
+            return HttpResponse::NotFound().json(ErrorEnvelope::new(
+                ErrorCode::NotFound,
+                "faucet not enabled on this network",
+            ))
+        }
+    };
+    data.state.credit(&body.address, amount).await;
+    let balance = data
+        .state
+        .get_account(&body.address)
+        .await
+        .map_or(0, |account| account.balance);
+    HttpResponse::Ok().json(json!({
+        "address": body.address,
+        "credited": amount,
+        "balance": balance,
+    }))
+}
+
+/// Return the account (if any) plus a Merkle proof against the state
+/// root. An absent account yields an exclusion proof, so light clients
+/// get a verifiable answer either way.
+async fn get_account_proof(
+    data: web::Data<ApiState>,
+    path: web::Path<String>,
+    query: web::Query<AccountQuery>,
+) -> impl Responder {
+    let address = path.into_inner();
+    let tree = match query.height {
+        Some(height) => data.state.state_tree_at(height).await,
+        None => data.state.state_tree().await,
+    };
+    let account = match query.height {
+        Some(height) => data.state.get_account_at(&address, height).await,
+        None => data.state.get_account(&address).await,
+    };
+    let proof = tree.prove(address.as_bytes());
+    HttpResponse::Ok().json(json!({
+        "address": address,
+        "account": account,
+        "state_root": hex::encode(tree.root()),
+        "proof": proof,
+    }))
+}
+
+/// Register a multisig account from its member keys and threshold.
+async fn create_multisig_account(
+    data: web::Data<ApiState>,
+    body: web::Json<MultisigParams>,
+) -> impl Responder {
+    match data.state.register_multisig(body.into_inner()).await {
+        Ok(address) => HttpResponse::Ok().json(json!({ "address": address })),
+        Err(err) => HttpResponse::BadRequest().json(ErrorEnvelope::from_err(&err)),
+    }
+}
+
+/// Stage a multisig transaction for signature collection. Members sign
+/// the returned `signing_bytes` and submit through the sign endpoint.
+async fn stage_multisig(
+    data: web::Data<ApiState>,
+    body: web::Json<SubmitTransaction>,
+) -> impl Responder {
+    let body = body.into_inner();
+    let mut tx = Transaction::new(
+        body.sender,
+        body.recipient,
+        body.amount,
+        body.nonce,
+        body.gas_limit,
+        body.gas_price,
+        body.data,
+    );
+    tx.denom = body.denom;
+    tx.id = body.id;
+    tx.fee_denom = body.fee_denom;
+    tx.fee_payer = body.fee_payer;
+    tx.fee_payer_signature = body.fee_payer_signature;
+    let signing_bytes = tx.signing_bytes();
+    let hash = data.multisig.stage(tx).await;
+    HttpResponse::Ok().json(json!({ "hash": hash, "signing_bytes": signing_bytes }))
+}
+
+async fn get_multisig(data: web::Data<ApiState>, path: web::Path<String>) -> impl Responder {
+    match data.multisig.get(&path.into_inner()).await {
+        Some(tx) => HttpResponse::Ok().json(tx),
+        None => HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NotFound,
+            "multisig transaction not staged",
+        )),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct PartialSignature {
+    signature: Vec<u8>,
+}
+
+/// Add one member's signature. Once the sender account's threshold is
+/// reached the transaction is broadcast and removed from staging.
+async fn sign_multisig(
+    data: web::Data<ApiState>,
+    path: web::Path<String>,
+    body: web::Json<PartialSignature>,
+) -> impl Responder {
+    let hash = path.into_inner();
+    let Some(tx) = data
+        .multisig
+        .add_signature(&hash, body.into_inner().signature)
+        .await
+    else {
+        return HttpResponse::NotFound().json(ErrorEnvelope::new(
+            ErrorCode::NotFound,
+            "multisig transaction not staged",
+        ));
+    };
+    let threshold_met = match data.state.get_account(&tx.sender).await {
+        Some(account) => account
+            .multisig
+            .is_some_and(|params| params.verify(&tx.signing_bytes(), &tx.signatures)),
+        None => false,
+    };
+    if !threshold_met {
+        return HttpResponse::Ok().json(json!({
+            "hash": hash,
+            "signatures": tx.signatures.len(),
+            "broadcast": false,
+        }));
+    }
+    data.multisig.remove(&hash).await;
+    match data.pool.add_transaction(tx.clone()).await {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "hash": hash,
+            "signatures": tx.signatures.len(),
+            "broadcast": true,
+        })),
+        Err(err) => HttpResponse::BadRequest().json(ErrorEnvelope::from_err(&err)),
+    }
+}
+
+async fn get_tokens(data: web::Data<ApiState>) -> impl Responder {
+    HttpResponse::Ok().json(data.engine.tokens.all().await)
+}
+
+async fn get_token(data: web::Data<ApiState>, path: web::Path<String>) -> impl Responder {
+    match data.engine.tokens.get(&path.into_inner()).await {
+        Some(token) => HttpResponse::Ok().json(token),
+        None => HttpResponse::NotFound()
+            .json(ErrorEnvelope::new(ErrorCode::NotFound, "denom not found")),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ValidatorsQuery {
+    height: Option<u64>,
+}
+
+/// The validator set, current or as of a historical height still in the
+/// retained snapshots. The jailed flag always reflects the present
+/// liveness tracker, as jail history is not versioned.
+async fn get_validators(
+    data: web::Data<ApiState>,
+    query: web::Query<ValidatorsQuery>,
+) -> impl Responder {
+    let set = match query.height {
+        Some(height) => match data.engine.validator_set_at(height).await {
+            Some(set) => set,
+            None => {
+                return HttpResponse::NotFound().json(ErrorEnvelope::new(
+                    ErrorCode::NotFound,
+                    "no validator set retained for that height",
+                ))
+            }
+        },
+        None => data.engine.validators.read().await.clone(),
+    };
+    let liveness = data.engine.liveness.read().await;
+    let validators: Vec<serde_json::Value> = set
+        .validators
+        .iter()
+        .map(|validator| {
+            json!({
+                "address": validator.address,
+                "public_key": hex::encode(&validator.public_key),
+                "voting_power": validator.voting_power,
+                "proposer_priority": validator.proposer_priority,
+                "jailed": liveness.is_jailed(&validator.address),
+            })
+        })
+        .collect();
+    HttpResponse::Ok().json(json!({
+        "height": query.height,
+        "total_power": set.total_power(),
+        "validators": validators,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SlashQuery {
+    validator: Option<String>,
+}
+
+async fn get_slashes(data: web::Data<ApiState>, query: web::Query<SlashQuery>) -> impl Responder {
+    let events = match &query.validator {
+        Some(validator) => data.engine.slashing.for_validator(validator).await,
+        None => data.engine.slashing.all().await,
+    };
+    HttpResponse::Ok().json(events)
+}
+
+async fn get_metrics(data: web::Data<ApiState>) -> impl Responder {
+    // Sampled gauges are refreshed at scrape time.
+    data.metrics.set_mempool_size(data.pool.len().await as u64);
+    data.metrics
+        .set_peer_count(data.network.peer_count().await as u64);
+    HttpResponse::Ok()
+        .content_type("text/plain; version=0.0.4")
+        .body(data.metrics.encode())
+}
@@ -0,0 +1,260 @@
+//! Stateless public RPC proxy mode.
+//!
+//! A proxy node exposes only the public query/broadcast endpoints and
+//! forwards them to one or more backend full nodes over their HTTP API,
+//! with per-key quotas and short-lived caching of hot queries (status,
+//! blocks). This lets operators scale public RPC horizontally without
+//! running full state everywhere.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use actix_web::{web, App, HttpRequest, HttpResponse, HttpServer, Responder};
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::errors::{ErrorCode, ErrorEnvelope};
+use crate::network::rate_limit::{TokenBucket, TokenBucketConfig};
+
+/// Configuration for proxy mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyConfig {
+    /// Base URLs of backend full nodes, e.g. `http://10.0.0.1:8080`.
+    pub backends: Vec<String>,
+    /// How long cached hot queries stay fresh.
+    pub cache_ttl_ms: u64,
+    /// Requests per second allowed per API key (or client IP).
+    pub requests_per_sec: f64,
+    /// Burst allowance per key.
+    pub burst: f64,
+}
+
+impl Default for ProxyConfig {
+    fn default() -> Self {
+        Self {
+            backends: Vec::new(),
+            cache_ttl_ms: 1000,
+            requests_per_sec: 10.0,
+            burst: 20.0,
+        }
+    }
+}
+
+struct CachedResponse {
+    fetched_at: Instant,
+    status: u16,
+    body: serde_json::Value,
+}
+
+/// Shared state of the proxy server.
+pub struct ProxyState {
+    config: ProxyConfig,
+    client: reqwest::Client,
+    quotas: RwLock<HashMap<String, Arc<TokenBucket>>>,
+    cache: RwLock<HashMap<String, CachedResponse>>,
+    next_backend: AtomicUsize,
+}
+
+impl ProxyState {
+    pub fn new(config: ProxyConfig) -> Self {
+        Self {
+            config,
+            client: reqwest::Client::new(),
+            quotas: RwLock::new(HashMap::new()),
+            cache: RwLock::new(HashMap::new()),
+            next_backend: AtomicUsize::new(0),
+        }
+    }
+
+    /// Quota key: the API key header when present, the client IP otherwise.
+    fn quota_key(req: &HttpRequest) -> String {
+        if let Some(key) = req.headers().get("x-api-key").and_then(|v| v.to_str().ok()) {
+            return format!("key:{key}");
+        }
+        req.peer_addr()
+            .map(|a| format!("ip:{}", a.ip()))
+            .unwrap_or_else(|| "ip:unknown".to_string())
+    }
+
+    async fn check_quota(&self, req: &HttpRequest) -> bool {
+        let key = Self::quota_key(req);
+        let bucket = {
+            let quotas = self.quotas.read().await;
+            quotas.get(&key).cloned()
+        };
+        let bucket = match bucket {
+            Some(bucket) => bucket,
+            None => {
+                let mut quotas = self.quotas.write().await;
+                Arc::clone(quotas.entry(key).or_insert_with(|| {
+                    Arc::new(TokenBucket::new(TokenBucketConfig {
+                        messages_per_sec: self.config.requests_per_sec,
+                        bytes_per_sec: f64::MAX,
+                        message_burst: self.config.burst,
+                        byte_burst: f64::MAX,
+                    }))
+                }))
+            }
+        };
+        bucket.allow(0)
+    }
+
+    /// Forward a GET to a backend, trying each one in round-robin order.
+    async fn forward_get(&self, path: &str) -> (u16, serde_json::Value) {
+        let backends = &self.config.backends;
+        if backends.is_empty() {
+            return (
+                502,
+                serde_json::to_value(ErrorEnvelope::new(
+                    ErrorCode::Internal,
+                    "no backends configured",
+                ))
+                .unwrap_or_default(),
+            );
+        }
+        let start = self.next_backend.fetch_add(1, Ordering::Relaxed);
+        for offset in 0..backends.len() {
+            let backend = &backends[(start + offset) % backends.len()];
+            let url = format!("{backend}{path}");
+            match self.client.get(&url).send().await {
+                Ok(response) => {
+                    let status = response.status().as_u16();
+                    let body = response.json().await.unwrap_or_default();
+                    return (status, body);
+                }
+                Err(err) => log::warn!("backend {backend} failed: {err}"),
+            }
+        }
+        (
+            502,
+            serde_json::to_value(ErrorEnvelope::new(
+                ErrorCode::Internal,
+                "all backends unreachable",
+            ))
+            .unwrap_or_default(),
+        )
+    }
+
+    /// Forward a GET with caching for hot paths.
+    async fn forward_get_cached(&self, path: &str) -> (u16, serde_json::Value) {
+        let ttl = Duration::from_millis(self.config.cache_ttl_ms);
+        {
+            let cache = self.cache.read().await;
+            if let Some(entry) = cache.get(path) {
+                if entry.fetched_at.elapsed() < ttl {
+                    return (entry.status, entry.body.clone());
+                }
+            }
+        }
+        let (status, body) = self.forward_get(path).await;
+        if status == 200 {
+            self.cache.write().await.insert(
+                path.to_string(),
+                CachedResponse {
+                    fetched_at: Instant::now(),
+                    status,
+                    body: body.clone(),
+                },
+            );
+        }
+        (status, body)
+    }
+}
+
+fn json_response(status: u16, body: serde_json::Value) -> HttpResponse {
+    HttpResponse::build(
+        actix_web::http::StatusCode::from_u16(status)
+            .unwrap_or(actix_web::http::StatusCode::BAD_GATEWAY),
+    )
+    .json(body)
+}
+
+fn quota_exceeded() -> HttpResponse {
+    HttpResponse::TooManyRequests().json(ErrorEnvelope::new(
+        ErrorCode::Internal,
+        "rate limit exceeded for this key",
+    ))
+}
+
+async fn proxy_status(req: HttpRequest, data: web::Data<ProxyState>) -> impl Responder {
+    if !data.check_quota(&req).await {
+        return quota_exceeded();
+    }
+    let (status, body) = data.forward_get_cached("/api/status").await;
+    json_response(status, body)
+}
+
+async fn proxy_block(
+    req: HttpRequest,
+    data: web::Data<ProxyState>,
+    path: web::Path<u64>,
+) -> impl Responder {
+    if !data.check_quota(&req).await {
+        return quota_exceeded();
+    }
+    let (status, body) = data
+        .forward_get_cached(&format!("/api/block/{}", path.into_inner()))
+        .await;
+    json_response(status, body)
+}
+
+async fn proxy_get(req: HttpRequest, data: web::Data<ProxyState>) -> impl Responder {
+    if !data.check_quota(&req).await {
+        return quota_exceeded();
+    }
+    let (status, body) = data.forward_get(req.path()).await;
+    json_response(status, body)
+}
+
+async fn proxy_broadcast(
+    req: HttpRequest,
+    data: web::Data<ProxyState>,
+    body: web::Json<serde_json::Value>,
+) -> impl Responder {
+    if !data.check_quota(&req).await {
+        return quota_exceeded();
+    }
+    let backends = &data.config.backends;
+    if backends.is_empty() {
+        return json_response(502, serde_json::json!({ "error": "no backends configured" }));
+    }
+    let start = data.next_backend.fetch_add(1, Ordering::Relaxed);
+    for offset in 0..backends.len() {
+        let backend = &backends[(start + offset) % backends.len()];
+        let url = format!("{backend}/api/transaction");
+        match data.client.post(&url).json(&body).send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                let body = response.json().await.unwrap_or_default();
+                return json_response(status, body);
+            }
+            Err(err) => log::warn!("backend {backend} failed: {err}"),
+        }
+    }
+    json_response(502, serde_json::json!({ "error": "all backends unreachable" }))
+}
+
+/// Public routes exposed in proxy mode.
+pub fn proxy_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/api")
+            .route("/status", web::get().to(proxy_status))
+            .route("/block/{height}", web::get().to(proxy_block))
+            .route("/transaction", web::post().to(proxy_broadcast))
+            .route("/transaction/{id}", web::get().to(proxy_get))
+            .route("/account/{address}", web::get().to(proxy_get))
+            .route("/tx/{hash}/status", web::get().to(proxy_get)),
+    );
+}
+
+/// Run the proxy server until shutdown.
+pub async fn run_proxy(bind_address: &str, config: ProxyConfig) -> std::io::Result<()> {
+    let state = web::Data::new(ProxyState::new(config));
+    log::info!("rpc proxy listening on {bind_address}");
+    HttpServer::new(move || App::new().app_data(state.clone()).configure(proxy_routes))
+        .bind(bind_address)?
+        .run()
+        .await
+}
@@ -0,0 +1,444 @@
+//! Embeddable wallet and RPC client.
+//!
+//! Lets Rust applications construct, sign, and submit transactions
+//! against a running node without reimplementing the canonical
+//! serialization or the keyring layout. [`TxBuilder`] assembles a
+//! transaction, [`Keystore`] loads signing keys from the same
+//! `keys/<name>.json` files the CLI keyring writes, and [`RpcClient`]
+//! talks to the node's HTTP API, including fee estimation and nonce
+//! discovery for the common transfer path.
+
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::consensus::TxReceipt;
+use crate::security::signer::Signer;
+use crate::security::SecurityManager;
+use crate::types::Transaction;
+use crate::wallet::Wallet;
+
+/// Gas limit applied to a plain transfer when the builder is not given
+/// one explicitly.
+pub const TRANSFER_GAS_LIMIT: u64 = 21_000;
+
+/// Errors produced by the client library.
+#[derive(Debug, Error)]
+pub enum ClientError {
+    #[error("no key named {0} in keystore")]
+    KeyNotFound(String),
+    #[error("invalid key material: {0}")]
+    InvalidKey(String),
+    #[error("signer backend error: {0}")]
+    Signer(String),
+    #[error("transaction is missing {0}; set it on the builder or submit via RpcClient::send")]
+    Incomplete(&'static str),
+    #[error("http error: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("node rejected request ({code}): {message}")]
+    Rejected { code: String, message: String },
+    #[error("unexpected response: {0}")]
+    Malformed(String),
+}
+
+/// One key file as written by the CLI keyring; the mnemonic is the
+/// source of truth and the rest is ignored here.
+#[derive(Deserialize)]
+struct StoredKey {
+    phrase: String,
+}
+
+/// Read-only view over a directory of CLI keyring files.
+pub struct Keystore {
+    dir: PathBuf,
+}
+
+impl Keystore {
+    /// Open the `keys` directory under a node home (or any directory of
+    /// `<name>.json` keyring records). The directory need not exist yet;
+    /// lookups in a missing directory simply find no keys.
+    pub fn open(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Load the named key and derive its first account for signing.
+    pub fn signer(&self, name: &str) -> Result<SecurityManager, ClientError> {
+        let raw = std::fs::read(self.dir.join(format!("{name}.json")))
+            .map_err(|_| ClientError::KeyNotFound(name.to_string()))?;
+        let record: StoredKey = serde_json::from_slice(&raw)
+            .map_err(|err| ClientError::InvalidKey(err.to_string()))?;
+        let wallet = Wallet::from_phrase(&record.phrase)
+            .map_err(|err| ClientError::InvalidKey(err.to_string()))?;
+        Ok(wallet.account(0))
+    }
+
+    /// Names of every key in the keystore.
+    pub fn list(&self) -> Vec<String> {
+        let Ok(entries) = std::fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        let mut names: Vec<String> = entries
+            .filter_map(|entry| {
+                let path = entry.ok()?.path();
+                (path.extension()? == "json")
+                    .then(|| path.file_stem()?.to_str().map(str::to_string))?
+            })
+            .collect();
+        names.sort();
+        names
+    }
+}
+
+/// Assembles a [`Transaction`] field by field. Fields that depend on
+/// chain state — the nonce and the gas price — can be left unset when
+/// submitting through [`RpcClient::send`], which fills them from the
+/// node; [`TxBuilder::sign`] requires an explicit nonce so fully
+/// offline construction stays deterministic.
+#[derive(Debug, Clone, Default)]
+pub struct TxBuilder {
+    recipient: String,
+    amount: u64,
+    denom: String,
+    nonce: Option<u64>,
+    gas_limit: Option<u64>,
+    gas_price: Option<u64>,
+    data: Vec<u8>,
+}
+
+impl TxBuilder {
+    /// Start a plain transfer of the native asset.
+    pub fn transfer(recipient: impl Into<String>, amount: u64) -> Self {
+        Self {
+            recipient: recipient.into(),
+            amount,
+            ..Self::default()
+        }
+    }
+
+    /// Denominate the transfer in a non-native asset.
+    pub fn denom(mut self, denom: impl Into<String>) -> Self {
+        self.denom = denom.into();
+        self
+    }
+
+    pub fn nonce(mut self, nonce: u64) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn gas_limit(mut self, gas_limit: u64) -> Self {
+        self.gas_limit = Some(gas_limit);
+        self
+    }
+
+    pub fn gas_price(mut self, gas_price: u64) -> Self {
+        self.gas_price = Some(gas_price);
+        self
+    }
+
+    /// Attach an arbitrary payload (module call, batch envelope, memo).
+    pub fn data(mut self, data: Vec<u8>) -> Self {
+        self.data = data;
+        self
+    }
+
+    /// Produce the signed transaction. The sender is the signer's
+    /// address; the signature covers the canonical signing bytes. Any
+    /// [`Signer`] backend works here, including a hardware one.
+    pub fn sign(self, signer: &dyn Signer) -> Result<Transaction, ClientError> {
+        let nonce = self.nonce.ok_or(ClientError::Incomplete("a nonce"))?;
+        let gas_price = self.gas_price.ok_or(ClientError::Incomplete("a gas price"))?;
+        let sender = signer
+            .address()
+            .map_err(|err| ClientError::Signer(err.to_string()))?;
+        let mut tx = Transaction::new(
+            sender,
+            self.recipient,
+            self.amount,
+            nonce,
+            self.gas_limit.unwrap_or(TRANSFER_GAS_LIMIT),
+            gas_price,
+            self.data,
+        );
+        tx.denom = self.denom;
+        tx.signature = signer
+            .sign(&tx.signing_bytes())
+            .map_err(|err| ClientError::Signer(err.to_string()))?;
+        Ok(tx)
+    }
+}
+
+/// A signed transaction bound to a chain id, as produced by
+/// `artha tx sign` and accepted by `POST /api/tx/raw`. The wire form is
+/// hex over the JSON encoding, so blobs survive copy-paste between
+/// air-gapped machines; the receiving node rejects a blob whose
+/// `chain_id` does not match its own network.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTxBlob {
+    pub chain_id: String,
+    pub tx: Transaction,
+}
+
+impl SignedTxBlob {
+    /// Encode as a hex blob for transport.
+    pub fn encode(&self) -> String {
+        hex::encode(serde_json::to_vec(self).unwrap_or_default())
+    }
+
+    /// Decode a hex blob, tolerating surrounding whitespace and an
+    /// optional `0x` prefix.
+    pub fn decode(raw: &str) -> Result<Self, ClientError> {
+        let bytes = hex::decode(raw.trim().trim_start_matches("0x"))
+            .map_err(|err| ClientError::Malformed(format!("invalid hex blob: {err}")))?;
+        serde_json::from_slice(&bytes)
+            .map_err(|err| ClientError::Malformed(format!("invalid signed blob: {err}")))
+    }
+}
+
+/// Account fields the client cares about, out of the API's account
+/// response.
+#[derive(Debug, Clone, Deserialize)]
+pub struct AccountInfo {
+    #[serde(default)]
+    pub balance: u64,
+    #[serde(default)]
+    pub nonce: u64,
+}
+
+/// Result of waiting for a transaction to commit.
+#[derive(Debug, Clone, Deserialize)]
+pub struct TxOutcome {
+    pub hash: String,
+    pub height: u64,
+    pub receipt: Option<TxReceipt>,
+}
+
+/// Async HTTP client for a node's `/api` surface.
+pub struct RpcClient {
+    base_url: String,
+    http: reqwest::Client,
+}
+
+impl RpcClient {
+    /// `base_url` is the node's API root, e.g. `http://localhost:8080`.
+    pub fn new(base_url: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            http: reqwest::Client::new(),
+        }
+    }
+
+    /// Current chain height.
+    pub async fn height(&self) -> Result<u64, ClientError> {
+        let status: serde_json::Value = self
+            .get(&format!("{}/api/status", self.base_url))
+            .await?;
+        status["height"]
+            .as_u64()
+            .ok_or_else(|| ClientError::Malformed("status response has no height".into()))
+    }
+
+    /// Account state, or `None` when the address has never been seen.
+    pub async fn account(&self, address: &str) -> Result<Option<AccountInfo>, ClientError> {
+        let response = self
+            .http
+            .get(format!("{}/api/account/{address}", self.base_url))
+            .send()
+            .await?;
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        Ok(Some(Self::decode(response).await?))
+    }
+
+    /// The nonce the account's next transaction should carry: one past
+    /// the highest committed nonce, or 1 for a fresh account.
+    pub async fn next_nonce(&self, address: &str) -> Result<u64, ClientError> {
+        Ok(self
+            .account(address)
+            .await?
+            .map_or(1, |account| account.nonce + 1))
+    }
+
+    /// Gas price estimate from the node's mempool: the median pending
+    /// price, or 1 when the mempool is empty.
+    pub async fn estimate_gas_price(&self) -> Result<u64, ClientError> {
+        let mempool: serde_json::Value = self
+            .get(&format!("{}/api/mempool", self.base_url))
+            .await?;
+        Ok(mempool["fees"]["median_gas_price"].as_u64().unwrap_or(1))
+    }
+
+    /// Submit a signed transaction; returns its hash.
+    pub async fn submit(&self, tx: &Transaction) -> Result<String, ClientError> {
+        let response = self
+            .http
+            .post(format!("{}/api/transaction", self.base_url))
+            .json(tx)
+            .send()
+            .await?;
+        let body: serde_json::Value = Self::decode(response).await?;
+        body["hash"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ClientError::Malformed("submit response has no hash".into()))
+    }
+
+    /// Broadcast a pre-signed blob through `POST /api/tx/raw`; returns
+    /// the transaction hash.
+    pub async fn submit_raw(&self, blob: &SignedTxBlob) -> Result<String, ClientError> {
+        let response = self
+            .http
+            .post(format!("{}/api/tx/raw", self.base_url))
+            .body(blob.encode())
+            .send()
+            .await?;
+        let body: serde_json::Value = Self::decode(response).await?;
+        body["hash"]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| ClientError::Malformed("submit response has no hash".into()))
+    }
+
+    /// Block until the transaction commits, fails, or `timeout` passes.
+    pub async fn wait(&self, hash: &str, timeout: Duration) -> Result<TxOutcome, ClientError> {
+        let response = self
+            .http
+            .get(format!(
+                "{}/api/tx/{hash}/wait?timeout_secs={}",
+                self.base_url,
+                timeout.as_secs().max(1)
+            ))
+            .send()
+            .await?;
+        Self::decode(response).await
+    }
+
+    /// Fill in the nonce and gas price from chain state where the
+    /// builder left them unset, then sign and submit in one step.
+    pub async fn send(
+        &self,
+        signer: &SecurityManager,
+        mut builder: TxBuilder,
+    ) -> Result<String, ClientError> {
+        if builder.nonce.is_none() {
+            builder = builder.nonce(self.next_nonce(&signer.address()).await?);
+        }
+        if builder.gas_price.is_none() {
+            builder = builder.gas_price(self.estimate_gas_price().await?);
+        }
+        self.submit(&builder.sign(signer)?).await
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T, ClientError> {
+        Self::decode(self.http.get(url).send().await?).await
+    }
+
+    /// Decode a successful response, or turn the API's error envelope
+    /// into [`ClientError::Rejected`].
+    async fn decode<T: serde::de::DeserializeOwned>(
+        response: reqwest::Response,
+    ) -> Result<T, ClientError> {
+        if response.status().is_success() {
+            return response
+                .json()
+                .await
+                .map_err(|err| ClientError::Malformed(err.to_string()));
+        }
+        let status = response.status();
+        let envelope: serde_json::Value = response.json().await.unwrap_or_default();
+        Err(ClientError::Rejected {
+            code: envelope["code"].as_str().unwrap_or(status.as_str()).to_string(),
+            message: envelope["error"]
+                .as_str()
+                .unwrap_or("no error body")
+                .to_string(),
+        })
+    }
+}
+
+/// Convenience re-export so callers can open a keystore rooted at a
+/// node home directory without knowing the layout.
+pub fn keystore_at_home(home: &Path) -> Keystore {
+    Keystore::open(home.join("keys"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::scheme::SignatureScheme;
+
+    #[test]
+    fn builder_signs_a_verifiable_transfer() {
+        let signer = Wallet::generate().account(0);
+        let tx = TxBuilder::transfer("bob", 25)
+            .nonce(1)
+            .gas_price(2)
+            .sign(&signer)
+            .unwrap();
+        assert_eq!(tx.sender, signer.address());
+        assert_eq!(tx.gas_limit, TRANSFER_GAS_LIMIT);
+        assert!(SignatureScheme::Ed25519.verify(
+            &signer.public_key(),
+            &tx.signing_bytes(),
+            &tx.signature,
+        ));
+    }
+
+    #[test]
+    fn builder_rejects_offline_signing_without_a_nonce() {
+        let signer = Wallet::generate().account(0);
+        let err = TxBuilder::transfer("bob", 25)
+            .gas_price(1)
+            .sign(&signer)
+            .unwrap_err();
+        assert!(matches!(err, ClientError::Incomplete("a nonce")));
+    }
+
+    #[test]
+    fn signed_blob_roundtrips_through_hex() {
+        let signer = Wallet::generate().account(0);
+        let tx = TxBuilder::transfer("bob", 5)
+            .nonce(3)
+            .gas_price(1)
+            .sign(&signer)
+            .unwrap();
+        let blob = SignedTxBlob {
+            chain_id: "artha-test".into(),
+            tx: tx.clone(),
+        };
+        let decoded = SignedTxBlob::decode(&format!(" 0x{}\n", blob.encode())).unwrap();
+        assert_eq!(decoded.chain_id, "artha-test");
+        assert_eq!(decoded.tx, tx);
+        assert!(SignedTxBlob::decode("not hex").is_err());
+    }
+
+    #[test]
+    fn keystore_reads_cli_keyring_records() {
+        let dir = std::env::temp_dir().join(format!("artha-client-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let wallet = Wallet::generate();
+        std::fs::write(
+            dir.join("alice.json"),
+            serde_json::json!({
+                "name": "alice",
+                "address": wallet.address(0),
+                "phrase": wallet.phrase(),
+            })
+            .to_string(),
+        )
+        .unwrap();
+        let keystore = Keystore::open(&dir);
+        assert_eq!(keystore.list(), vec!["alice"]);
+        assert_eq!(keystore.signer("alice").unwrap().address(), wallet.address(0));
+        let missing = keystore.signer("bob").err();
+        assert!(matches!(missing, Some(ClientError::KeyNotFound(_))));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
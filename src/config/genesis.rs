@@ -0,0 +1,138 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::ConsensusConfig;
+use crate::consensus::{Validator, ValidatorSet};
+use crate::types::transaction::now_unix;
+
+/// A member of the initial validator set.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GenesisValidator {
+    pub address: String,
+    pub public_key: Vec<u8>,
+    /// Optional BLS public key for aggregated commit signatures.
+    #[serde(default)]
+    pub bls_public_key: Vec<u8>,
+    pub power: u64,
+}
+
+/// An account pre-funded at genesis.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct GenesisAccount {
+    pub address: String,
+    pub balance: u64,
+}
+
+/// The chain's genesis document. Every node of a network starts from an
+/// identical copy; the chain hash commits to its contents, so nodes with
+/// diverging genesis files refuse to peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Genesis {
+    pub chain_id: String,
+    /// Unix timestamp the chain nominally starts at.
+    pub genesis_time: u64,
+    /// Consensus parameters the chain launches with.
+    pub consensus: ConsensusConfig,
+    pub validators: Vec<GenesisValidator>,
+    /// Pre-funded account balances.
+    #[serde(default)]
+    pub accounts: Vec<GenesisAccount>,
+}
+
+impl Genesis {
+    /// Load and validate a genesis document from a JSON file.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = std::fs::read(path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        let genesis: Genesis = serde_json::from_slice(&raw)
+            .map_err(|err| format!("invalid genesis {}: {err}", path.display()))?;
+        genesis.validate()?;
+        Ok(genesis)
+    }
+
+    /// A single-validator genesis for this node, used when no genesis
+    /// file exists (the historical single-node startup behavior).
+    pub fn single_node(
+        chain_id: String,
+        address: String,
+        public_key: Vec<u8>,
+        consensus: ConsensusConfig,
+    ) -> Self {
+        Self {
+            chain_id,
+            genesis_time: now_unix(),
+            consensus,
+            validators: vec![GenesisValidator {
+                address,
+                public_key,
+                bls_public_key: Vec::new(),
+                power: 1,
+            }],
+            accounts: Vec::new(),
+        }
+    }
+
+    /// Attach a BLS public key to the single validator, enabling
+    /// aggregated commit signatures on its blocks.
+    pub fn with_bls_key(mut self, bls_public_key: Vec<u8>) -> Self {
+        if let Some(validator) = self.validators.first_mut() {
+            validator.bls_public_key = bls_public_key;
+        }
+        self
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.chain_id.is_empty() {
+            return Err("genesis chain_id is empty".into());
+        }
+        if self.validators.is_empty() {
+            return Err("genesis has no validators".into());
+        }
+        for validator in &self.validators {
+            if validator.power == 0 {
+                return Err(format!("validator {} has zero power", validator.address));
+            }
+        }
+        Ok(())
+    }
+
+    /// The initial validator set described by this document.
+    pub fn validator_set(&self) -> ValidatorSet {
+        ValidatorSet::new(
+            self.validators
+                .iter()
+                .map(|v| Validator {
+                    address: v.address.clone(),
+                    public_key: v.public_key.clone(),
+                    bls_public_key: v.bls_public_key.clone(),
+                    voting_power: v.power,
+                    proposer_priority: 0,
+                })
+                .collect(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn genesis_round_trips_and_validates() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val1".into(),
+            vec![7; 32],
+            ConsensusConfig::default(),
+        );
+        let encoded = serde_json::to_vec(&genesis).unwrap();
+        let decoded: Genesis = serde_json::from_slice(&encoded).unwrap();
+        assert_eq!(decoded.chain_id, "artha-test");
+        assert_eq!(decoded.validator_set().get("val1").unwrap().voting_power, 1);
+
+        let mut empty = genesis.clone();
+        empty.validators.clear();
+        assert!(empty.validate().is_err());
+    }
+}
@@ -0,0 +1,8 @@
+pub mod genesis;
+pub mod settings;
+
+pub use genesis::{Genesis, GenesisAccount, GenesisValidator};
+pub use settings::{
+    ConsensusConfig, IndexerConfig, NetworkConfig, NodeConfig, OrderingPolicy, PruningConfig,
+    SignerConfig, StorageBackend, TlsConfig,
+};
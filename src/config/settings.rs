@@ -0,0 +1,480 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+/// Top-level node configuration. Every field has a sane default, so a
+/// config file only needs the values it changes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NodeConfig {
+    pub network: NetworkConfig,
+    pub consensus: ConsensusConfig,
+    /// Address the HTTP API binds to.
+    pub api_address: String,
+    /// Directory for node data (keys, blocks, state).
+    pub data_dir: String,
+    /// Key-value backend persisting blocks, state, and indexes.
+    #[serde(default)]
+    pub storage: StorageBackend,
+    /// Bearer token required by the admin API. Unset disables the
+    /// admin endpoints entirely.
+    #[serde(default)]
+    pub admin_token: Option<String>,
+    /// API keys accepted (via `X-Api-Key`) on privileged routes such as
+    /// the faucet and admin scope. Empty means only the admin token, if
+    /// any, grants access.
+    #[serde(default)]
+    pub api_keys: Vec<String>,
+    /// Tokens the devnet faucet grants per request. Unset disables the
+    /// faucet; mainnet network ids disable it regardless.
+    #[serde(default)]
+    pub faucet_amount: Option<u64>,
+    /// TLS settings for the API server. Unset serves plain HTTP.
+    #[serde(default)]
+    pub tls: Option<TlsConfig>,
+    /// Origins allowed to call the API cross-origin. Empty leaves CORS
+    /// off; `"*"` allows any origin.
+    #[serde(default)]
+    pub cors_allowed_origins: Vec<String>,
+    /// Push committed chain events to an external indexer sink.
+    #[serde(default)]
+    pub indexer: Option<IndexerConfig>,
+    /// When set, run as a stateless RPC proxy instead of a full node.
+    #[serde(default)]
+    pub proxy: Option<crate::api::proxy::ProxyConfig>,
+    /// Lowest effective gas price this node admits to its mempool, in
+    /// native units. A node-local spam filter on top of the chain's
+    /// governed `min_gas_price` floor.
+    #[serde(default)]
+    pub min_gas_price: u64,
+    /// Backend holding the validator signing key.
+    #[serde(default)]
+    pub signer: SignerConfig,
+}
+
+/// Transports for the indexer event sink; at least one should be set.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct IndexerConfig {
+    /// Webhook URL each event is POSTed to as JSON.
+    pub webhook_url: Option<String>,
+    /// Kafka bootstrap servers; requires building with the `kafka`
+    /// feature.
+    pub kafka_brokers: Option<String>,
+    /// Kafka topic events are produced to.
+    pub kafka_topic: String,
+    /// Postgres connection string for the SQL indexing backend;
+    /// requires building with the `postgres` feature.
+    pub postgres_url: Option<String>,
+}
+
+impl Default for IndexerConfig {
+    fn default() -> Self {
+        Self {
+            webhook_url: None,
+            kafka_brokers: None,
+            kafka_topic: "artha-events".to_string(),
+            postgres_url: None,
+        }
+    }
+}
+
+/// Certificate and key for HTTPS termination, both PEM-encoded files.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TlsConfig {
+    pub cert_path: String,
+    pub key_path: String,
+}
+
+impl NodeConfig {
+    /// Load a config file, chosen by extension: `.toml` parses as TOML,
+    /// anything else as JSON. Missing fields fall back to their serde
+    /// defaults.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let raw = std::fs::read_to_string(path)
+            .map_err(|err| format!("cannot read {}: {err}", path.display()))?;
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            toml::from_str(&raw).map_err(|err| format!("invalid config {}: {err}", path.display()))
+        } else {
+            serde_json::from_str(&raw)
+                .map_err(|err| format!("invalid config {}: {err}", path.display()))
+        }
+    }
+
+    /// Apply `ARTHA_*` environment overrides on top of file values, so
+    /// deployments can tweak a shared config without editing it.
+    pub fn apply_env_overrides(&mut self) {
+        if let Ok(value) = std::env::var("ARTHA_API_ADDRESS") {
+            self.api_address = value;
+        }
+        if let Ok(value) = std::env::var("ARTHA_DATA_DIR") {
+            self.data_dir = value;
+        }
+        if let Ok(value) = std::env::var("ARTHA_ADMIN_TOKEN") {
+            self.admin_token = Some(value);
+        }
+        if let Ok(value) = std::env::var("ARTHA_API_KEYS") {
+            self.api_keys = value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Ok(value) = std::env::var("ARTHA_P2P_LISTEN_ADDRESS") {
+            self.network.listen_address = value;
+        }
+        if let Ok(value) = std::env::var("ARTHA_NETWORK_ID") {
+            self.network.network_id = value;
+        }
+        if let Ok(value) = std::env::var("ARTHA_SEED_NODES") {
+            self.network.seed_nodes = value
+                .split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(String::from)
+                .collect();
+        }
+        if let Ok(value) = std::env::var("ARTHA_STORAGE") {
+            match value.as_str() {
+                "sled" => self.storage = StorageBackend::Sled,
+                "rocksdb" => self.storage = StorageBackend::Rocksdb,
+                "memory" => self.storage = StorageBackend::Memory,
+                other => log::warn!("ignoring unknown ARTHA_STORAGE value {other:?}"),
+            }
+        }
+        if let Ok(value) = std::env::var("ARTHA_BLOCK_INTERVAL_MS") {
+            match value.parse() {
+                Ok(ms) => self.consensus.block_interval_ms = ms,
+                Err(_) => log::warn!("ignoring non-numeric ARTHA_BLOCK_INTERVAL_MS {value:?}"),
+            }
+        }
+    }
+}
+
+impl Default for NodeConfig {
+    fn default() -> Self {
+        Self {
+            network: NetworkConfig::default(),
+            consensus: ConsensusConfig::default(),
+            api_address: "127.0.0.1:8080".to_string(),
+            data_dir: ".artha".to_string(),
+            storage: StorageBackend::default(),
+            admin_token: None,
+            api_keys: Vec::new(),
+            faucet_amount: None,
+            tls: None,
+            cors_allowed_origins: Vec::new(),
+            indexer: None,
+            proxy: None,
+            min_gas_price: 0,
+            signer: SignerConfig::default(),
+        }
+    }
+}
+
+/// Which backend holds the validator signing key.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "snake_case")]
+pub enum SignerConfig {
+    /// Key loaded from the keys directory and held in process memory;
+    /// the default.
+    #[default]
+    Memory,
+    /// YubiHSM 2 reached through a yubihsm-connector service. Requires
+    /// building with the `hsm` feature; the session password is read
+    /// from the `ARTHA_HSM_PASSWORD` environment variable so it never
+    /// sits in the config file.
+    Yubihsm {
+        /// Host the yubihsm-connector service listens on.
+        addr: String,
+        port: u16,
+        /// Object id of the authentication key opening the session.
+        auth_key_id: u16,
+        /// Object id of the ed25519 signing key on the device.
+        key_id: u16,
+    },
+}
+
+/// Which key-value backend persists chain data.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    /// Pure-Rust embedded database; the default.
+    #[default]
+    Sled,
+    /// RocksDB, for production deployments. Requires building with the
+    /// `rocksdb` feature.
+    Rocksdb,
+    /// Volatile store for tests and throwaway devnets.
+    Memory,
+}
+
+/// P2P networking configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Address the P2P listener binds to.
+    pub listen_address: String,
+    /// Identifier of the network this node belongs to.
+    pub network_id: String,
+    /// Addresses of seed nodes to bootstrap from.
+    pub seed_nodes: Vec<String>,
+    /// Peers to keep permanently connected (sentries, fellow
+    /// validators); dropped connections are redialed with backoff.
+    pub persistent_peers: Vec<String>,
+    /// Maximum number of peers to keep connected.
+    pub max_peers: usize,
+    /// Maximum inbound (accepted) connections; a separate cap so inbound
+    /// flooding cannot crowd out peers we chose to dial.
+    pub max_inbound_peers: usize,
+    /// Maximum outbound (dialed) connections.
+    pub max_outbound_peers: usize,
+    /// Minimum number of peers before the node considers itself connected.
+    pub min_peers: usize,
+    /// Private network mode: hex identity public keys (node ids) allowed
+    /// to connect. Empty means the network is open to anyone. Every
+    /// member of a private deployment must carry the same list.
+    #[serde(default)]
+    pub allowed_peers: Vec<String>,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            listen_address: "127.0.0.1:26656".to_string(),
+            network_id: "artha-devnet".to_string(),
+            seed_nodes: Vec::new(),
+            persistent_peers: Vec::new(),
+            max_peers: 50,
+            max_inbound_peers: 30,
+            max_outbound_peers: 20,
+            min_peers: 3,
+            allowed_peers: Vec::new(),
+        }
+    }
+}
+
+/// Consensus engine configuration.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConsensusConfig {
+    /// Target interval between blocks, in milliseconds.
+    pub block_interval_ms: u64,
+    /// Maximum encoded block size in bytes.
+    pub max_block_size: usize,
+    /// Maximum number of transactions included in a block.
+    pub max_transactions_per_block: usize,
+    /// Sum of transaction gas limits a block may carry.
+    #[serde(default = "default_max_block_gas")]
+    pub max_block_gas: u64,
+    /// Whether to propose blocks when no transactions are pending.
+    #[serde(default = "default_create_empty_blocks")]
+    pub create_empty_blocks: bool,
+    /// Minimum time between consecutive empty blocks, in milliseconds,
+    /// so an idle chain does not mint blocks as fast as rounds finish.
+    #[serde(default = "default_target_block_time_ms")]
+    pub target_block_time_ms: u64,
+    /// How many blocks of slashing history to retain for queries.
+    #[serde(default = "default_slash_retention_blocks")]
+    pub slash_retention_blocks: u64,
+    /// Denominations accepted for fees besides the native asset.
+    #[serde(default)]
+    pub fee_denoms: Vec<String>,
+    /// Blocks an unbonding stake entry waits before funds release.
+    #[serde(default = "default_unbonding_period_blocks")]
+    pub unbonding_period_blocks: u64,
+    /// Native tokens minted per block and distributed with fees.
+    #[serde(default = "default_block_reward")]
+    pub block_reward: u64,
+    /// Sliding window, in blocks, for downtime tracking.
+    #[serde(default = "default_downtime_window_blocks")]
+    pub downtime_window_blocks: u64,
+    /// Minimum fraction of the window a validator must sign.
+    #[serde(default = "default_downtime_min_signed_ratio")]
+    pub downtime_min_signed_ratio: f64,
+    /// Blocks a validator stays jailed after a downtime offense.
+    #[serde(default = "default_downtime_jail_blocks")]
+    pub downtime_jail_blocks: u64,
+    /// What historical state and block bodies to retain.
+    #[serde(default)]
+    pub pruning: PruningConfig,
+    /// How proposers must order transactions inside a block; verified
+    /// by every node, so a proposer cannot reorder for its own benefit.
+    #[serde(default)]
+    pub ordering: OrderingPolicy,
+    /// Account allowed to execute restricted governance txs: account
+    /// freezes and permission grants. `None` disables those txs
+    /// entirely.
+    #[serde(default, alias = "freeze_authority")]
+    pub authority: Option<String>,
+    /// Round-step timeouts: how long to wait for a proposal before
+    /// prevoting nil, in milliseconds. Raise these on high-latency
+    /// networks.
+    #[serde(default = "default_timeout_propose_ms")]
+    pub timeout_propose_ms: u64,
+    /// How long to wait for a prevote polka before precommitting nil.
+    #[serde(default = "default_timeout_prevote_ms")]
+    pub timeout_prevote_ms: u64,
+    /// How long to wait for precommits before starting the next round.
+    #[serde(default = "default_timeout_precommit_ms")]
+    pub timeout_precommit_ms: u64,
+    /// Extra milliseconds added to every step timeout per round number,
+    /// so later rounds wait longer.
+    #[serde(default = "default_timeout_delta_ms")]
+    pub timeout_delta_ms: u64,
+}
+
+/// Deterministic transaction order enforced inside blocks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrderingPolicy {
+    /// Highest effective gas price first; ties broken by sender, nonce,
+    /// and id. Matches the mempool's pending order.
+    #[default]
+    FeeDescending,
+    /// Senders in lexical order, each sender's transactions by ascending
+    /// nonce, ignoring fees entirely.
+    SenderFifo,
+}
+
+/// Retention policy for old state versions and block bodies.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "mode", rename_all = "snake_case")]
+pub enum PruningConfig {
+    /// Keep everything (archive node).
+    #[default]
+    Everything,
+    /// Keep the `keep` most recent heights, pruning continuously.
+    Recent { keep: u64 },
+    /// Keep the `keep` most recent heights, pruning every `interval`
+    /// blocks in batches.
+    Interval { keep: u64, interval: u64 },
+}
+
+impl PruningConfig {
+    /// First height to retain at `head`, or `None` when nothing should
+    /// be pruned yet. `last_floor` is the floor of the previous prune.
+    pub fn retain_floor(&self, head: u64, last_floor: u64) -> Option<u64> {
+        let floor = match self {
+            PruningConfig::Everything => return None,
+            PruningConfig::Recent { keep } => head.checked_sub(*keep)? + 1,
+            PruningConfig::Interval { keep, interval } => {
+                let floor = head.checked_sub(*keep)? + 1;
+                if floor.saturating_sub(last_floor) < *interval {
+                    return None;
+                }
+                floor
+            }
+        };
+        (floor > last_floor).then_some(floor)
+    }
+}
+
+fn default_downtime_window_blocks() -> u64 {
+    100
+}
+
+fn default_downtime_min_signed_ratio() -> f64 {
+    0.5
+}
+
+fn default_downtime_jail_blocks() -> u64 {
+    1_000
+}
+
+fn default_block_reward() -> u64 {
+    50
+}
+
+fn default_unbonding_period_blocks() -> u64 {
+    10_000
+}
+
+fn default_slash_retention_blocks() -> u64 {
+    100_000
+}
+
+fn default_max_block_gas() -> u64 {
+    30_000_000
+}
+
+fn default_create_empty_blocks() -> bool {
+    true
+}
+
+fn default_target_block_time_ms() -> u64 {
+    5_000
+}
+
+fn default_timeout_propose_ms() -> u64 {
+    3_000
+}
+
+fn default_timeout_prevote_ms() -> u64 {
+    1_000
+}
+
+fn default_timeout_precommit_ms() -> u64 {
+    1_000
+}
+
+fn default_timeout_delta_ms() -> u64 {
+    500
+}
+
+impl Default for ConsensusConfig {
+    fn default() -> Self {
+        Self {
+            block_interval_ms: 1000,
+            max_block_size: 1024 * 1024,
+            max_transactions_per_block: 1000,
+            max_block_gas: default_max_block_gas(),
+            create_empty_blocks: default_create_empty_blocks(),
+            target_block_time_ms: default_target_block_time_ms(),
+            slash_retention_blocks: default_slash_retention_blocks(),
+            fee_denoms: Vec::new(),
+            unbonding_period_blocks: default_unbonding_period_blocks(),
+            block_reward: default_block_reward(),
+            downtime_window_blocks: default_downtime_window_blocks(),
+            downtime_min_signed_ratio: default_downtime_min_signed_ratio(),
+            downtime_jail_blocks: default_downtime_jail_blocks(),
+            pruning: PruningConfig::default(),
+            ordering: OrderingPolicy::default(),
+            authority: None,
+            timeout_propose_ms: default_timeout_propose_ms(),
+            timeout_prevote_ms: default_timeout_prevote_ms(),
+            timeout_precommit_ms: default_timeout_precommit_ms(),
+            timeout_delta_ms: default_timeout_delta_ms(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn partial_toml_config_fills_in_defaults() {
+        let dir = std::env::temp_dir().join(format!("artha-config-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("config.toml");
+        std::fs::write(
+            &path,
+            "api_address = \"0.0.0.0:9000\"\n\n[network]\nmax_peers = 7\n",
+        )
+        .unwrap();
+        let config = NodeConfig::load(&path).unwrap();
+        assert_eq!(config.api_address, "0.0.0.0:9000");
+        assert_eq!(config.network.max_peers, 7);
+        // Everything unspecified keeps its default.
+        assert_eq!(config.network.listen_address, "127.0.0.1:26656");
+        assert_eq!(config.network.max_inbound_peers, 30);
+        assert_eq!(config.network.max_outbound_peers, 20);
+        assert_eq!(config.consensus.block_interval_ms, 1000);
+        assert_eq!(config.consensus.timeout_propose_ms, 3_000);
+        assert_eq!(config.consensus.timeout_delta_ms, 500);
+        assert_eq!(config.storage, StorageBackend::Sled);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+}
@@ -0,0 +1,223 @@
+//! Block storage with orphan handling and canonical-head selection.
+//!
+//! Gossip can deliver blocks out of order: a block may arrive before its
+//! parent, or on a fork of the chain we currently follow. The store
+//! keeps every valid block by hash, parks blocks whose parent is
+//! unknown in an orphan pool, connects them when the parent shows up,
+//! and moves the canonical head to the best properly committed tip.
+
+use std::collections::{HashMap, HashSet};
+
+use tokio::sync::RwLock;
+
+use crate::types::Block;
+
+/// What happened when a block was offered to the store.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InsertOutcome {
+    /// The block extended a known parent; the head may have moved.
+    Connected,
+    /// The parent is unknown; the block waits in the orphan pool.
+    Orphaned,
+    /// The block was already stored.
+    Duplicate,
+}
+
+struct ChainState {
+    /// Every connected block, by hash.
+    blocks: HashMap<Vec<u8>, Block>,
+    /// Hashes of blocks backed by a verified commit; only these are
+    /// eligible to become the canonical head.
+    committed: HashSet<Vec<u8>>,
+    /// Blocks waiting for their parent, keyed by the missing parent
+    /// hash.
+    orphans: HashMap<Vec<u8>, Vec<Block>>,
+    /// Hash of the canonical head, empty before the first block.
+    head: Vec<u8>,
+}
+
+/// Fork-aware block store; see the module docs.
+pub struct ChainStore {
+    state: RwLock<ChainState>,
+    /// Cap on parked orphans so a peer can't balloon memory with
+    /// unconnectable blocks.
+    max_orphans: usize,
+}
+
+impl ChainStore {
+    pub fn new(max_orphans: usize) -> Self {
+        Self {
+            state: RwLock::new(ChainState {
+                blocks: HashMap::new(),
+                committed: HashSet::new(),
+                orphans: HashMap::new(),
+                head: Vec::new(),
+            }),
+            max_orphans,
+        }
+    }
+
+    /// Offer a block to the store. `committed` marks blocks whose
+    /// commit certificate has been verified by the caller; only those
+    /// can take over the canonical head. Orphans connect automatically
+    /// once their parent arrives.
+    pub async fn insert(&self, block: Block, committed: bool) -> InsertOutcome {
+        let hash = block.hash();
+        let mut state = self.state.write().await;
+        if state.blocks.contains_key(&hash) {
+            if committed && state.committed.insert(hash.clone()) {
+                Self::maybe_advance_head(&mut state, &hash);
+            }
+            return InsertOutcome::Duplicate;
+        }
+        // Height 1 roots the chain; anything else needs a known parent.
+        let parent_known =
+            block.header.height == 1 || state.blocks.contains_key(&block.header.prev_hash);
+        if !parent_known {
+            let waiting: usize = state.orphans.values().map(Vec::len).sum();
+            if waiting >= self.max_orphans {
+                // Drop an arbitrary parked block to stay bounded.
+                if let Some(parent) = state.orphans.keys().next().cloned() {
+                    if let Some(queue) = state.orphans.get_mut(&parent) {
+                        queue.pop();
+                        if queue.is_empty() {
+                            state.orphans.remove(&parent);
+                        }
+                    }
+                }
+            }
+            state
+                .orphans
+                .entry(block.header.prev_hash.clone())
+                .or_default()
+                .push(block);
+            return InsertOutcome::Orphaned;
+        }
+        Self::connect(&mut state, block, committed);
+        InsertOutcome::Connected
+    }
+
+    /// Connect `block` and every orphan chain it unblocks.
+    fn connect(state: &mut ChainState, block: Block, committed: bool) {
+        let mut queue = vec![(block, committed)];
+        while let Some((block, committed)) = queue.pop() {
+            let hash = block.hash();
+            state.blocks.insert(hash.clone(), block);
+            if committed {
+                state.committed.insert(hash.clone());
+            }
+            Self::maybe_advance_head(state, &hash);
+            if let Some(children) = state.orphans.remove(&hash) {
+                // Orphans carry no commit of their own; they become
+                // head candidates when their commit is later observed
+                // via a duplicate insert.
+                queue.extend(children.into_iter().map(|child| (child, false)));
+            }
+        }
+    }
+
+    /// Switch the canonical head to `hash` if it is committed and
+    /// strictly better (higher) than the current head.
+    fn maybe_advance_head(state: &mut ChainState, hash: &[u8]) {
+        if !state.committed.contains(hash) {
+            return;
+        }
+        let height = state.blocks[hash].header.height;
+        let head_height = state
+            .blocks
+            .get(&state.head)
+            .map(|b| b.header.height)
+            .unwrap_or(0);
+        if height > head_height {
+            if !state.head.is_empty() && state.blocks[hash].header.prev_hash != state.head {
+                log::info!("reorg: new head at height {height}");
+            }
+            state.head = hash.to_vec();
+        }
+    }
+
+    /// The canonical head block, if any block has been committed.
+    pub async fn head(&self) -> Option<Block> {
+        let state = self.state.read().await;
+        state.blocks.get(&state.head).cloned()
+    }
+
+    pub async fn block(&self, hash: &[u8]) -> Option<Block> {
+        self.state.read().await.blocks.get(hash).cloned()
+    }
+
+    /// The canonical chain from genesis to the head, oldest first.
+    pub async fn canonical_chain(&self) -> Vec<Block> {
+        let state = self.state.read().await;
+        let mut chain = Vec::new();
+        let mut cursor = state.head.clone();
+        while let Some(block) = state.blocks.get(&cursor) {
+            cursor = block.header.prev_hash.clone();
+            chain.push(block.clone());
+        }
+        chain.reverse();
+        chain
+    }
+
+    pub async fn orphan_count(&self) -> usize {
+        self.state.read().await.orphans.values().map(Vec::len).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn child_of(parent: &Block, height: u64) -> Block {
+        Block::new(height, parent.hash(), vec![0; 32], "val0".into(), Vec::new())
+    }
+
+    #[tokio::test]
+    async fn orphans_connect_when_the_parent_arrives() {
+        let store = ChainStore::new(16);
+        let b1 = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), Vec::new());
+        let b2 = child_of(&b1, 2);
+        let b3 = child_of(&b2, 3);
+
+        assert_eq!(store.insert(b3.clone(), true).await, InsertOutcome::Orphaned);
+        assert_eq!(store.insert(b2.clone(), true).await, InsertOutcome::Orphaned);
+        assert_eq!(store.orphan_count().await, 2);
+        assert_eq!(store.insert(b1.clone(), true).await, InsertOutcome::Connected);
+        assert_eq!(store.orphan_count().await, 0);
+        // The orphans connected but carried no verified commit, so the
+        // head stays at the committed block until theirs is observed.
+        assert_eq!(store.head().await.unwrap().hash(), b1.hash());
+        assert_eq!(store.insert(b3.clone(), true).await, InsertOutcome::Duplicate);
+        assert_eq!(store.head().await.unwrap().hash(), b3.hash());
+        assert_eq!(store.canonical_chain().await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn committed_higher_fork_takes_over_the_head() {
+        let store = ChainStore::new(16);
+        let b1 = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), Vec::new());
+        let b2a = child_of(&b1, 2);
+        let mut b2b = child_of(&b1, 2);
+        b2b.header.proposer = "val1".into();
+        let b3b = child_of(&b2b, 3);
+
+        store.insert(b1, true).await;
+        store.insert(b2a.clone(), true).await;
+        assert_eq!(store.head().await.unwrap().hash(), b2a.hash());
+        // A committed fork at the same height does not displace the
+        // head, but its committed child does.
+        store.insert(b2b.clone(), true).await;
+        assert_eq!(store.head().await.unwrap().hash(), b2a.hash());
+        store.insert(b3b.clone(), true).await;
+        assert_eq!(store.head().await.unwrap().hash(), b3b.hash());
+        let chain: Vec<Vec<u8>> = store
+            .canonical_chain()
+            .await
+            .iter()
+            .map(|b| b.hash())
+            .collect();
+        assert_eq!(chain.last().unwrap(), &b3b.hash());
+        assert!(chain.contains(&b2b.hash()));
+        assert!(!chain.contains(&b2a.hash()));
+    }
+}
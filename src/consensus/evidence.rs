@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use super::tendermint::{Vote, VoteType};
+use crate::security::SecurityManager;
+
+/// Two conflicting signed votes by the same validator for the same
+/// (height, round, type): a self-contained proof of equivocation that
+/// any node holding the validator's public key can verify.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DuplicateVoteEvidence {
+    pub vote_a: Vote,
+    pub vote_b: Vote,
+}
+
+/// Misbehavior evidence gossiped between nodes and committed in blocks.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Evidence {
+    DoubleSign(DuplicateVoteEvidence),
+}
+
+impl Evidence {
+    /// Whether the evidence actually proves misbehavior: same validator
+    /// and vote slot, different block hashes.
+    pub fn is_valid(&self) -> bool {
+        match self {
+            Evidence::DoubleSign(ev) => {
+                ev.vote_a.validator == ev.vote_b.validator
+                    && ev.vote_a.height == ev.vote_b.height
+                    && ev.vote_a.round == ev.vote_b.round
+                    && ev.vote_a.vote_type == ev.vote_b.vote_type
+                    && ev.vote_a.block_hash != ev.vote_b.block_hash
+            }
+        }
+    }
+
+    /// The misbehaving validator's address.
+    pub fn validator(&self) -> &str {
+        match self {
+            Evidence::DoubleSign(ev) => &ev.vote_a.validator,
+        }
+    }
+
+    /// The height the misbehavior occurred at.
+    pub fn height(&self) -> u64 {
+        match self {
+            Evidence::DoubleSign(ev) => ev.vote_a.height,
+        }
+    }
+
+    /// Verify that both votes carry valid signatures from the accused
+    /// validator's key, making the proof independently checkable. The
+    /// votes must have been signed for `chain_id`.
+    pub fn verify_signatures(&self, chain_id: &str, public_key: &[u8]) -> bool {
+        match self {
+            Evidence::DoubleSign(ev) => {
+                SecurityManager::verify(
+                    public_key,
+                    &ev.vote_a.signing_bytes(chain_id),
+                    &ev.vote_a.signature,
+                ) && SecurityManager::verify(
+                    public_key,
+                    &ev.vote_b.signing_bytes(chain_id),
+                    &ev.vote_b.signature,
+                )
+            }
+        }
+    }
+
+    pub fn hash(&self) -> Vec<u8> {
+        let bytes = bincode::serialize(self).unwrap_or_default();
+        Sha256::digest(&bytes).to_vec()
+    }
+}
+
+/// Records every signed vote a node observes, across heights and rounds,
+/// and produces `DuplicateVoteEvidence` when a validator signs two
+/// different block hashes for the same slot. Unlike the per-round check
+/// in the tendermint module, history survives round and height changes.
+pub struct VoteHistory {
+    /// Last signed vote per (validator, height, round, type).
+    seen: RwLock<HashMap<(String, u64, u32, VoteType), Vote>>,
+}
+
+impl VoteHistory {
+    pub fn new() -> Self {
+        Self {
+            seen: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record a vote. Returns equivocation evidence if this validator
+    /// already signed a different block hash for the same slot.
+    pub async fn observe(&self, vote: Vote) -> Option<Evidence> {
+        let key = (
+            vote.validator.clone(),
+            vote.height,
+            vote.round,
+            vote.vote_type,
+        );
+        let mut seen = self.seen.write().await;
+        match seen.get(&key) {
+            Some(previous) if previous.block_hash != vote.block_hash => {
+                Some(Evidence::DoubleSign(DuplicateVoteEvidence {
+                    vote_a: previous.clone(),
+                    vote_b: vote,
+                }))
+            }
+            Some(_) => None,
+            None => {
+                seen.insert(key, vote);
+                None
+            }
+        }
+    }
+
+    /// Drop history below `height`, once evidence there has expired.
+    pub async fn prune_below(&self, height: u64) {
+        self.seen.write().await.retain(|key, _| key.1 >= height);
+    }
+}
+
+impl Default for VoteHistory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pending evidence awaiting inclusion in a block. Evidence older than
+/// `max_age_blocks` behind the head is expired and never committed.
+pub struct EvidencePool {
+    pending: RwLock<Vec<Evidence>>,
+    max_age_blocks: u64,
+}
+
+impl EvidencePool {
+    pub fn new(max_age_blocks: u64) -> Self {
+        Self {
+            pending: RwLock::new(Vec::new()),
+            max_age_blocks,
+        }
+    }
+
+    /// Whether evidence from `evidence_height` is too old to commit at
+    /// `head_height`.
+    pub fn is_expired(&self, evidence_height: u64, head_height: u64) -> bool {
+        head_height.saturating_sub(evidence_height) > self.max_age_blocks
+    }
+
+    /// Add verified evidence, ignoring duplicates and invalid proofs.
+    /// Returns true if the evidence was new.
+    pub async fn add(&self, evidence: Evidence) -> bool {
+        if !evidence.is_valid() {
+            return false;
+        }
+        let mut pending = self.pending.write().await;
+        if pending.iter().any(|e| e.hash() == evidence.hash()) {
+            return false;
+        }
+        pending.push(evidence);
+        true
+    }
+
+    /// Unexpired pending evidence to commit in a block at `head_height`,
+    /// removing it (and anything expired) from the pool.
+    pub async fn take_for_block(&self, head_height: u64) -> Vec<Evidence> {
+        let mut pending = self.pending.write().await;
+        let taken = pending
+            .iter()
+            .filter(|e| !self.is_expired(e.height(), head_height))
+            .cloned()
+            .collect();
+        pending.clear();
+        taken
+    }
+}
+
+/// Root over the evidence committed in a block, mirroring the tx root.
+pub fn compute_evidence_root(evidence: &[Evidence]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for ev in evidence {
+        hasher.update(ev.hash());
+    }
+    hasher.finalize().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conflicting_votes(validator: &str, height: u64) -> Evidence {
+        let vote_a = Vote::new(VoteType::Precommit, height, 0, vec![1; 32], validator.into());
+        let vote_b = Vote::new(VoteType::Precommit, height, 0, vec![2; 32], validator.into());
+        Evidence::DoubleSign(DuplicateVoteEvidence { vote_a, vote_b })
+    }
+
+    #[tokio::test]
+    async fn pool_dedupes_and_expires_evidence() {
+        let pool = EvidencePool::new(100);
+        let evidence = conflicting_votes("val1", 10);
+        assert!(pool.add(evidence.clone()).await);
+        assert!(!pool.add(evidence.clone()).await);
+
+        // Not an equivocation: same hash twice.
+        let vote = Vote::new(VoteType::Precommit, 10, 0, vec![1; 32], "val2".into());
+        let same = Evidence::DoubleSign(DuplicateVoteEvidence {
+            vote_a: vote.clone(),
+            vote_b: vote,
+        });
+        assert!(!pool.add(same).await);
+
+        // Expired relative to a far-future head.
+        assert!(pool.is_expired(10, 200));
+        assert!(pool.take_for_block(200).await.is_empty());
+        assert!(pool.add(evidence).await);
+        assert_eq!(pool.take_for_block(50).await.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn history_detects_equivocation_across_rounds() {
+        let history = VoteHistory::new();
+        let first = Vote::new(VoteType::Prevote, 7, 2, vec![1; 32], "val1".into());
+        assert!(history.observe(first.clone()).await.is_none());
+        // Re-observing the same vote is not equivocation.
+        assert!(history.observe(first.clone()).await.is_none());
+        // A different slot is fine.
+        let other_round = Vote::new(VoteType::Prevote, 7, 3, vec![2; 32], "val1".into());
+        assert!(history.observe(other_round).await.is_none());
+        // A conflicting hash for the original slot is equivocation, and
+        // the evidence carries both signed votes.
+        let conflict = Vote::new(VoteType::Prevote, 7, 2, vec![2; 32], "val1".into());
+        let evidence = history.observe(conflict.clone()).await.unwrap();
+        assert!(evidence.is_valid());
+        let Evidence::DoubleSign(ev) = &evidence;
+        assert_eq!(ev.vote_a, first);
+        assert_eq!(ev.vote_b, conflict);
+        // Pruned history forgets the old votes.
+        history.prune_below(8).await;
+        assert!(history.observe(first).await.is_none());
+    }
+}
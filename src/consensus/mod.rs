@@ -0,0 +1,3148 @@
+pub mod chain;
+pub mod evidence;
+pub mod params;
+pub mod rewards;
+pub mod sim;
+pub mod slashing;
+pub mod staking;
+pub mod tendermint;
+pub mod upgrade;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::config::{ConsensusConfig, Genesis, OrderingPolicy, PruningConfig};
+use crate::network::queue::{LaneStats, MessageLanes, Priority};
+use crate::security::bls;
+use crate::security::smt::SparseMerkleTree;
+use crate::security::state::{Permission, StateSecurityManager};
+use crate::security::signer::Signer;
+use crate::security::SecurityManager;
+use crate::storage::{Column, KvStore, WriteBatch};
+use crate::types::gas::{self, GasMeter};
+use crate::types::transaction::{BatchTx, Msg};
+use crate::types::{Block, Transaction, TransactionPool, TxStatus, TxTracker};
+use evidence::{Evidence, EvidencePool, VoteHistory};
+use params::{GovTx, ParamStore, ScheduledChange};
+use slashing::{LivenessTracker, SlashEvent, SlashReason, SlashingStore};
+use staking::{StakingState, StakingTx};
+use chain::{ChainStore, InsertOutcome};
+use tendermint::{RoundTimeouts, TendermintConsensus, TimeoutAction, Vote, VoteType};
+use upgrade::{UpgradeManager, UpgradePlan};
+
+#[derive(Debug, Error)]
+pub enum ConsensusError {
+    #[error("not a validator")]
+    NotValidator,
+    #[error("invalid proposer: expected {expected}, got {got}")]
+    InvalidProposer { expected: String, got: String },
+    #[error("invalid block: {0}")]
+    InvalidBlock(String),
+    #[error("invalid vote: {0}")]
+    InvalidVote(String),
+    #[error("invalid commit: {0}")]
+    InvalidCommit(String),
+    #[error("network error: {0}")]
+    Network(String),
+    #[error("internal error: {0}")]
+    Internal(String),
+}
+
+/// A consensus validator.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Validator {
+    pub address: String,
+    pub public_key: Vec<u8>,
+    /// Optional BLS public key, enabling aggregated commit signatures.
+    #[serde(default)]
+    pub bls_public_key: Vec<u8>,
+    pub voting_power: u64,
+    pub proposer_priority: i64,
+}
+
+/// The active validator set.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ValidatorSet {
+    pub validators: Vec<Validator>,
+}
+
+impl ValidatorSet {
+    pub fn new(validators: Vec<Validator>) -> Self {
+        Self { validators }
+    }
+
+    pub fn total_power(&self) -> u64 {
+        self.validators.iter().map(|v| v.voting_power).sum()
+    }
+
+    pub fn get(&self, address: &str) -> Option<&Validator> {
+        self.validators.iter().find(|v| v.address == address)
+    }
+
+    pub fn contains(&self, address: &str) -> bool {
+        self.get(address).is_some()
+    }
+
+    pub fn len(&self) -> usize {
+        self.validators.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.validators.is_empty()
+    }
+
+    /// Advance proposer priorities by one round and return the proposer.
+    ///
+    /// Tendermint's accumulate-and-subtract rotation: every validator's
+    /// priority grows by its voting power, the highest-priority validator
+    /// proposes and pays the total power back, so proposers rotate
+    /// proportionally to voting power across rounds.
+    pub fn advance_proposer(&mut self) -> Option<Validator> {
+        if self.validators.is_empty() {
+            return None;
+        }
+        let total = self.total_power() as i64;
+        for validator in &mut self.validators {
+            validator.proposer_priority += validator.voting_power as i64;
+        }
+        let proposer = self
+            .validators
+            .iter_mut()
+            .max_by(|a, b| {
+                a.proposer_priority
+                    .cmp(&b.proposer_priority)
+                    // Deterministic tie-break so all nodes agree.
+                    .then_with(|| b.address.cmp(&a.address))
+            })?;
+        proposer.proposer_priority -= total;
+        Some(proposer.clone())
+    }
+}
+
+/// A change to one validator's power produced at end-of-block.
+/// `voting_power == 0` means the validator was removed.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ValidatorUpdate {
+    pub address: String,
+    pub public_key: Vec<u8>,
+    pub voting_power: u64,
+}
+
+/// The latest irreversibly committed block. Persisted across restarts
+/// so the node never accepts a competing block at or below this height,
+/// and served to API consumers as the finality frontier.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct CommittedState {
+    pub height: u64,
+    pub block_hash: Vec<u8>,
+}
+
+/// Key within [`Column::State`] holding the serialized
+/// [`CommittedState`].
+const CHECKPOINT_KEY: &[u8] = b"committed_state";
+
+/// Key within [`Column::Blocks`] for the block committed at `height`;
+/// big-endian so a prefix scan walks blocks in height order.
+fn block_key(height: u64) -> Vec<u8> {
+    height.to_be_bytes().to_vec()
+}
+
+/// Execution receipt for one transaction in a block.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxReceipt {
+    pub tx_hash: String,
+    pub gas_used: u64,
+    /// Fee charged to the sender: `gas_used * gas_price`.
+    pub fee: u64,
+}
+
+/// Results of executing one block, stored alongside it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockResults {
+    pub height: u64,
+    /// Validator set changes produced at this height.
+    pub validator_updates: Vec<ValidatorUpdate>,
+    /// Per-transaction execution receipts, in block order.
+    #[serde(default)]
+    pub receipts: Vec<TxReceipt>,
+}
+
+/// Compute the per-height validator diff between two sets: additions and
+/// power changes carry the new power, removals carry power 0.
+pub fn diff_validator_sets(old: &ValidatorSet, new: &ValidatorSet) -> Vec<ValidatorUpdate> {
+    let mut updates = Vec::new();
+    for validator in &new.validators {
+        match old.get(&validator.address) {
+            Some(previous) if previous.voting_power == validator.voting_power => {}
+            _ => updates.push(ValidatorUpdate {
+                address: validator.address.clone(),
+                public_key: validator.public_key.clone(),
+                voting_power: validator.voting_power,
+            }),
+        }
+    }
+    for validator in &old.validators {
+        if new.get(&validator.address).is_none() {
+            updates.push(ValidatorUpdate {
+                address: validator.address.clone(),
+                public_key: validator.public_key.clone(),
+                voting_power: 0,
+            });
+        }
+    }
+    updates
+}
+
+/// Commit certificate for a block: the +2/3 precommits that finalized it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Commit {
+    pub height: u64,
+    pub round: u32,
+    pub block_hash: Vec<u8>,
+    pub votes: Vec<Vote>,
+    /// Addresses whose precommits back `bls_signature`, when aggregated.
+    #[serde(default)]
+    pub bls_signers: Vec<String>,
+    /// One aggregated BLS signature replacing per-vote ed25519
+    /// signatures, for validator sets with registered BLS keys.
+    #[serde(default)]
+    pub bls_signature: Vec<u8>,
+}
+
+impl Commit {
+    /// Hash identifying this certificate, carried in the next block's
+    /// header as `last_commit_hash`.
+    pub fn hash(&self) -> Vec<u8> {
+        let bytes = bincode::serialize(self).unwrap_or_default();
+        Sha256::digest(&bytes).to_vec()
+    }
+}
+
+/// Bytes every validator signs (with its BLS key) to back a commit for
+/// `block_hash` at (height, round). All signers cover the same message,
+/// which is what makes their signatures aggregatable.
+pub fn commit_signing_bytes(chain_id: &str, height: u64, round: u32, block_hash: &[u8]) -> Vec<u8> {
+    crate::security::consensus_sign_doc(chain_id, "consensus/commit", height, round, block_hash)
+}
+
+/// Bytes a proposer signs over a proposal for `block_hash` at
+/// (height, round), scoped to `chain_id` like every other signature.
+pub fn proposal_signing_bytes(
+    chain_id: &str,
+    height: u64,
+    round: u32,
+    block_hash: &[u8],
+) -> Vec<u8> {
+    crate::security::consensus_sign_doc(chain_id, "consensus/proposal", height, round, block_hash)
+}
+
+/// The total order `policy` imposes between two block transactions.
+fn policy_cmp(
+    policy: OrderingPolicy,
+    fees: &crate::types::FeePolicy,
+    a: &Transaction,
+    b: &Transaction,
+) -> std::cmp::Ordering {
+    match policy {
+        OrderingPolicy::FeeDescending => fees
+            .effective_gas_price(b)
+            .cmp(&fees.effective_gas_price(a))
+            .then_with(|| a.sender.cmp(&b.sender))
+            .then_with(|| a.nonce.cmp(&b.nonce))
+            .then_with(|| a.id.cmp(&b.id)),
+        OrderingPolicy::SenderFifo => {
+            a.sender.cmp(&b.sender).then_with(|| a.nonce.cmp(&b.nonce))
+        }
+    }
+}
+
+/// Sort block transactions into the order `policy` requires.
+fn sort_by_policy(
+    policy: OrderingPolicy,
+    fees: &crate::types::FeePolicy,
+    transactions: &mut [Transaction],
+) {
+    transactions.sort_by(|a, b| policy_cmp(policy, fees, a, b));
+}
+
+/// Index of the first adjacent pair out of `policy` order, if any.
+/// Dropped transactions never reorder the rest, so a valid block is
+/// exactly a sorted sequence.
+fn ordering_violation(
+    policy: OrderingPolicy,
+    fees: &crate::types::FeePolicy,
+    transactions: &[Transaction],
+) -> Option<usize> {
+    transactions
+        .windows(2)
+        .position(|pair| policy_cmp(policy, fees, &pair[0], &pair[1]) == std::cmp::Ordering::Greater)
+}
+
+/// Messages exchanged between consensus participants.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum ConsensusMessage {
+    Proposal {
+        height: u64,
+        round: u32,
+        block: Block,
+        proposer: String,
+        signature: Vec<u8>,
+    },
+    Vote(Vote),
+    Commit(Commit),
+    Evidence(Evidence),
+}
+
+impl ConsensusMessage {
+    /// The height the message pertains to, for routing and tracing.
+    pub fn height(&self) -> u64 {
+        match self {
+            ConsensusMessage::Proposal { height, .. } => *height,
+            ConsensusMessage::Vote(vote) => vote.height,
+            ConsensusMessage::Commit(commit) => commit.height,
+            ConsensusMessage::Evidence(evidence) => evidence.height(),
+        }
+    }
+}
+
+/// A peer participating in consensus gossip. Quality scoring lives in the
+/// shared reputation subsystem (`network::reputation`).
+#[derive(Debug, Clone)]
+pub struct Peer {
+    pub id: String,
+    pub address: String,
+    pub last_seen: u64,
+}
+
+impl ConsensusMessage {
+    /// Scheduling class within the consensus lanes: proposals and votes
+    /// drive round progress, commits can be reconstructed from votes.
+    pub fn priority(&self) -> Priority {
+        match self {
+            ConsensusMessage::Proposal { .. } | ConsensusMessage::Vote(_) => Priority::High,
+            ConsensusMessage::Commit(_) | ConsensusMessage::Evidence(_) => Priority::Normal,
+        }
+    }
+}
+
+/// Capacity of each consensus message lane.
+const CONSENSUS_QUEUE_CAPACITY: usize = 1024;
+
+/// Block intervals without a commit before the stall watchdog declares
+/// the height wedged and forces the next round.
+const STALL_MULTIPLIER: u32 = 10;
+
+/// Queues consensus messages between the engine and the network layer.
+pub struct ConsensusNetworkManager {
+    /// Incoming messages, consumed by the engine.
+    inbound: MessageLanes<ConsensusMessage>,
+    /// Outgoing messages, consumed by the network layer.
+    outbound: MessageLanes<ConsensusMessage>,
+    peers: Arc<RwLock<HashMap<String, Peer>>>,
+}
+
+impl ConsensusNetworkManager {
+    pub fn new() -> Self {
+        Self {
+            inbound: MessageLanes::new(CONSENSUS_QUEUE_CAPACITY),
+            outbound: MessageLanes::new(CONSENSUS_QUEUE_CAPACITY),
+            peers: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Queue a message for broadcast to all peers, applying backpressure
+    /// when the lane is full.
+    pub async fn broadcast(&self, message: ConsensusMessage) {
+        let priority = message.priority();
+        self.outbound.send(priority, message).await;
+    }
+
+    /// Deliver an incoming message from the network layer. The message is
+    /// dropped (and counted) if the lane is full.
+    pub async fn deliver(&self, message: ConsensusMessage) {
+        let priority = message.priority();
+        if !self.inbound.try_send(priority, message) {
+            log::warn!("consensus inbound lane full, dropping message");
+        }
+    }
+
+    /// Receive the next message queued for broadcast.
+    pub async fn next_outbound(&self) -> Option<ConsensusMessage> {
+        self.outbound.recv().await
+    }
+
+    /// Receive the next incoming message, waiting until one arrives.
+    pub async fn recv_message(&self) -> Option<ConsensusMessage> {
+        self.inbound.recv().await
+    }
+
+    /// Drop counters for the inbound and outbound lanes.
+    pub fn queue_stats(&self) -> (LaneStats, LaneStats) {
+        (self.inbound.stats(), self.outbound.stats())
+    }
+
+    pub async fn add_peer(&self, peer: Peer) {
+        self.peers.write().await.insert(peer.id.clone(), peer);
+    }
+
+    pub async fn peer_count(&self) -> usize {
+        self.peers.read().await.len()
+    }
+}
+
+impl Default for ConsensusNetworkManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Chain state tracked by the consensus engine.
+pub struct ConsensusState {
+    pub height: u64,
+    pub last_block_hash: Vec<u8>,
+    pub last_state_root: Vec<u8>,
+    /// Sparse Merkle tree mapping heights to committed block hashes.
+    pub state_tree: SparseMerkleTree,
+    pub blocks: Vec<Block>,
+    /// Per-height execution results, parallel to `blocks`.
+    pub results: Vec<BlockResults>,
+    /// Per-height commit certificates, served to light clients.
+    pub commits: Vec<Commit>,
+    /// Validator set snapshots at the heights where the set changed,
+    /// starting with the genesis set at height 0.
+    pub validator_history: Vec<(u64, ValidatorSet)>,
+    /// Height of the latest sealed state snapshot. The pruner never
+    /// deletes at or above this height.
+    pub snapshot_height: u64,
+}
+
+impl ConsensusState {
+    pub fn new() -> Self {
+        Self {
+            height: 0,
+            last_block_hash: vec![0u8; 32],
+            last_state_root: vec![0u8; 32],
+            state_tree: SparseMerkleTree::new(),
+            blocks: Vec::new(),
+            results: Vec::new(),
+            commits: Vec::new(),
+            validator_history: Vec::new(),
+            snapshot_height: 0,
+        }
+    }
+}
+
+impl Default for ConsensusState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Cap on orphan blocks the fork-aware chain store may park while
+/// waiting for their parents to arrive.
+const MAX_ORPHAN_BLOCKS: usize = 128;
+
+/// The consensus engine: proposes, validates, and finalizes blocks.
+pub struct ConsensusEngine {
+    pub config: ConsensusConfig,
+    pub state: Arc<RwLock<ConsensusState>>,
+    pub validators: Arc<RwLock<ValidatorSet>>,
+    pub mempool: Arc<TransactionPool>,
+    pub network: Arc<ConsensusNetworkManager>,
+    pub tendermint: Arc<RwLock<TendermintConsensus>>,
+    /// Transaction lifecycle tracker, updated as txs move through blocks.
+    pub tracker: Arc<TxTracker>,
+    /// Applied slash and jail events, retained for audit queries.
+    pub slashing: Arc<SlashingStore>,
+    /// Pending misbehavior evidence awaiting inclusion in a block.
+    pub evidence: Arc<EvidencePool>,
+    /// Signed votes observed across heights, for equivocation detection.
+    pub vote_history: Arc<VoteHistory>,
+    /// Validator updates produced by staking txs in the current block,
+    /// applied to the set at end of block.
+    pending_updates: RwLock<Vec<ValidatorUpdate>>,
+    /// Account ledger; bonds and delegations lock balance here.
+    pub accounts: Arc<StateSecurityManager>,
+    /// Bonded stake backing validator voting power.
+    pub staking: RwLock<StakingState>,
+    /// Per-validator signing records and jail terms.
+    pub liveness: RwLock<LivenessTracker>,
+    /// Governed consensus parameters and scheduled changes.
+    pub params: RwLock<ParamStore>,
+    /// Pending coordinated upgrade plan, if any.
+    pub upgrade: RwLock<UpgradeManager>,
+    /// Deployed contract code, keyed by hash.
+    pub contracts: Arc<crate::contracts::ContractStore>,
+    /// User-issued token denominations and their issuance state.
+    pub tokens: Arc<crate::tokens::TokenRegistry>,
+    /// Latest irreversible block, enforced against competing proposals
+    /// and persisted so a restart cannot roll finality back.
+    checkpoint: RwLock<CommittedState>,
+    /// Key-value store persisting blocks and the checkpoint; `None`
+    /// keeps the chain memory-only.
+    store: RwLock<Option<Arc<dyn KvStore>>>,
+    /// Fork-aware block index: locally finalized blocks and gossiped
+    /// side-chain blocks, with orphans parked until their parent shows
+    /// up.
+    pub chain: Arc<ChainStore>,
+    /// Chain identifier every signature is domain-separated by.
+    pub chain_id: String,
+    /// This node's signing backend, used for proposals and votes. May
+    /// hold the key in process memory or in a hardware module.
+    security: Arc<dyn Signer>,
+    /// Optional BLS keypair, set at startup when the keys directory
+    /// holds one; enables aggregated commit signatures.
+    bls: RwLock<Option<bls::BlsKeypair>>,
+    /// This node's validator address.
+    pub address: String,
+    /// Chain events published as blocks finalize; API streams and
+    /// indexer sinks subscribe here.
+    pub events: crate::events::EventBus,
+    /// Set by [`ConsensusEngine::begin_shutdown`]; the proposer loop
+    /// exits before starting another height.
+    shutting_down: std::sync::atomic::AtomicBool,
+}
+
+impl ConsensusEngine {
+    pub fn new(
+        genesis: &Genesis,
+        mempool: Arc<TransactionPool>,
+        network: Arc<ConsensusNetworkManager>,
+        tracker: Arc<TxTracker>,
+        accounts: Arc<StateSecurityManager>,
+        security: Arc<dyn Signer>,
+    ) -> Self {
+        let config = genesis.consensus.clone();
+        let validators = genesis.validator_set();
+        let slashing = Arc::new(SlashingStore::new(config.slash_retention_blocks));
+        // Evidence expires on the same horizon as slash history: anything
+        // older can no longer be audited against retained events.
+        let evidence = Arc::new(EvidencePool::new(config.slash_retention_blocks));
+        let params = RwLock::new(ParamStore::new(&config));
+        let liveness = RwLock::new(LivenessTracker::new(
+            config.downtime_window_blocks as usize,
+            config.downtime_min_signed_ratio,
+        ));
+        let mut state = ConsensusState::new();
+        state.validator_history.push((0, validators.clone()));
+        let timeouts = RoundTimeouts::from(&config);
+        Self {
+            config,
+            state: Arc::new(RwLock::new(state)),
+            validators: Arc::new(RwLock::new(validators)),
+            mempool,
+            network,
+            tendermint: Arc::new(RwLock::new(TendermintConsensus::with_timeouts(1, timeouts))),
+            tracker,
+            slashing,
+            evidence,
+            vote_history: Arc::new(VoteHistory::new()),
+            pending_updates: RwLock::new(Vec::new()),
+            accounts,
+            staking: RwLock::new(StakingState::new()),
+            liveness,
+            params,
+            upgrade: RwLock::new(UpgradeManager::new()),
+            contracts: Arc::new(crate::contracts::ContractStore::new()),
+            tokens: Arc::new(crate::tokens::TokenRegistry::new()),
+            checkpoint: RwLock::new(CommittedState::default()),
+            store: RwLock::new(None),
+            chain: Arc::new(ChainStore::new(MAX_ORPHAN_BLOCKS)),
+            chain_id: genesis.chain_id.clone(),
+            // The caller probes the backend before handing it over, so
+            // a failure here is transient; an empty address just means
+            // this node never matches the proposer schedule.
+            address: security.address().unwrap_or_else(|err| {
+                log::error!("signer backend cannot derive an address: {err}");
+                String::new()
+            }),
+            security,
+            bls: RwLock::new(None),
+            events: crate::events::EventBus::new(),
+            shutting_down: std::sync::atomic::AtomicBool::new(false),
+        }
+    }
+
+    /// Slash a validator: cut its voting power by `fraction` and record
+    /// the event (with jail status) for audit queries.
+    pub async fn apply_slash(
+        &self,
+        validator: &str,
+        height: u64,
+        reason: SlashReason,
+        fraction: f64,
+        evidence_hash: String,
+        jail: bool,
+    ) -> Result<(), ConsensusError> {
+        let mut validators = self.validators.write().await;
+        let entry = validators
+            .validators
+            .iter_mut()
+            .find(|v| v.address == validator)
+            .ok_or(ConsensusError::NotValidator)?;
+        let amount = (entry.voting_power as f64 * fraction) as u64;
+        entry.voting_power = entry.voting_power.saturating_sub(amount);
+        drop(validators);
+        self.slashing
+            .record(SlashEvent::new(
+                validator.to_string(),
+                height,
+                reason,
+                amount,
+                evidence_hash,
+                jail,
+            ))
+            .await;
+        Ok(())
+    }
+
+    /// Pick the proposer for the current round, rotating priorities so
+    /// proposers alternate proportionally to voting power.
+    pub async fn select_proposer(&self) -> Option<Validator> {
+        self.validators.write().await.advance_proposer()
+    }
+
+    /// Build a block proposal by reaping the mempool in effective-fee
+    /// order, within the governed transaction count, encoded size, and
+    /// block gas limits. A transaction whose nonce is not the next one
+    /// executable for its sender is passed over rather than included
+    /// out of order.
+    pub async fn create_block(&self) -> Result<Block, ConsensusError> {
+        let state = self.state.read().await;
+        let (max_txs, max_size, max_gas, min_gas_price) = {
+            let params = self.params.read().await;
+            (
+                params.current().max_transactions_per_block,
+                params.current().max_block_size,
+                params.current().max_block_gas,
+                params.current().min_gas_price,
+            )
+        };
+        let mut transactions = Vec::new();
+        let mut size = 0usize;
+        let mut gas = 0u64;
+        // Next includable nonce per sender already selected this pass.
+        let mut next_nonces: HashMap<String, u64> = HashMap::new();
+        for tx in self.mempool.pending().await {
+            if transactions.len() >= max_txs {
+                break;
+            }
+            let expected = match next_nonces.get(&tx.sender) {
+                Some(nonce) => *nonce,
+                None => self
+                    .accounts
+                    .get_account(&tx.sender)
+                    .await
+                    .map(|account| account.nonce)
+                    .unwrap_or(0),
+            } + 1;
+            if tx.nonce != expected {
+                continue;
+            }
+            // The chain-wide fee floor; sub-floor txs would invalidate
+            // the whole block.
+            if self.mempool.fee_policy().effective_gas_price(&tx) < min_gas_price {
+                continue;
+            }
+            let tx_size = serde_json::to_vec(&tx).map(|b| b.len()).unwrap_or(0);
+            if size + tx_size > max_size || gas.saturating_add(tx.gas_limit) > max_gas {
+                continue;
+            }
+            size += tx_size;
+            gas += tx.gas_limit;
+            next_nonces.insert(tx.sender.clone(), expected);
+            transactions.push(tx);
+        }
+        // `pending()` already yields fee-descending order; other
+        // policies re-sort the selected set.
+        sort_by_policy(
+            self.config.ordering,
+            self.mempool.fee_policy(),
+            &mut transactions,
+        );
+        for tx in &transactions {
+            self.tracker.record(&tx.hash(), TxStatus::Proposed).await;
+        }
+        let evidence = self.evidence.take_for_block(state.height + 1).await;
+        // Carry the certificate that finalized the current head, so the
+        // new block proves its parent's finality on its own.
+        let last_commit = state
+            .commits
+            .iter()
+            .rev()
+            .find(|commit| commit.height == state.height)
+            .cloned();
+        let mut block = Block::new(
+            state.height + 1,
+            state.last_block_hash.clone(),
+            state.last_state_root.clone(),
+            self.address.clone(),
+            transactions,
+        )
+        .with_evidence(evidence)
+        .with_last_commit(last_commit);
+        // Commit to who may sign this block and under what rules.
+        block.header.validator_hash = hash_validator_set(&*self.validators.read().await);
+        block.header.consensus_hash = self.params.read().await.current().hash();
+        Ok(block)
+    }
+
+    /// Sign a consensus message with this node's validator key. A
+    /// backend failure (a hardware signer losing its session) yields an
+    /// empty signature, which peers simply refuse to count.
+    pub fn sign_message(&self, message: &[u8]) -> Vec<u8> {
+        self.security.sign(message).unwrap_or_else(|err| {
+            log::error!("signer backend failed to sign: {err}");
+            Vec::new()
+        })
+    }
+
+    /// Install the validator's BLS keypair. Precommits signed after
+    /// this carry a BLS signature alongside the ed25519 one, so the
+    /// proposer can fold the round into one aggregated certificate.
+    pub async fn set_bls_key(&self, keypair: bls::BlsKeypair) {
+        *self.bls.write().await = Some(keypair);
+    }
+
+    /// Attach this node's BLS signature over the commit sign-doc to a
+    /// non-nil precommit. Nil precommits never enter a commit, so they
+    /// carry none.
+    async fn attach_bls_signature(&self, vote: &mut Vote) {
+        if vote.vote_type != VoteType::Precommit || vote.is_nil() {
+            return;
+        }
+        if let Some(keypair) = self.bls.read().await.as_ref() {
+            vote.bls_signature = keypair.sign(&commit_signing_bytes(
+                &self.chain_id,
+                vote.height,
+                vote.round,
+                &vote.block_hash,
+            ));
+        }
+    }
+
+    /// Validate a proposed block against the current state.
+    pub async fn verify_block(&self, block: &Block) -> Result<(), ConsensusError> {
+        // Finality floor: nothing at or below the persisted checkpoint
+        // can be replaced, even right after a restart.
+        let checkpoint = self.checkpoint.read().await;
+        if block.header.height <= checkpoint.height && block.hash() != checkpoint.block_hash {
+            return Err(ConsensusError::InvalidBlock(format!(
+                "height {} is at or below the finalized checkpoint {}",
+                block.header.height, checkpoint.height
+            )));
+        }
+        drop(checkpoint);
+        let state = self.state.read().await;
+        if block.header.height != state.height + 1 {
+            return Err(ConsensusError::InvalidBlock(format!(
+                "unexpected height {} (head {})",
+                block.header.height, state.height
+            )));
+        }
+        if block.header.prev_hash != state.last_block_hash {
+            return Err(ConsensusError::InvalidBlock(
+                "prev_hash does not match chain head".into(),
+            ));
+        }
+        // Deferred execution: the header must carry the root this node
+        // computed when it executed the previous block.
+        if block.header.state_root != state.last_state_root {
+            return Err(ConsensusError::InvalidBlock(
+                "state_root does not match locally executed state".into(),
+            ));
+        }
+        if block.header.tx_root != crate::types::block::compute_tx_root(&block.transactions) {
+            return Err(ConsensusError::InvalidBlock("tx_root mismatch".into()));
+        }
+        if let Some(index) = ordering_violation(
+            self.config.ordering,
+            self.mempool.fee_policy(),
+            &block.transactions,
+        ) {
+            return Err(ConsensusError::InvalidBlock(format!(
+                "transactions {index} and {} violate the {:?} ordering policy",
+                index + 1,
+                self.config.ordering
+            )));
+        }
+        if block.header.evidence_root != evidence::compute_evidence_root(&block.evidence) {
+            return Err(ConsensusError::InvalidBlock("evidence_root mismatch".into()));
+        }
+        let min_gas_price = self.params.read().await.current().min_gas_price;
+        if min_gas_price > 0 {
+            for tx in &block.transactions {
+                if self.mempool.fee_policy().effective_gas_price(tx) < min_gas_price {
+                    return Err(ConsensusError::InvalidBlock(format!(
+                        "transaction {} pays below the chain's minimum gas price",
+                        tx.id
+                    )));
+                }
+            }
+        }
+        if block.header.validator_hash != hash_validator_set(&*self.validators.read().await) {
+            return Err(ConsensusError::InvalidBlock(
+                "validator_hash does not match the active set".into(),
+            ));
+        }
+        if block.header.consensus_hash != self.params.read().await.current().hash() {
+            return Err(ConsensusError::InvalidBlock(
+                "consensus_hash does not match the governed parameters".into(),
+            ));
+        }
+        // The header must commit to exactly the carried certificate, and
+        // the certificate must actually finalize the parent block.
+        let expected_commit_hash = block
+            .last_commit
+            .as_deref()
+            .map(Commit::hash)
+            .unwrap_or_default();
+        if block.header.last_commit_hash != expected_commit_hash {
+            return Err(ConsensusError::InvalidBlock(
+                "last_commit_hash mismatch".into(),
+            ));
+        }
+        if let Some(last_commit) = &block.last_commit {
+            if last_commit.height + 1 != block.header.height {
+                return Err(ConsensusError::InvalidBlock(format!(
+                    "last_commit is for height {}, not the parent",
+                    last_commit.height
+                )));
+            }
+            if last_commit.block_hash != block.header.prev_hash {
+                return Err(ConsensusError::InvalidBlock(
+                    "last_commit does not certify the parent block".into(),
+                ));
+            }
+            self.verify_commit(last_commit).await?;
+        }
+        for ev in &block.evidence {
+            if !ev.is_valid() {
+                return Err(ConsensusError::InvalidBlock(format!(
+                    "invalid evidence against {}",
+                    ev.validator()
+                )));
+            }
+            if self.evidence.is_expired(ev.height(), block.header.height) {
+                return Err(ConsensusError::InvalidBlock(format!(
+                    "expired evidence against {}",
+                    ev.validator()
+                )));
+            }
+            // The proof must carry real signatures from the accused key.
+            if let Some(accused) = self.validators.read().await.get(ev.validator()) {
+                if !ev.verify_signatures(&self.chain_id, &accused.public_key) {
+                    return Err(ConsensusError::InvalidBlock(format!(
+                        "unsigned evidence against {}",
+                        ev.validator()
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify a commit certificate: either one aggregated BLS signature
+    /// covering every listed signer, or per-vote ed25519 signatures.
+    /// Both paths must reach +2/3 of the voting power.
+    pub async fn verify_commit(&self, commit: &Commit) -> Result<(), ConsensusError> {
+        let validators = self.validators.read().await;
+        if !commit.bls_signature.is_empty() {
+            let mut power = 0u64;
+            let mut keys = Vec::with_capacity(commit.bls_signers.len());
+            for address in &commit.bls_signers {
+                let validator = validators
+                    .get(address)
+                    .ok_or_else(|| ConsensusError::InvalidCommit("unknown validator".into()))?;
+                if validator.bls_public_key.is_empty() {
+                    return Err(ConsensusError::InvalidCommit(format!(
+                        "{address} has no BLS key"
+                    )));
+                }
+                keys.push(validator.bls_public_key.clone());
+                power += validator.voting_power;
+            }
+            let message = commit_signing_bytes(
+                &self.chain_id,
+                commit.height,
+                commit.round,
+                &commit.block_hash,
+            );
+            if !bls::verify_aggregate(&keys, &message, &commit.bls_signature) {
+                return Err(ConsensusError::InvalidCommit(
+                    "bad aggregated BLS signature".into(),
+                ));
+            }
+            if !TendermintConsensus::has_two_thirds(power, validators.total_power()) {
+                return Err(ConsensusError::InvalidCommit(
+                    "insufficient voting power".into(),
+                ));
+            }
+            return Ok(());
+        }
+        let mut power = 0u64;
+        let mut batch = Vec::with_capacity(commit.votes.len());
+        for vote in &commit.votes {
+            let validator = validators
+                .get(&vote.validator)
+                .ok_or_else(|| ConsensusError::InvalidCommit("unknown validator".into()))?;
+            if vote.block_hash != commit.block_hash {
+                return Err(ConsensusError::InvalidCommit("vote for wrong block".into()));
+            }
+            batch.push((
+                validator.public_key.clone(),
+                vote.signing_bytes(&self.chain_id),
+                vote.signature.clone(),
+            ));
+            power += validator.voting_power;
+        }
+        if !TendermintConsensus::has_two_thirds(power, validators.total_power()) {
+            return Err(ConsensusError::InvalidCommit(
+                "insufficient voting power".into(),
+            ));
+        }
+        drop(validators);
+        // Batch verification amortizes the curve arithmetic across all
+        // votes; a large commit is CPU-bound, so keep it off the async
+        // executor.
+        let verified = tokio::task::spawn_blocking(move || SecurityManager::verify_batch(&batch))
+            .await
+            .unwrap_or(false);
+        if !verified {
+            return Err(ConsensusError::InvalidCommit(
+                "commit signature batch failed verification".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Apply a transaction during block execution, metering gas against
+    /// its `gas_limit`. Returns the gas used. A batch payload executes
+    /// its messages in order under one nonce and one fee; a failing
+    /// message aborts the transaction.
+    pub async fn apply_transaction(&self, tx: &Transaction) -> Result<u64, ConsensusError> {
+        let mut meter = GasMeter::new(tx.gas_limit);
+        meter
+            .charge(gas::TX_BASE_GAS)
+            .map_err(ConsensusError::InvalidBlock)?;
+        if let Some(BatchTx::Batch { msgs }) = BatchTx::parse(tx) {
+            // One authorization check covers the whole batch.
+            let sender = self.accounts.get_account(&tx.sender).await.unwrap_or_default();
+            if sender.frozen {
+                return Err(ConsensusError::InvalidBlock(
+                    "sender account is frozen".into(),
+                ));
+            }
+            for msg in msgs {
+                match msg {
+                    Msg::Transfer {
+                        recipient,
+                        amount,
+                        denom,
+                    } => {
+                        if !sender.permissions.contains(&Permission::Transfer) {
+                            return Err(ConsensusError::InvalidBlock(
+                                "sender lacks transfer permission".into(),
+                            ));
+                        }
+                        meter
+                            .charge(gas::TRANSFER_GAS)
+                            .map_err(ConsensusError::InvalidBlock)?;
+                        self.accounts
+                            .debit_denom(&tx.sender, &denom, amount)
+                            .await
+                            .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+                        self.accounts.credit_denom(&recipient, &denom, amount).await;
+                    }
+                    Msg::Exec { data } => {
+                        let mut sub = tx.clone();
+                        sub.data = data;
+                        self.apply_msg(&sub, &mut meter).await?;
+                    }
+                }
+            }
+            self.accounts
+                .advance_nonce(&tx.sender, tx.nonce)
+                .await
+                .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+            return Ok(meter.used());
+        }
+        if tx.amount > 0 {
+            meter
+                .charge(gas::TRANSFER_GAS)
+                .map_err(ConsensusError::InvalidBlock)?;
+        }
+        self.apply_msg(tx, &mut meter).await?;
+        // Move the value through the ledger: debit the sender, credit
+        // the recipient, and bump the sender's nonce.
+        self.accounts
+            .apply_transaction(tx)
+            .await
+            .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+        Ok(meter.used())
+    }
+
+    /// Execute one module payload (staking, contracts, tokens,
+    /// governance) carried in a transaction's data.
+    async fn apply_msg(
+        &self,
+        tx: &Transaction,
+        meter: &mut GasMeter,
+    ) -> Result<(), ConsensusError> {
+        if let Some(op) = StakingTx::parse(tx) {
+            match &op {
+                StakingTx::CreateValidator { power, .. } => {
+                    // Joining the validator set means casting consensus
+                    // votes, which is a granted capability.
+                    self.require_permission(&tx.sender, Permission::Vote).await?;
+                    let validators = self.validators.read().await;
+                    let update = op
+                        .validator_update(&tx.sender, &validators)
+                        .expect("create always yields an update");
+                    self.pending_updates.write().await.push(update);
+                    // Seed the self-bond so later bonds add onto it.
+                    self.staking.write().await.bond(&tx.sender, *power);
+                }
+                StakingTx::EditValidator { .. } => {
+                    let validators = self.validators.read().await;
+                    match op.validator_update(&tx.sender, &validators) {
+                        Some(update) => self.pending_updates.write().await.push(update),
+                        None => {
+                            return Err(ConsensusError::InvalidBlock(format!(
+                                "staking edit for unknown validator {}",
+                                tx.sender
+                            )))
+                        }
+                    }
+                }
+                StakingTx::Bond { amount } => {
+                    self.accounts
+                        .debit(&tx.sender, *amount)
+                        .await
+                        .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+                    self.staking.write().await.bond(&tx.sender, *amount);
+                }
+                StakingTx::Unbond { amount } => {
+                    let release = self.state.read().await.height
+                        + 1
+                        + self.config.unbonding_period_blocks;
+                    self.staking
+                        .write()
+                        .await
+                        .unbond(&tx.sender, *amount, release)
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+                StakingTx::Delegate { validator, amount } => {
+                    self.accounts
+                        .debit(&tx.sender, *amount)
+                        .await
+                        .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+                    self.staking
+                        .write()
+                        .await
+                        .delegate(&tx.sender, validator, *amount);
+                }
+                StakingTx::Undelegate { validator, amount } => {
+                    let release = self.state.read().await.height
+                        + 1
+                        + self.config.unbonding_period_blocks;
+                    self.staking
+                        .write()
+                        .await
+                        .undelegate(&tx.sender, validator, *amount, release)
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+                StakingTx::Unjail => {
+                    let height = self.state.read().await.height + 1;
+                    match self.liveness.write().await.try_release(&tx.sender, height) {
+                        Some(validator) => {
+                            self.pending_updates.write().await.push(ValidatorUpdate {
+                                address: validator.address.clone(),
+                                public_key: validator.public_key.clone(),
+                                voting_power: validator.voting_power,
+                            });
+                        }
+                        None => {
+                            return Err(ConsensusError::InvalidBlock(format!(
+                                "{} is not eligible for unjail",
+                                tx.sender
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+        if let Some(op) = crate::contracts::ContractTx::parse(tx) {
+            match op {
+                crate::contracts::ContractTx::Deploy { code } => {
+                    // Deployment is a granted capability, not a default.
+                    self.require_permission(&tx.sender, Permission::DeployContract)
+                        .await?;
+                    meter
+                        .charge(gas::CODE_BYTE_GAS.saturating_mul(code.len() as u64))
+                        .map_err(ConsensusError::InvalidBlock)?;
+                    let code_hash = self
+                        .contracts
+                        .insert(code)
+                        .await
+                        .map_err(ConsensusError::InvalidBlock)?;
+                    let address =
+                        crate::contracts::contract_address(&tx.sender, tx.nonce, &code_hash);
+                    self.accounts
+                        .create_contract_account(&address, &code_hash)
+                        .await
+                        .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+                    log::info!(
+                        "deployed contract {address} (code {})",
+                        hex::encode(&code_hash)
+                    );
+                }
+                crate::contracts::ContractTx::Call { contract, input } => {
+                    let code_hash = self
+                        .accounts
+                        .get_account(&contract)
+                        .await
+                        .filter(|account| !account.code_hash.is_empty())
+                        .map(|account| account.code_hash)
+                        .ok_or_else(|| {
+                            ConsensusError::InvalidBlock(format!("{contract} is not a contract"))
+                        })?;
+                    let code = self.contracts.get(&code_hash).await.ok_or_else(|| {
+                        ConsensusError::InvalidBlock(format!(
+                            "missing code {}",
+                            hex::encode(&code_hash)
+                        ))
+                    })?;
+                    let ctx = crate::contracts::vm::CallContext {
+                        contract,
+                        caller: tx.sender.clone(),
+                        input,
+                    };
+                    crate::contracts::vm::execute(&code, &ctx, &self.accounts, meter)
+                        .await
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+            }
+        }
+        if let Some(op) = crate::tokens::TokenTx::parse(tx) {
+            match op {
+                crate::tokens::TokenTx::CreateDenom { denom, max_supply } => {
+                    self.tokens
+                        .create(&tx.sender, &denom, max_supply)
+                        .await
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+                crate::tokens::TokenTx::Mint { denom, to, amount } => {
+                    self.tokens
+                        .mint(&tx.sender, &denom, amount)
+                        .await
+                        .map_err(ConsensusError::InvalidBlock)?;
+                    self.accounts.credit_denom(&to, &denom, amount).await;
+                }
+                crate::tokens::TokenTx::Burn { denom, amount } => {
+                    self.accounts
+                        .debit_denom(&tx.sender, &denom, amount)
+                        .await
+                        .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+                    self.tokens
+                        .burn(&tx.sender, &denom, amount)
+                        .await
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+            }
+        }
+        if let Some(op) = crate::types::fees::FeeGrantTx::parse(tx) {
+            match op {
+                crate::types::fees::FeeGrantTx::GrantAllowance { grantee, allowance } => {
+                    self.accounts
+                        .grant_allowance(&tx.sender, &grantee, allowance)
+                        .await;
+                }
+                crate::types::fees::FeeGrantTx::RevokeAllowance { grantee } => {
+                    self.accounts.revoke_allowance(&tx.sender, &grantee).await;
+                }
+            }
+        }
+        if let Some(gov) = GovTx::parse(tx) {
+            let head = self.state.read().await.height;
+            match gov {
+                GovTx::ParamChange {
+                    key,
+                    value,
+                    effective_height,
+                } => {
+                    self.require_permission(&tx.sender, Permission::Propose)
+                        .await?;
+                    self.params
+                        .write()
+                        .await
+                        .schedule(
+                            ScheduledChange {
+                                key,
+                                value,
+                                effective_height,
+                            },
+                            head,
+                        )
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+                GovTx::Upgrade { name, height, info } => {
+                    self.require_permission(&tx.sender, Permission::Propose)
+                        .await?;
+                    self.upgrade
+                        .write()
+                        .await
+                        .schedule(UpgradePlan { name, height, info }, head)
+                        .map_err(ConsensusError::InvalidBlock)?;
+                }
+                GovTx::CancelUpgrade => {
+                    self.require_permission(&tx.sender, Permission::Propose)
+                        .await?;
+                    if let Some(plan) = self.upgrade.write().await.cancel() {
+                        log::info!("upgrade {} cancelled", plan.name);
+                    }
+                }
+                GovTx::FreezeAccount { address } => {
+                    self.require_authority(&tx.sender)?;
+                    self.accounts.freeze_account(&address).await;
+                    log::warn!("account {address} frozen by authority {}", tx.sender);
+                }
+                GovTx::UnfreezeAccount { address } => {
+                    self.require_authority(&tx.sender)?;
+                    self.accounts.unfreeze_account(&address).await;
+                    log::warn!("account {address} unfrozen by authority {}", tx.sender);
+                }
+                GovTx::GrantPermission {
+                    address,
+                    permission,
+                } => {
+                    self.require_authority(&tx.sender)?;
+                    self.accounts.grant_permission(&address, permission).await;
+                    log::info!("granted {permission:?} to {address}");
+                }
+                GovTx::RevokePermission {
+                    address,
+                    permission,
+                } => {
+                    self.require_authority(&tx.sender)?;
+                    self.accounts.revoke_permission(&address, permission).await;
+                    log::info!("revoked {permission:?} from {address}");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Gate for restricted governance txs (freezes, permission grants):
+    /// only the configured authority account may execute them, and a
+    /// chain with no authority configured cannot execute them at all.
+    fn require_authority(&self, sender: &str) -> Result<(), ConsensusError> {
+        match self.config.authority.as_deref() {
+            Some(authority) if authority == sender => Ok(()),
+            Some(_) => Err(ConsensusError::InvalidBlock(format!(
+                "{sender} is not the governance authority"
+            ))),
+            None => Err(ConsensusError::InvalidBlock(
+                "no governance authority is configured on this chain".into(),
+            )),
+        }
+    }
+
+    /// Reject a tx whose sender does not hold `permission`. Capabilities
+    /// are granted and revoked on-chain via [`GovTx::GrantPermission`].
+    async fn require_permission(
+        &self,
+        sender: &str,
+        permission: Permission,
+    ) -> Result<(), ConsensusError> {
+        if self.accounts.has_permission(sender, permission).await {
+            Ok(())
+        } else {
+            Err(ConsensusError::InvalidBlock(format!(
+                "{sender} lacks the {permission:?} permission"
+            )))
+        }
+    }
+
+    /// Commit a block: apply its transactions and advance the chain head.
+    #[tracing::instrument(
+        name = "finalize_block",
+        skip_all,
+        fields(height = block.header.height, txs = block.transactions.len())
+    )]
+    pub async fn finalize_block(&self, block: Block) -> Result<(), ConsensusError> {
+        let commit_started = std::time::Instant::now();
+        let validators_before = self.validators.read().await.clone();
+        let mut receipts = Vec::with_capacity(block.transactions.len());
+        let mut fees = 0u64;
+        for tx in &block.transactions {
+            let gas_used = self.apply_transaction(tx).await?;
+            // Charge the fee for the gas actually used; it joins the
+            // block reward distributed below. A sponsored transaction
+            // draws on the payer's fee-grant allowance instead of the
+            // sender's balance.
+            let fee = gas_used.saturating_mul(tx.gas_price);
+            let payer = if tx.fee_payer.is_empty() {
+                &tx.sender
+            } else {
+                &tx.fee_payer
+            };
+            if fee > 0 {
+                if payer != &tx.sender {
+                    self.accounts
+                        .use_allowance(payer, &tx.sender, fee)
+                        .await
+                        .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+                }
+                self.accounts
+                    .debit(payer, fee)
+                    .await
+                    .map_err(|e| ConsensusError::InvalidBlock(e.to_string()))?;
+            }
+            fees += fee;
+            receipts.push(TxReceipt {
+                tx_hash: tx.hash(),
+                gas_used,
+                fee,
+            });
+            self.mempool.remove_transaction(&tx.id).await;
+            self.tracker
+                .record(
+                    &tx.hash(),
+                    TxStatus::Committed {
+                        height: block.header.height,
+                    },
+                )
+                .await;
+        }
+        // Committed evidence is what actually slashes; locally observed
+        // double-signing is slashed too for the single-node path.
+        for ev in &block.evidence {
+            if let Err(err) = self
+                .apply_slash(
+                    ev.validator(),
+                    block.header.height,
+                    SlashReason::DoubleSign,
+                    0.05,
+                    hex::encode(ev.hash()),
+                    true,
+                )
+                .await
+            {
+                log::error!("failed to slash {}: {err}", ev.validator());
+            }
+        }
+        let offenders = self.tendermint.read().await.check_double_signing();
+        for offender in offenders {
+            if block.evidence.iter().any(|ev| ev.validator() == offender) {
+                continue;
+            }
+            if let Err(err) = self
+                .apply_slash(
+                    &offender,
+                    block.header.height,
+                    SlashReason::DoubleSign,
+                    0.05,
+                    String::new(),
+                    true,
+                )
+                .await
+            {
+                log::error!("failed to slash {offender}: {err}");
+            }
+        }
+        // Distribute the block reward plus collected fees to the proposer
+        // and the validators that signed this block's precommits.
+        let (signers, commit): (Vec<(String, u64)>, Commit) = {
+            let tendermint = self.tendermint.read().await;
+            let validators = self.validators.read().await;
+            let block_hash = block.hash();
+            let votes: Vec<Vote> = tendermint
+                .round_state
+                .precommits
+                .values()
+                .filter(|vote| vote.block_hash == block_hash)
+                .cloned()
+                .collect();
+            let signers = votes
+                .iter()
+                .filter_map(|vote| {
+                    validators
+                        .get(&vote.validator)
+                        .map(|v| (v.address.clone(), v.voting_power))
+                })
+                .collect();
+            // Fold the BLS-signed precommits into one aggregated proof
+            // when those signers alone reach quorum; otherwise the
+            // per-vote ed25519 certificate stands on its own.
+            let message = commit_signing_bytes(
+                &self.chain_id,
+                block.header.height,
+                tendermint.round_state.round,
+                &block_hash,
+            );
+            let mut bls_signers = Vec::new();
+            let mut bls_parts = Vec::new();
+            let mut bls_power = 0u64;
+            for vote in &votes {
+                let Some(validator) = validators.get(&vote.validator) else {
+                    continue;
+                };
+                if vote.bls_signature.is_empty() || validator.bls_public_key.is_empty() {
+                    continue;
+                }
+                if !bls::verify(&validator.bls_public_key, &message, &vote.bls_signature) {
+                    log::warn!("invalid BLS precommit signature from {}", vote.validator);
+                    continue;
+                }
+                bls_signers.push(validator.address.clone());
+                bls_parts.push(vote.bls_signature.clone());
+                bls_power += validator.voting_power;
+            }
+            let (bls_signers, bls_signature) = match bls::aggregate(&bls_parts) {
+                Some(aggregated)
+                    if TendermintConsensus::has_two_thirds(bls_power, validators.total_power()) =>
+                {
+                    (bls_signers, aggregated)
+                }
+                _ => (Vec::new(), Vec::new()),
+            };
+            let commit = Commit {
+                height: block.header.height,
+                round: tendermint.round_state.round,
+                block_hash,
+                votes,
+                bls_signers,
+                bls_signature,
+            };
+            (signers, commit)
+        };
+        for (address, amount) in
+            rewards::distribute(self.config.block_reward + fees, &block.header.proposer, &signers)
+        {
+            self.accounts.credit(&address, amount).await;
+        }
+        // Downtime: record who signed, jail validators that fell below
+        // the liveness threshold, and auto-release finished jail terms.
+        {
+            let mut liveness = self.liveness.write().await;
+            for validator in liveness.due_for_release(block.header.height) {
+                log::info!("unjailing {} after served term", validator.address);
+                self.pending_updates.write().await.push(ValidatorUpdate {
+                    address: validator.address.clone(),
+                    public_key: validator.public_key.clone(),
+                    voting_power: validator.voting_power,
+                });
+            }
+            let signer_set: std::collections::HashSet<String> =
+                signers.iter().map(|(address, _)| address.clone()).collect();
+            let offenders = {
+                let validators = self.validators.read().await;
+                liveness.record_block(&validators.validators, &signer_set)
+            };
+            for offender in offenders {
+                if let Err(err) = self
+                    .apply_slash(
+                        &offender,
+                        block.header.height,
+                        SlashReason::Downtime,
+                        0.01,
+                        String::new(),
+                        true,
+                    )
+                    .await
+                {
+                    log::error!("failed to slash {offender} for downtime: {err}");
+                    continue;
+                }
+                let jailed = self.validators.read().await.get(&offender).cloned();
+                if let Some(validator) = jailed {
+                    liveness.jail(
+                        validator,
+                        block.header.height + self.config.downtime_jail_blocks,
+                    );
+                    self.pending_updates.write().await.push(ValidatorUpdate {
+                        address: offender.clone(),
+                        public_key: Vec::new(),
+                        voting_power: 0,
+                    });
+                }
+            }
+        }
+        // Release matured unbonding entries and refresh the power of any
+        // validator whose bonded stake changed in this block.
+        {
+            let mut staking_state = self.staking.write().await;
+            for entry in staking_state.mature(block.header.height) {
+                self.accounts.credit(&entry.delegator, entry.amount).await;
+            }
+            let touched = staking_state.take_touched();
+            if !touched.is_empty() {
+                let validators = self.validators.read().await;
+                let mut pending = self.pending_updates.write().await;
+                for address in touched {
+                    if let Some(validator) = validators.get(&address) {
+                        pending.push(ValidatorUpdate {
+                            address: address.clone(),
+                            public_key: validator.public_key.clone(),
+                            voting_power: staking_state.power_of(&address),
+                        });
+                    }
+                }
+            }
+        }
+        // Apply validator changes produced by staking txs in this block,
+        // then diff against the pre-block set for the results record.
+        let staking_updates: Vec<ValidatorUpdate> =
+            self.pending_updates.write().await.drain(..).collect();
+        if !staking_updates.is_empty() {
+            let mut validators = self.validators.write().await;
+            staking::apply_validator_updates(&mut validators, &staking_updates);
+        }
+        let validators_after = self.validators.read().await.clone();
+        let validator_updates = diff_validator_sets(&validators_before, &validators_after);
+        // Seal this height's account versions for historical queries,
+        // and carry the executed state root into the next header.
+        self.accounts.commit_version(block.header.height).await;
+        let state_root = self.accounts.state_root().await;
+        let mut state = self.state.write().await;
+        state.height = block.header.height;
+        state.snapshot_height = block.header.height;
+        state.last_block_hash = block.hash();
+        state.last_state_root = state_root;
+        state
+            .state_tree
+            .insert(&block.header.height.to_be_bytes(), &block.hash());
+        if !validator_updates.is_empty() {
+            state
+                .validator_history
+                .push((block.header.height, validators_after));
+        }
+        state.results.push(BlockResults {
+            height: block.header.height,
+            validator_updates: validator_updates.clone(),
+            receipts,
+        });
+        // Feed the finalized block to the fork-aware index. Our own
+        // commit is verified by construction, so the block is
+        // immediately head-eligible there.
+        self.chain.insert(block.clone(), true).await;
+        state.commits.push(commit);
+        state.blocks.push(block);
+        // Flip governed parameters whose effective height has arrived.
+        for change in self.params.write().await.apply_due(state.height) {
+            log::info!(
+                "governed parameter {} changed to {} at height {}",
+                change.key,
+                change.value,
+                state.height
+            );
+        }
+        let mut tendermint = self.tendermint.write().await;
+        let metrics = crate::metrics::Metrics::handle();
+        // Rounds are zero-based; a height that commits in round 0 took
+        // one round. The precommit step ends here when the round ran.
+        metrics
+            .rounds_per_height
+            .observe_value(f64::from(tendermint.round_state.round) + 1.0);
+        if tendermint.round_state.step == tendermint::Step::Precommit {
+            metrics
+                .precommit_duration
+                .observe(tendermint.round_state.step_started.elapsed());
+        }
+        *tendermint =
+            TendermintConsensus::with_timeouts(state.height + 1, RoundTimeouts::from(&self.config));
+        self.vote_history
+            .prune_below(state.height.saturating_sub(self.config.slash_retention_blocks))
+            .await;
+        // Advance and persist the finality checkpoint: everything at or
+        // below this height is now irreversible.
+        let committed = CommittedState {
+            height: state.height,
+            block_hash: state.last_block_hash.clone(),
+        };
+        let stored = state.blocks.last().expect("just pushed");
+        let block_bytes = serde_json::to_vec(stored);
+        let commit_bytes = serde_json::to_vec(state.commits.last().expect("just pushed"));
+        let tx_hashes: Vec<String> = stored.transactions.iter().map(|tx| tx.hash()).collect();
+        let proposer = stored.header.proposer.clone();
+        let timestamp = stored.header.timestamp;
+        let confirmed_evidence: Vec<(String, String)> = stored
+            .evidence
+            .iter()
+            .map(|ev| (ev.validator().to_string(), hex::encode(ev.hash())))
+            .collect();
+        drop(state);
+        drop(tendermint);
+        // Persist the block, its transaction index entries, and the
+        // checkpoint in one atomic batch: a crash can never leave the
+        // index pointing at a block that was not written.
+        if let Some(store) = self.store.read().await.clone() {
+            match (block_bytes, serde_json::to_vec(&committed)) {
+                (Ok(block_bytes), Ok(checkpoint_bytes)) => {
+                    let mut batch = WriteBatch::new();
+                    batch.put(Column::Blocks, &block_key(committed.height), &block_bytes);
+                    for hash in &tx_hashes {
+                        batch.put(
+                            Column::TxIndex,
+                            hash.as_bytes(),
+                            &committed.height.to_be_bytes(),
+                        );
+                    }
+                    batch.put(Column::State, CHECKPOINT_KEY, &checkpoint_bytes);
+                    // The canonical commit rides in the same batch, so a
+                    // stored block always has its certificate alongside.
+                    match &commit_bytes {
+                        Ok(bytes) => {
+                            batch.put(Column::Commits, &block_key(committed.height), bytes)
+                        }
+                        Err(err) => {
+                            log::error!("failed to encode commit {}: {err}", committed.height)
+                        }
+                    }
+                    if let Err(err) = store.apply(batch) {
+                        log::error!("failed to persist block {}: {err}", committed.height);
+                    }
+                }
+                (Err(err), _) | (_, Err(err)) => {
+                    log::error!("failed to encode block {}: {err}", committed.height)
+                }
+            }
+        }
+        *self.checkpoint.write().await = committed.clone();
+        metrics.record_block(committed.height, tx_hashes.len() as u64);
+        metrics.commit_duration.observe(commit_started.elapsed());
+        // Announce the commit on the event bus, block first so
+        // subscribers see the container before its contents.
+        self.events.publish(crate::events::ChainEvent::NewBlock {
+            height: committed.height,
+            block_hash: hex::encode(&committed.block_hash),
+            proposer,
+            transactions: tx_hashes.len(),
+            timestamp,
+        });
+        for hash in &tx_hashes {
+            self.events.publish(crate::events::ChainEvent::TxCommitted {
+                hash: hash.clone(),
+                height: committed.height,
+            });
+        }
+        if !validator_updates.is_empty() {
+            self.events
+                .publish(crate::events::ChainEvent::ValidatorSetUpdated {
+                    height: committed.height,
+                    updates: validator_updates,
+                });
+        }
+        for (validator, evidence_hash) in confirmed_evidence {
+            self.events
+                .publish(crate::events::ChainEvent::EvidenceConfirmed {
+                    height: committed.height,
+                    validator,
+                    evidence_hash,
+                });
+        }
+        self.recheck_mempool().await;
+        log::info!("committed block at height {}", committed.height);
+        Ok(())
+    }
+
+    /// Re-validate every pending transaction against the post-commit
+    /// state and drop the ones the chain can no longer execute: spent
+    /// nonces, fees below the governed minimum, and senders that no
+    /// longer cover the transfer plus worst-case fee.
+    async fn recheck_mempool(&self) {
+        let min_gas_price = self.params.read().await.current().min_gas_price;
+        for tx in self.mempool.pending().await {
+            let account = self.accounts.get_account(&tx.sender).await;
+            let (nonce, balance) = account
+                .map(|account| (account.nonce, account.balance))
+                .unwrap_or((0, 0));
+            let reason = if tx.nonce <= nonce {
+                Some("nonce already spent")
+            } else if self.mempool.fee_policy().effective_gas_price(&tx) < min_gas_price {
+                Some("gas price below the governed minimum")
+            } else {
+                // Worst-case native debit: the transfer when it is in
+                // the native denom, plus the fee when the sender pays
+                // its own way.
+                let mut needed = if crate::security::state::is_native(&tx.denom) {
+                    tx.amount
+                } else {
+                    0
+                };
+                if tx.fee_payer.is_empty() {
+                    needed = needed.saturating_add(tx.gas_limit.saturating_mul(tx.gas_price));
+                }
+                (balance < needed).then_some("insufficient balance after commit")
+            };
+            if let Some(reason) = reason {
+                self.mempool.remove_transaction(&tx.id).await;
+                self.tracker
+                    .record(
+                        &tx.hash(),
+                        TxStatus::Failed {
+                            reason: format!("dropped in post-commit recheck: {reason}"),
+                        },
+                    )
+                    .await;
+            }
+        }
+    }
+
+    /// The latest finalized (irreversible) height and block hash.
+    pub async fn committed_state(&self) -> CommittedState {
+        self.checkpoint.read().await.clone()
+    }
+
+    /// Height of the committed block containing `tx_hash`, looked up in
+    /// the persistent transaction index.
+    pub async fn tx_block_height(&self, tx_hash: &str) -> Option<u64> {
+        let store = self.store.read().await.clone()?;
+        let bytes = store
+            .get(&Column::TxIndex.key(tx_hash.as_bytes()))
+            .ok()
+            .flatten()?;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    }
+
+    /// Recover persisted chain data from `store` and persist to it from
+    /// here on: restore the finality checkpoint, then replay any
+    /// persisted blocks the in-memory state has not executed yet —
+    /// after a crash mid-commit the block store can be ahead of the
+    /// state — so the state root is consistent before consensus starts.
+    #[tracing::instrument(name = "recover_from_store", skip_all)]
+    pub async fn recover_from(&self, store: Arc<dyn KvStore>) {
+        if let Ok(Some(bytes)) = store.get(&Column::State.key(CHECKPOINT_KEY)) {
+            match serde_json::from_slice::<CommittedState>(&bytes) {
+                Ok(saved) => {
+                    let mut checkpoint = self.checkpoint.write().await;
+                    if saved.height > checkpoint.height {
+                        log::info!("restored finality checkpoint at height {}", saved.height);
+                        *checkpoint = saved;
+                    }
+                }
+                Err(err) => log::warn!("ignoring corrupt checkpoint record: {err}"),
+            }
+        }
+        *self.store.write().await = Some(Arc::clone(&store));
+        // Anchor the deferred-execution root chain: the first block
+        // commits the state produced by applying the genesis accounts.
+        {
+            let mut state = self.state.write().await;
+            if state.height == 0 {
+                state.last_state_root = self.accounts.state_root().await;
+            }
+        }
+        loop {
+            let next = self.state.read().await.height + 1;
+            let Ok(Some(bytes)) = store.get(&Column::Blocks.key(&block_key(next))) else {
+                break;
+            };
+            match serde_json::from_slice::<Block>(&bytes) {
+                Ok(block) => {
+                    log::info!("replaying persisted block {next}");
+                    if let Err(err) = self.finalize_block(block).await {
+                        log::error!("replay of block {next} failed: {err}");
+                        break;
+                    }
+                    // Replay sees no live precommits, so swap in the
+                    // canonical commit persisted with the block.
+                    if let Ok(Some(bytes)) = store.get(&Column::Commits.key(&block_key(next))) {
+                        match serde_json::from_slice::<Commit>(&bytes) {
+                            Ok(saved) => {
+                                let mut state = self.state.write().await;
+                                if let Some(commit) =
+                                    state.commits.iter_mut().find(|c| c.height == next)
+                                {
+                                    *commit = saved;
+                                }
+                            }
+                            Err(err) => {
+                                log::warn!("ignoring corrupt commit record at {next}: {err}")
+                            }
+                        }
+                    }
+                }
+                Err(err) => {
+                    log::error!("corrupt persisted block {next}: {err}");
+                    break;
+                }
+            }
+        }
+    }
+
+    /// Apply one incoming consensus message to the round state.
+    #[tracing::instrument(name = "consensus_message", skip_all, fields(height = message.height()))]
+    pub async fn handle_message(&self, message: ConsensusMessage) {
+        match message {
+            ConsensusMessage::Proposal {
+                height,
+                round,
+                block,
+                proposer,
+                signature,
+            } => {
+                // The proposal must be signed by a known validator over
+                // the chain-scoped sign-doc; a foreign or forged proposal
+                // is dropped before it can become our round's proposal.
+                let signed = {
+                    let validators = self.validators.read().await;
+                    validators.get(&proposer).is_some_and(|validator| {
+                        SecurityManager::verify(
+                            &validator.public_key,
+                            &proposal_signing_bytes(&self.chain_id, height, round, &block.hash()),
+                            &signature,
+                        )
+                    })
+                };
+                if signed && self.verify_block(&block).await.is_ok() {
+                    let mut tendermint = self.tendermint.write().await;
+                    if block.header.height == tendermint.round_state.height {
+                        tendermint.round_state.proposal = Some(block);
+                    }
+                }
+            }
+            ConsensusMessage::Vote(vote) => {
+                // Check the signing history first: a conflicting vote for
+                // an already-signed slot is gossipable equivocation proof.
+                if let Some(evidence) = self.vote_history.observe(vote.clone()).await {
+                    log::warn!("equivocation by {} at height {}", evidence.validator(), evidence.height());
+                    if self.evidence.add(evidence.clone()).await {
+                        self.network
+                            .broadcast(ConsensusMessage::Evidence(evidence))
+                            .await;
+                    }
+                }
+                let validators = self.validators.read().await;
+                let total = validators.total_power();
+                let is_prevote = vote.vote_type == VoteType::Prevote;
+                let mut tendermint = self.tendermint.write().await;
+                if tendermint.add_vote(vote) && is_prevote {
+                    tendermint.update_lock_on_polka(total, |address| {
+                        validators.get(address).map_or(0, |v| v.voting_power)
+                    });
+                    // A polka (for a block or for nil) resolves our
+                    // prevote step: precommit what the polka says.
+                    if tendermint.round_state.step == tendermint::Step::Prevote
+                        && tendermint.round_state.polka.is_some()
+                    {
+                        let height = tendermint.round_state.height;
+                        let round = tendermint.round_state.round;
+                        let decision = tendermint.precommit_decision();
+                        crate::metrics::Metrics::handle()
+                            .prevote_duration
+                            .observe(tendermint.round_state.step_started.elapsed());
+                        tendermint.round_state.step = tendermint::Step::Precommit;
+                        tendermint.round_state.step_started = std::time::Instant::now();
+                        drop(tendermint);
+                        self.broadcast_vote(VoteType::Precommit, height, round, decision)
+                            .await;
+                    }
+                }
+            }
+            ConsensusMessage::Commit(_) => {}
+            ConsensusMessage::Evidence(evidence) => {
+                // Re-gossip evidence we have not seen before so it
+                // reaches the next proposer.
+                if self.evidence.add(evidence.clone()).await {
+                    self.network
+                        .broadcast(ConsensusMessage::Evidence(evidence))
+                        .await;
+                }
+            }
+        }
+    }
+
+    /// Handle a block gossiped outside our own proposal path and offer
+    /// it to the fork-aware chain store, which parks orphans until
+    /// their parent arrives. A block cannot carry its own certificate:
+    /// its `last_commit` commits the parent, so a verified one marks
+    /// the parent committed (and head-eligible) in the store.
+    pub async fn receive_block(&self, block: Block) {
+        let height = block.header.height;
+        if let Some(commit) = block.last_commit.as_deref() {
+            match self.verify_commit(commit).await {
+                Ok(()) => {
+                    if let Some(parent) = self.chain.block(&block.header.prev_hash).await {
+                        self.chain.insert(parent, true).await;
+                    }
+                }
+                Err(err) => {
+                    log::debug!("dropping gossiped block at height {height}: {err}");
+                    return;
+                }
+            }
+        }
+        match self.chain.insert(block, false).await {
+            InsertOutcome::Orphaned => {
+                log::debug!("parked orphan block at height {height}");
+            }
+            InsertOutcome::Connected => {
+                let head_height = self
+                    .chain
+                    .head()
+                    .await
+                    .map_or(0, |head| head.header.height);
+                let local = self.height().await;
+                if head_height > local {
+                    log::info!(
+                        "peers are committed at height {head_height}, local height is {local}"
+                    );
+                }
+            }
+            InsertOutcome::Duplicate => {}
+        }
+    }
+
+    /// Run one pruning pass. Returns the new retain floor when anything
+    /// was pruned: block bodies, results, and account state versions
+    /// below the floor are deleted. The floor is clamped to the latest
+    /// snapshot height so pruning can never outrun sealed state.
+    pub async fn prune_once(&self, last_floor: u64) -> Option<u64> {
+        let (head, snapshot) = {
+            let state = self.state.read().await;
+            (state.height, state.snapshot_height)
+        };
+        let floor = self.config.pruning.retain_floor(head, last_floor)?;
+        let floor = floor.min(snapshot);
+        if floor <= last_floor {
+            return None;
+        }
+        let mut state = self.state.write().await;
+        state.blocks.retain(|b| b.header.height >= floor);
+        state.results.retain(|r| r.height >= floor);
+        state.commits.retain(|c| c.height >= floor);
+        // Keep the newest snapshot below the floor: it still describes
+        // the set in effect at the floor itself.
+        let cut = state
+            .validator_history
+            .partition_point(|(height, _)| *height < floor);
+        if cut > 1 {
+            state.validator_history.drain(..cut - 1);
+        }
+        drop(state);
+        self.accounts.prune_versions_below(floor).await;
+        log::info!("pruned state and blocks below height {floor}");
+        Some(floor)
+    }
+
+    /// Background task applying the configured pruning policy.
+    pub async fn run_pruner(self: Arc<Self>) {
+        if self.config.pruning == PruningConfig::Everything {
+            return;
+        }
+        let mut last_floor = 0u64;
+        let mut ticker = tokio::time::interval(Duration::from_secs(10));
+        loop {
+            ticker.tick().await;
+            if let Some(floor) = self.prune_once(last_floor).await {
+                last_floor = floor;
+            }
+        }
+    }
+
+    /// Ask the proposer loop to stop before starting another height. An
+    /// in-flight block still finalizes, so shutdown never tears a
+    /// commit in half.
+    pub fn begin_shutdown(&self) {
+        self.shutting_down
+            .store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+
+    /// Consume inbound consensus messages from the network lanes.
+    pub async fn run_messages(self: Arc<Self>) {
+        while let Some(message) = self.network.recv_message().await {
+            self.handle_message(message).await;
+        }
+    }
+
+    /// Broadcast a signed vote for (height, round); an empty hash is nil.
+    async fn broadcast_vote(&self, vote_type: VoteType, height: u64, round: u32, block_hash: Vec<u8>) {
+        let mut vote = Vote::new(vote_type, height, round, block_hash, self.address.clone());
+        self.attach_bls_signature(&mut vote).await;
+        vote.signature = self.sign_message(&vote.signing_bytes(&self.chain_id));
+        self.network.broadcast(ConsensusMessage::Vote(vote)).await;
+    }
+
+    /// Fire round-step timeouts: prevote nil when the proposal is late,
+    /// precommit nil when no polka forms, and start the next round (with
+    /// escalated timeouts) when precommit stalls.
+    pub async fn run_timers(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(Duration::from_millis(100));
+        loop {
+            ticker.tick().await;
+            let step_started = self.tendermint.read().await.round_state.step_started;
+            let action = self
+                .tendermint
+                .write()
+                .await
+                .advance_step_if_due(std::time::Instant::now());
+            let metrics = crate::metrics::Metrics::handle();
+            match action {
+                Some(TimeoutAction::PrevoteNil { height, round }) => {
+                    log::debug!("propose timeout at {height}/{round}, prevoting nil");
+                    metrics.timeouts_propose.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    metrics.propose_duration.observe(step_started.elapsed());
+                    self.broadcast_vote(VoteType::Prevote, height, round, Vec::new()).await;
+                }
+                Some(TimeoutAction::PrecommitNil { height, round }) => {
+                    log::debug!("prevote timeout at {height}/{round}, precommitting nil");
+                    metrics.timeouts_prevote.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    metrics.prevote_duration.observe(step_started.elapsed());
+                    self.broadcast_vote(VoteType::Precommit, height, round, Vec::new()).await;
+                }
+                Some(TimeoutAction::NextRound { height, round }) => {
+                    log::warn!("round failed at height {height}, starting round {round}");
+                    metrics.timeouts_precommit.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    metrics.precommit_duration.observe(step_started.elapsed());
+                }
+                None => {}
+            }
+        }
+    }
+
+    /// Watch for a wedged height: if no block commits for
+    /// [`STALL_MULTIPLIER`] times the expected block interval, log the
+    /// round diagnostics an operator needs (step, votes seen, expected
+    /// proposer, connected peers), count the event, and force the next
+    /// round so the escalated timeout schedule gets a fresh chance.
+    pub async fn run_watchdog(self: Arc<Self>) {
+        let mut last_height = self.state.read().await.height;
+        let mut last_advance = std::time::Instant::now();
+        let mut ticker = tokio::time::interval(Duration::from_millis(500));
+        loop {
+            ticker.tick().await;
+            let height = self.state.read().await.height;
+            if height != last_height {
+                last_height = height;
+                last_advance = std::time::Instant::now();
+                continue;
+            }
+            let interval_ms = self.params.read().await.current().block_interval_ms;
+            let stall_after = Duration::from_millis(interval_ms) * STALL_MULTIPLIER;
+            if last_advance.elapsed() < stall_after {
+                continue;
+            }
+            // Peek at the rotation without advancing the real priorities.
+            let expected_proposer = self
+                .validators
+                .read()
+                .await
+                .clone()
+                .advance_proposer()
+                .map(|v| v.address)
+                .unwrap_or_else(|| "<none>".into());
+            let peers = crate::metrics::Metrics::handle()
+                .peer_count
+                .load(std::sync::atomic::Ordering::Relaxed);
+            let mut tendermint = self.tendermint.write().await;
+            let round = tendermint.round_state.round;
+            log::warn!(
+                "height {} stalled for {:?} at round {round} ({:?} step, {} prevotes, {} precommits, expected proposer {expected_proposer}, {peers} peers); forcing round {}",
+                height + 1,
+                last_advance.elapsed(),
+                tendermint.round_state.step,
+                tendermint.round_state.prevotes.len(),
+                tendermint.round_state.precommits.len(),
+                round + 1,
+            );
+            crate::metrics::Metrics::handle()
+                .stalled_rounds
+                .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            tendermint.start_round(round + 1);
+            last_advance = std::time::Instant::now();
+        }
+    }
+
+    /// Build, self-vote, and finalize blocks on a fixed interval.
+    ///
+    /// This is a simplified single-node loop; real multi-node rounds are
+    /// driven by the tendermint module as messages arrive.
+    pub async fn run(self: Arc<Self>) {
+        tokio::spawn(Arc::clone(&self).run_timers());
+        tokio::spawn(Arc::clone(&self).run_messages());
+        tokio::spawn(Arc::clone(&self).run_pruner());
+        tokio::spawn(Arc::clone(&self).run_watchdog());
+        let mut last_block_at = std::time::Instant::now();
+        loop {
+            // Read the interval each round so governance changes apply.
+            let interval_ms = self.params.read().await.current().block_interval_ms;
+            tokio::time::sleep(Duration::from_millis(interval_ms)).await;
+            // A block that already started finalizing runs to
+            // completion; we only refuse to start another height.
+            if self
+                .shutting_down
+                .load(std::sync::atomic::Ordering::Relaxed)
+            {
+                log::info!("consensus loop stopped for shutdown");
+                return;
+            }
+            // With nothing pending, either hold off entirely or pace
+            // empty blocks to the configured target time.
+            if self.mempool.is_empty().await {
+                if !self.config.create_empty_blocks {
+                    continue;
+                }
+                let target = Duration::from_millis(self.config.target_block_time_ms);
+                if last_block_at.elapsed() < target {
+                    continue;
+                }
+            }
+            // Halt at a scheduled upgrade height; the new binary resumes
+            // from the persisted state.
+            {
+                let next_height = self.state.read().await.height + 1;
+                if let Some(plan) = self.upgrade.read().await.should_halt(next_height) {
+                    log::warn!(
+                        "halting at height {} for upgrade {}: expecting {}",
+                        next_height,
+                        plan.name,
+                        plan.info
+                    );
+                    return;
+                }
+            }
+            let proposer = match self.select_proposer().await {
+                Some(v) => v,
+                None => continue,
+            };
+            if proposer.address != self.address {
+                continue;
+            }
+            let proposing_since = std::time::Instant::now();
+            match self.create_block().await {
+                Ok(block) => {
+                    crate::metrics::Metrics::handle()
+                        .propose_duration
+                        .observe(proposing_since.elapsed());
+                    let height = block.header.height;
+                    let signature = self.sign_message(&proposal_signing_bytes(
+                        &self.chain_id,
+                        height,
+                        0,
+                        &block.hash(),
+                    ));
+                    self.network
+                        .broadcast(ConsensusMessage::Proposal {
+                            height,
+                            round: 0,
+                            block: block.clone(),
+                            proposer: self.address.clone(),
+                            signature,
+                        })
+                        .await;
+                    let mut vote = Vote::new(
+                        VoteType::Precommit,
+                        height,
+                        0,
+                        block.hash(),
+                        self.address.clone(),
+                    );
+                    self.attach_bls_signature(&mut vote).await;
+                    vote.signature = self.sign_message(&vote.signing_bytes(&self.chain_id));
+                    self.network
+                        .broadcast(ConsensusMessage::Vote(vote.clone()))
+                        .await;
+                    // Count our own precommit toward the round state so
+                    // rewards and liveness see this node as a signer.
+                    self.handle_message(ConsensusMessage::Vote(vote)).await;
+                    if let Err(err) = self.finalize_block(block).await {
+                        log::error!("failed to finalize block: {err}");
+                    }
+                    last_block_at = std::time::Instant::now();
+                }
+                Err(err) => log::error!("failed to create block: {err}"),
+            }
+        }
+    }
+
+    pub async fn height(&self) -> u64 {
+        self.state.read().await.height
+    }
+
+    /// Serve a light client header request from retained blocks and
+    /// commits.
+    pub async fn signed_header(&self, height: u64) -> Option<crate::network::light::SignedHeader> {
+        let state = self.state.read().await;
+        let header = state
+            .blocks
+            .iter()
+            .find(|b| b.header.height == height)?
+            .header
+            .clone();
+        let commit = state.commits.iter().find(|c| c.height == height)?.clone();
+        Some(crate::network::light::SignedHeader { header, commit })
+    }
+
+    /// Validator set in effect at `height`, from retained snapshots.
+    pub async fn validator_set_at(&self, height: u64) -> Option<ValidatorSet> {
+        let state = self.state.read().await;
+        state
+            .validator_history
+            .iter()
+            .rev()
+            .find(|(snapshot_height, _)| *snapshot_height <= height)
+            .map(|(_, set)| set.clone())
+    }
+}
+
+/// Canonical chain identity hash, computed from the network id, consensus
+/// parameters, and the initial validator set. Two nodes agree on this value
+/// only if they were initialized from the same genesis, so the handshake
+/// can reject accidental cross-network peering even when the `network_id`
+/// string happens to match.
+pub fn genesis_hash(
+    network_id: &str,
+    config: &ConsensusConfig,
+    validators: &ValidatorSet,
+) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(network_id.as_bytes());
+    hasher.update(config.block_interval_ms.to_be_bytes());
+    hasher.update((config.max_block_size as u64).to_be_bytes());
+    hasher.update((config.max_transactions_per_block as u64).to_be_bytes());
+    hasher.update(hash_validator_set(validators));
+    hasher.finalize().to_vec()
+}
+
+/// Hash a validator set deterministically.
+pub fn hash_validator_set(set: &ValidatorSet) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for validator in &set.validators {
+        hasher.update(&validator.public_key);
+        hasher.update(validator.voting_power.to_be_bytes());
+    }
+    hasher.finalize().to_vec()
+}
+
+/// Build a single-validator set for this node (used at startup).
+pub fn single_validator(address: String, public_key: Vec<u8>) -> ValidatorSet {
+    ValidatorSet::new(vec![Validator {
+        address,
+        public_key,
+        bls_public_key: Vec::new(),
+        voting_power: 1,
+        proposer_priority: 0,
+    }])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validator_set_power() {
+        let set = ValidatorSet::new(vec![
+            Validator {
+                address: "a".into(),
+                public_key: vec![1],
+                bls_public_key: Vec::new(),
+                voting_power: 10,
+                proposer_priority: 0,
+            },
+            Validator {
+                address: "b".into(),
+                public_key: vec![2],
+                bls_public_key: Vec::new(),
+                voting_power: 5,
+                proposer_priority: 0,
+            },
+        ]);
+        assert_eq!(set.total_power(), 15);
+        assert!(set.contains("a"));
+        assert!(!set.contains("c"));
+    }
+
+    #[test]
+    fn validator_diff_reports_changes_and_removals() {
+        let make = |address: &str, power: u64| Validator {
+            address: address.into(),
+            public_key: vec![0],
+            bls_public_key: Vec::new(),
+            voting_power: power,
+            proposer_priority: 0,
+        };
+        let old = ValidatorSet::new(vec![make("a", 10), make("b", 5)]);
+        let new = ValidatorSet::new(vec![make("a", 8), make("c", 3)]);
+        let updates = diff_validator_sets(&old, &new);
+        assert_eq!(updates.len(), 3);
+        assert!(updates.iter().any(|u| u.address == "a" && u.voting_power == 8));
+        assert!(updates.iter().any(|u| u.address == "b" && u.voting_power == 0));
+        assert!(updates.iter().any(|u| u.address == "c" && u.voting_power == 3));
+    }
+
+    #[test]
+    fn proposer_rotation_is_proportional_to_power() {
+        let mut set = ValidatorSet::new(vec![
+            Validator {
+                address: "a".into(),
+                public_key: vec![1],
+                bls_public_key: Vec::new(),
+                voting_power: 3,
+                proposer_priority: 0,
+            },
+            Validator {
+                address: "b".into(),
+                public_key: vec![2],
+                bls_public_key: Vec::new(),
+                voting_power: 1,
+                proposer_priority: 0,
+            },
+        ]);
+        let mut counts = std::collections::HashMap::new();
+        for _ in 0..40 {
+            let proposer = set.advance_proposer().unwrap();
+            *counts.entry(proposer.address).or_insert(0u32) += 1;
+        }
+        assert_eq!(counts["a"], 30);
+        assert_eq!(counts["b"], 10);
+    }
+
+    #[tokio::test]
+    async fn pruner_respects_retention_and_snapshot_floor() {
+        let mut genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        genesis.consensus.pruning = PruningConfig::Recent { keep: 2 };
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::new(SecurityManager::new()),
+        );
+        {
+            let mut state = engine.state.write().await;
+            for height in 1..=5 {
+                state.blocks.push(Block::new(
+                    height,
+                    vec![0; 32],
+                    vec![0; 32],
+                    "val0".into(),
+                    Vec::new(),
+                ));
+                state.results.push(BlockResults {
+                    height,
+                    validator_updates: Vec::new(),
+                    receipts: Vec::new(),
+                });
+            }
+            state.height = 5;
+            // The snapshot lags the head; pruning must stop there even
+            // though retention alone would allow height 4.
+            state.snapshot_height = 3;
+        }
+        assert_eq!(engine.prune_once(0).await, Some(3));
+        let state = engine.state.read().await;
+        let heights: Vec<u64> = state.blocks.iter().map(|b| b.header.height).collect();
+        assert_eq!(heights, vec![3, 4, 5]);
+        assert_eq!(state.results.len(), 3);
+        drop(state);
+        // Nothing new to prune until the head advances.
+        assert_eq!(engine.prune_once(3).await, None);
+    }
+
+    #[tokio::test]
+    async fn finalized_transfers_move_balances_and_update_root() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("alice", 100_000).await;
+        accounts.commit_version(0).await;
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let tx = Transaction::new("alice".into(), "bob".into(), 100, 1, 30_000, 1, Vec::new());
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![tx]);
+        engine.finalize_block(block).await.unwrap();
+
+        let bob = accounts.get_account("bob").await.unwrap();
+        assert_eq!(bob.balance, 100);
+        let alice = accounts.get_account("alice").await.unwrap();
+        assert_eq!(alice.nonce, 1);
+        // Sender paid the amount plus gas_used * gas_price.
+        let fee = gas::TX_BASE_GAS + gas::TRANSFER_GAS;
+        assert_eq!(alice.balance, 100_000 - 100 - fee);
+        // The executed state root feeds the next header.
+        let state = engine.state.read().await;
+        assert_eq!(state.last_state_root, accounts.state_root().await);
+        assert_ne!(state.last_state_root, vec![0u8; 32]);
+    }
+
+    #[tokio::test]
+    async fn batched_messages_execute_under_one_nonce() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("alice", 10_000).await;
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let payload = serde_json::to_vec(&BatchTx::Batch {
+            msgs: vec![
+                Msg::Transfer {
+                    recipient: "bob".into(),
+                    amount: 100,
+                    denom: String::new(),
+                },
+                Msg::Transfer {
+                    recipient: "carol".into(),
+                    amount: 200,
+                    denom: String::new(),
+                },
+            ],
+        })
+        .unwrap();
+        let tx = Transaction::new("alice".into(), String::new(), 0, 1, 50_000, 0, payload);
+        let gas_used = engine.apply_transaction(&tx).await.unwrap();
+        assert_eq!(gas_used, gas::TX_BASE_GAS + 2 * gas::TRANSFER_GAS);
+        assert_eq!(accounts.get_account("bob").await.unwrap().balance, 100);
+        assert_eq!(accounts.get_account("carol").await.unwrap().balance, 200);
+        let alice = accounts.get_account("alice").await.unwrap();
+        assert_eq!(alice.balance, 9_700);
+        // The whole batch consumed a single nonce, and a replay fails.
+        assert_eq!(alice.nonce, 1);
+        assert!(engine.apply_transaction(&tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn recovery_replays_persisted_blocks_into_state() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let store: Arc<dyn KvStore> = Arc::new(crate::storage::memory::MemoryStore::new());
+        let make_engine = |accounts: Arc<StateSecurityManager>| {
+            ConsensusEngine::new(
+                &genesis,
+                Arc::new(TransactionPool::new(10)),
+                Arc::new(ConsensusNetworkManager::new()),
+                Arc::new(TxTracker::default()),
+                accounts,
+                Arc::new(SecurityManager::new()),
+            )
+        };
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("alice", 100_000).await;
+        let engine = make_engine(Arc::clone(&accounts));
+        engine.recover_from(Arc::clone(&store)).await;
+        let tx = Transaction::new("alice".into(), "bob".into(), 100, 1, 30_000, 1, Vec::new());
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![tx]);
+        engine.finalize_block(block).await.unwrap();
+        let root = engine.state.read().await.last_state_root.clone();
+
+        // A "crashed" node restarts with genesis balances only; replay
+        // reconstructs the executed state and the same root.
+        let recovered_accounts = Arc::new(StateSecurityManager::new());
+        recovered_accounts.set_balance("alice", 100_000).await;
+        let recovered = make_engine(Arc::clone(&recovered_accounts));
+        recovered.recover_from(Arc::clone(&store)).await;
+        assert_eq!(recovered.height().await, 1);
+        assert_eq!(
+            recovered_accounts.get_account("bob").await.unwrap().balance,
+            100
+        );
+        assert_eq!(recovered.state.read().await.last_state_root, root);
+    }
+
+    #[tokio::test]
+    async fn finalized_checkpoint_rejects_competing_history() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let make_engine = || {
+            ConsensusEngine::new(
+                &genesis,
+                Arc::new(TransactionPool::new(10)),
+                Arc::new(ConsensusNetworkManager::new()),
+                Arc::new(TxTracker::default()),
+                Arc::new(StateSecurityManager::new()),
+                Arc::new(SecurityManager::new()),
+            )
+        };
+        let store: Arc<dyn KvStore> = Arc::new(crate::storage::memory::MemoryStore::new());
+        let engine = make_engine();
+        engine.recover_from(Arc::clone(&store)).await;
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), Vec::new());
+        engine.finalize_block(block.clone()).await.unwrap();
+        assert_eq!(engine.committed_state().await.height, 1);
+
+        // A competing block at the finalized height is refused outright.
+        let rival = Block::new(1, vec![0; 32], vec![1; 32], "val1".into(), Vec::new());
+        assert!(engine.verify_block(&rival).await.is_err());
+
+        // A fresh engine restores the floor from the store and keeps
+        // enforcing it.
+        let restarted = make_engine();
+        restarted.recover_from(Arc::clone(&store)).await;
+        assert_eq!(restarted.committed_state().await.height, 1);
+        assert!(restarted.verify_block(&rival).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn create_block_reaps_executable_txs_within_gas_limit() {
+        let mut genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        genesis.consensus.max_block_gas = 50_000;
+        let pool = Arc::new(TransactionPool::new(10));
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::clone(&pool),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::new(SecurityManager::new()),
+        );
+        for (sender, nonce) in [("alice", 1), ("alice", 2), ("alice", 4), ("bob", 1)] {
+            let tx =
+                Transaction::new(sender.into(), "carol".into(), 1, nonce, 21_000, 1, Vec::new());
+            pool.add_transaction(tx).await.unwrap();
+        }
+        let block = engine.create_block().await.unwrap();
+        let included: Vec<(String, u64)> = block
+            .transactions
+            .iter()
+            .map(|tx| (tx.sender.clone(), tx.nonce))
+            .collect();
+        // alice's nonce-4 tx is not executable (gap at 3), and a third
+        // 21k tx would push the block past its 50k gas limit.
+        assert_eq!(included, vec![("alice".into(), 1), ("alice".into(), 2)]);
+    }
+
+    #[tokio::test]
+    async fn ordering_policy_shapes_blocks_and_rejects_reordered_ones() {
+        let mut genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        genesis.consensus.ordering = crate::config::OrderingPolicy::SenderFifo;
+        let pool = Arc::new(TransactionPool::new(10));
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::clone(&pool),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::new(SecurityManager::new()),
+        );
+        // carol outbids alice, but FIFO ordering ignores fees.
+        for (sender, gas_price) in [("carol", 9), ("alice", 1)] {
+            let tx =
+                Transaction::new(sender.into(), "bob".into(), 1, 1, 21_000, gas_price, Vec::new());
+            pool.add_transaction(tx).await.unwrap();
+        }
+        let block = engine.create_block().await.unwrap();
+        let senders: Vec<&str> = block
+            .transactions
+            .iter()
+            .map(|tx| tx.sender.as_str())
+            .collect();
+        assert_eq!(senders, vec!["alice", "carol"]);
+
+        // The same transactions fee-ordered violate the FIFO policy.
+        let mut reordered = block.transactions.clone();
+        reordered.reverse();
+        let fees = pool.fee_policy();
+        assert_eq!(
+            ordering_violation(crate::config::OrderingPolicy::SenderFifo, fees, &reordered),
+            Some(0)
+        );
+        assert_eq!(
+            ordering_violation(
+                crate::config::OrderingPolicy::FeeDescending,
+                fees,
+                &reordered
+            ),
+            None
+        );
+    }
+
+    #[tokio::test]
+    async fn fee_grants_let_a_sponsor_pay_gas() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("alice", 100).await;
+        accounts.set_balance("bob", 100_000).await;
+        accounts.grant_allowance("bob", "alice", 50_000).await;
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let mut tx = Transaction::new("alice".into(), "carol".into(), 50, 1, 30_000, 1, Vec::new());
+        tx.fee_payer = "bob".into();
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![tx]);
+        engine.finalize_block(block).await.unwrap();
+
+        let fee = gas::TX_BASE_GAS + gas::TRANSFER_GAS;
+        // Alice only paid the amount; bob covered the fee out of his
+        // balance and his allowance shrank to match.
+        assert_eq!(accounts.get_account("alice").await.unwrap().balance, 50);
+        assert_eq!(
+            accounts.get_account("bob").await.unwrap().balance,
+            100_000 - fee
+        );
+        assert_eq!(accounts.allowance("bob", "alice").await, 50_000 - fee);
+
+        // A sponsored transaction without an allowance is rejected.
+        let mut tx = Transaction::new("alice".into(), "carol".into(), 1, 2, 30_000, 1, Vec::new());
+        tx.fee_payer = "dave".into();
+        let block = Block::new(2, vec![0; 32], vec![0; 32], "val0".into(), vec![tx]);
+        assert!(engine.finalize_block(block).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn freezing_requires_the_configured_authority() {
+        let mut genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        genesis.consensus.authority = Some("gov".into());
+        let accounts = Arc::new(StateSecurityManager::new());
+        for name in ["gov", "alice", "mallory"] {
+            accounts.set_balance(name, 1_000_000).await;
+        }
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let freeze_tx = |sender: &str| {
+            Transaction::new(
+                sender.into(),
+                String::new(),
+                0,
+                1,
+                30_000,
+                1,
+                serde_json::to_vec(&GovTx::FreezeAccount {
+                    address: "alice".into(),
+                })
+                .unwrap(),
+            )
+        };
+
+        // Anyone but the authority is rejected at execution.
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![freeze_tx("mallory")]);
+        assert!(engine.finalize_block(block).await.is_err());
+        assert!(!accounts.get_account("alice").await.unwrap().frozen);
+
+        // The authority freezes alice as an ordinary state transition.
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![freeze_tx("gov")]);
+        engine.finalize_block(block).await.unwrap();
+        assert!(accounts.get_account("alice").await.unwrap().frozen);
+
+        // A frozen sender cannot get a transaction applied.
+        let tx = Transaction::new("alice".into(), "bob".into(), 1, 1, 30_000, 1, Vec::new());
+        let block = Block::new(2, vec![0; 32], vec![0; 32], "val0".into(), vec![tx]);
+        assert!(engine.finalize_block(block).await.is_err());
+
+        // And the authority can lift the freeze again.
+        let unfreeze = Transaction::new(
+            "gov".into(),
+            String::new(),
+            0,
+            2,
+            30_000,
+            1,
+            serde_json::to_vec(&GovTx::UnfreezeAccount {
+                address: "alice".into(),
+            })
+            .unwrap(),
+        );
+        let block = Block::new(2, vec![0; 32], vec![0; 32], "val0".into(), vec![unfreeze]);
+        engine.finalize_block(block).await.unwrap();
+        assert!(!accounts.get_account("alice").await.unwrap().frozen);
+    }
+
+    #[tokio::test]
+    async fn gov_txs_require_the_propose_permission() {
+        let mut genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        genesis.consensus.authority = Some("gov".into());
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("gov", 1_000_000).await;
+        accounts.set_balance("alice", 1_000_000).await;
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let param_change = |nonce: u64| {
+            Transaction::new(
+                "alice".into(),
+                String::new(),
+                0,
+                nonce,
+                30_000,
+                1,
+                serde_json::to_vec(&GovTx::ParamChange {
+                    key: "max_block_gas".into(),
+                    value: 1_000_000,
+                    effective_height: 100,
+                })
+                .unwrap(),
+            )
+        };
+
+        // By default an account may only transfer; proposing fails.
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![param_change(1)]);
+        assert!(engine.finalize_block(block).await.is_err());
+
+        // The authority grants Propose on-chain, after which the same
+        // proposal goes through.
+        let grant = Transaction::new(
+            "gov".into(),
+            String::new(),
+            0,
+            1,
+            30_000,
+            1,
+            serde_json::to_vec(&GovTx::GrantPermission {
+                address: "alice".into(),
+                permission: Permission::Propose,
+            })
+            .unwrap(),
+        );
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![grant]);
+        engine.finalize_block(block).await.unwrap();
+        // The failed attempt consumed no nonce.
+        let block = Block::new(2, vec![0; 32], vec![0; 32], "val0".into(), vec![param_change(1)]);
+        engine.finalize_block(block).await.unwrap();
+
+        // Revocation closes the door again.
+        let revoke = Transaction::new(
+            "gov".into(),
+            String::new(),
+            0,
+            2,
+            30_000,
+            1,
+            serde_json::to_vec(&GovTx::RevokePermission {
+                address: "alice".into(),
+                permission: Permission::Propose,
+            })
+            .unwrap(),
+        );
+        let block = Block::new(3, vec![0; 32], vec![0; 32], "val0".into(), vec![revoke]);
+        engine.finalize_block(block).await.unwrap();
+        let block = Block::new(4, vec![0; 32], vec![0; 32], "val0".into(), vec![param_change(2)]);
+        assert!(engine.finalize_block(block).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn contract_deploy_enforces_permission() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let accounts = Arc::new(StateSecurityManager::new());
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let code = [b"\0asm\x01\x00\x00\x00".as_slice(), &[1, 2]].concat();
+        let payload =
+            serde_json::to_vec(&crate::contracts::ContractTx::Deploy { code: code.clone() })
+                .unwrap();
+        let tx = Transaction::new("alice".into(), String::new(), 0, 1, 100_000, 0, payload);
+
+        // Without the capability the deployment is rejected.
+        assert!(engine.apply_transaction(&tx).await.is_err());
+
+        accounts
+            .grant_permission("alice", Permission::DeployContract)
+            .await;
+        engine.apply_transaction(&tx).await.unwrap();
+        let address = crate::contracts::contract_address(
+            "alice",
+            tx.nonce,
+            &crate::contracts::code_hash(&code),
+        );
+        let contract = accounts.get_account(&address).await.unwrap();
+        assert_eq!(contract.code_hash, crate::contracts::code_hash(&code));
+        assert!(engine.contracts.get(&contract.code_hash).await.is_some());
+        // Redeploying at the same (sender, nonce) collides.
+        assert!(engine.apply_transaction(&tx).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn commit_accepts_aggregated_bls_signature() {
+        use crate::config::GenesisValidator;
+        use crate::security::bls::BlsKeypair;
+
+        let keys: Vec<BlsKeypair> = (0..2).map(|_| BlsKeypair::generate()).collect();
+        let genesis = Genesis {
+            chain_id: "artha-test".into(),
+            genesis_time: 0,
+            consensus: ConsensusConfig::default(),
+            validators: keys
+                .iter()
+                .enumerate()
+                .map(|(i, key)| GenesisValidator {
+                    address: format!("val{i}"),
+                    public_key: vec![i as u8; 32],
+                    bls_public_key: key.public_key(),
+                    power: 1,
+                })
+                .collect(),
+            accounts: Vec::new(),
+        };
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::new(SecurityManager::new()),
+        );
+
+        let block_hash = vec![9; 32];
+        let message = commit_signing_bytes("artha-test", 1, 0, &block_hash);
+        let signatures: Vec<Vec<u8>> = keys.iter().map(|k| k.sign(&message)).collect();
+        let commit = Commit {
+            height: 1,
+            round: 0,
+            block_hash: block_hash.clone(),
+            votes: Vec::new(),
+            bls_signers: vec!["val0".into(), "val1".into()],
+            bls_signature: bls::aggregate(&signatures).unwrap(),
+        };
+        engine.verify_commit(&commit).await.unwrap();
+
+        // One signer short of the claimed aggregate fails verification.
+        let mut partial = commit.clone();
+        partial.bls_signature = bls::aggregate(&signatures[..1]).unwrap();
+        assert!(engine.verify_commit(&partial).await.is_err());
+        // A lone signer is a valid aggregate but lacks +2/3 power.
+        let mut minority = commit;
+        minority.bls_signers = vec!["val0".into()];
+        minority.bls_signature = bls::aggregate(&signatures[..1]).unwrap();
+        assert!(engine.verify_commit(&minority).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn commit_recheck_drops_stale_and_insolvent_txs() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let pool = Arc::new(TransactionPool::new(10));
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("alice", 100_000).await;
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::clone(&pool),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let committed =
+            Transaction::new("alice".into(), "bob".into(), 100, 1, 30_000, 1, Vec::new());
+        pool.add_transaction(committed.clone()).await.unwrap();
+        let follow_up =
+            Transaction::new("alice".into(), "bob".into(), 100, 2, 30_000, 1, Vec::new());
+        pool.add_transaction(follow_up).await.unwrap();
+        // A sender with no balance cannot cover transfer plus fee.
+        let insolvent =
+            Transaction::new("pauper".into(), "bob".into(), 50, 1, 21_000, 1, Vec::new());
+        pool.add_transaction(insolvent).await.unwrap();
+
+        let block = Block::new(1, vec![0; 32], vec![0; 32], "val0".into(), vec![committed]);
+        engine.finalize_block(block).await.unwrap();
+
+        // Only alice's next-nonce transaction survives the recheck.
+        let remaining: Vec<(String, u64)> = pool
+            .pending()
+            .await
+            .iter()
+            .map(|tx| (tx.sender.clone(), tx.nonce))
+            .collect();
+        assert_eq!(remaining, vec![("alice".into(), 2)]);
+    }
+
+    #[tokio::test]
+    async fn state_root_lags_execution_by_one_block() {
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            "val0".into(),
+            vec![0; 32],
+            ConsensusConfig::default(),
+        );
+        let accounts = Arc::new(StateSecurityManager::new());
+        accounts.set_balance("alice", 100_000).await;
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::clone(&accounts),
+            Arc::new(SecurityManager::new()),
+        );
+        let store: Arc<dyn KvStore> = Arc::new(crate::storage::memory::MemoryStore::new());
+        engine.recover_from(Arc::clone(&store)).await;
+
+        // Block 1 commits the genesis root, not the result of its own
+        // transactions.
+        let genesis_root = accounts.state_root().await;
+        let first = engine.create_block().await.unwrap();
+        assert_eq!(first.header.state_root, genesis_root);
+        let tx = Transaction::new("alice".into(), "bob".into(), 100, 1, 30_000, 1, Vec::new());
+        let block = Block::new(1, vec![0; 32], genesis_root.clone(), "val0".into(), vec![tx]);
+        engine.finalize_block(block).await.unwrap();
+
+        // Block 2 carries the root block 1's execution produced.
+        let next = engine.create_block().await.unwrap();
+        assert_eq!(next.header.state_root, accounts.state_root().await);
+        assert_ne!(next.header.state_root, genesis_root);
+    }
+
+    #[tokio::test]
+    async fn headers_commit_to_validators_and_params() {
+        let security = Arc::new(SecurityManager::new());
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            security.address(),
+            security.public_key(),
+            ConsensusConfig::default(),
+        );
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::clone(&security) as Arc<dyn Signer>,
+        );
+        let block = engine.create_block().await.unwrap();
+        let validators = engine.validators.read().await.clone();
+        assert_eq!(block.header.validator_hash, hash_validator_set(&validators));
+        assert_eq!(
+            block.header.consensus_hash,
+            engine.params.read().await.current().hash()
+        );
+        engine.verify_block(&block).await.unwrap();
+
+        // A header claiming a different signer set or rule set is
+        // rejected.
+        let mut wrong_set = block.clone();
+        wrong_set.header.validator_hash = vec![0u8; 32];
+        assert!(engine.verify_block(&wrong_set).await.is_err());
+        let mut wrong_rules = block;
+        wrong_rules.header.consensus_hash = vec![0u8; 32];
+        assert!(engine.verify_block(&wrong_rules).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn blocks_carry_a_verifiable_last_commit() {
+        let security = Arc::new(SecurityManager::new());
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            security.address(),
+            security.public_key(),
+            ConsensusConfig::default(),
+        );
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::clone(&security) as Arc<dyn Signer>,
+        );
+
+        // The first block has no parent certificate to carry.
+        let first = engine.create_block().await.unwrap();
+        assert!(first.last_commit.is_none());
+        // Record our own precommit, like the proposer loop does, so the
+        // finalized commit certifies the block.
+        let mut vote = Vote::new(
+            VoteType::Precommit,
+            1,
+            0,
+            first.hash(),
+            security.address(),
+        );
+        vote.signature = engine.sign_message(&vote.signing_bytes(&engine.chain_id));
+        engine.handle_message(ConsensusMessage::Vote(vote)).await;
+        engine.finalize_block(first.clone()).await.unwrap();
+
+        // The next block carries the parent's certificate and passes
+        // full verification, including the commit signatures.
+        let next = engine.create_block().await.unwrap();
+        let commit = next.last_commit.clone().unwrap();
+        assert_eq!(commit.height, 1);
+        assert_eq!(commit.block_hash, first.hash());
+        engine.verify_block(&next).await.unwrap();
+
+        // Dropping the certificate breaks the header binding.
+        let mut stripped = next.clone();
+        stripped.last_commit = None;
+        assert!(engine.verify_block(&stripped).await.is_err());
+        // A certificate for some other block is refused even when the
+        // header hash is recomputed to match it.
+        let mut forged = (*commit).clone();
+        forged.block_hash = vec![7; 32];
+        let reheaded = Block {
+            header: next.header.clone(),
+            transactions: next.transactions.clone(),
+            evidence: next.evidence.clone(),
+            last_commit: None,
+        }
+        .with_last_commit(Some(forged));
+        assert!(engine.verify_block(&reheaded).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn commits_aggregate_bls_precommits() {
+        let security = Arc::new(SecurityManager::new());
+        let bls_keypair = bls::BlsKeypair::generate();
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            security.address(),
+            security.public_key(),
+            ConsensusConfig::default(),
+        )
+        .with_bls_key(bls_keypair.public_key());
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::clone(&security) as Arc<dyn Signer>,
+        );
+        engine.set_bls_key(bls_keypair).await;
+
+        // Record our own precommit the way the proposer loop does: BLS
+        // signature over the commit sign-doc, ed25519 over the vote.
+        let first = engine.create_block().await.unwrap();
+        let mut vote = Vote::new(
+            VoteType::Precommit,
+            1,
+            0,
+            first.hash(),
+            security.address(),
+        );
+        engine.attach_bls_signature(&mut vote).await;
+        vote.signature = engine.sign_message(&vote.signing_bytes(&engine.chain_id));
+        engine.handle_message(ConsensusMessage::Vote(vote)).await;
+        engine.finalize_block(first).await.unwrap();
+
+        // The sole validator is the whole quorum, so the commit carries
+        // the aggregated proof and verifies through the BLS path even
+        // with the individual votes stripped.
+        let commit = engine.state.read().await.commits.last().unwrap().clone();
+        assert_eq!(commit.bls_signers, vec![security.address()]);
+        assert!(!commit.bls_signature.is_empty());
+        let mut stripped = commit.clone();
+        stripped.votes.clear();
+        engine.verify_commit(&stripped).await.unwrap();
+
+        // A tampered aggregate is refused.
+        let mut forged = commit;
+        forged.bls_signature[0] ^= 1;
+        assert!(engine.verify_commit(&forged).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn chain_store_tracks_finalized_and_gossiped_blocks() {
+        let security = Arc::new(SecurityManager::new());
+        let genesis = Genesis::single_node(
+            "artha-test".into(),
+            security.address(),
+            security.public_key(),
+            ConsensusConfig::default(),
+        );
+        let engine = ConsensusEngine::new(
+            &genesis,
+            Arc::new(TransactionPool::new(10)),
+            Arc::new(ConsensusNetworkManager::new()),
+            Arc::new(TxTracker::default()),
+            Arc::new(StateSecurityManager::new()),
+            Arc::clone(&security) as Arc<dyn Signer>,
+        );
+
+        // A locally finalized block becomes the fork-aware store's head.
+        let first = engine.create_block().await.unwrap();
+        engine.finalize_block(first.clone()).await.unwrap();
+        let head = engine.chain.head().await.unwrap();
+        assert_eq!(head.hash(), first.hash());
+
+        // A gossiped block whose parent we have never seen is parked in
+        // the orphan pool rather than dropped.
+        let orphan = Block::new(5, vec![9; 32], vec![0; 32], "val0".into(), Vec::new());
+        engine.receive_block(orphan).await;
+        assert_eq!(engine.chain.orphan_count().await, 1);
+        assert_eq!(engine.chain.head().await.unwrap().hash(), first.hash());
+    }
+}
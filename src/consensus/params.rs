@@ -0,0 +1,217 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::config::ConsensusConfig;
+use crate::security::state::Permission;
+use crate::types::Transaction;
+
+/// Consensus parameters under on-chain governance. Values start from the
+/// node's static config and change only through scheduled proposals, so
+/// every node flips to the new value at the same height.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GovernedParams {
+    pub block_interval_ms: u64,
+    pub max_block_size: usize,
+    pub max_transactions_per_block: usize,
+    /// Sum of transaction gas limits a block may carry.
+    pub max_block_gas: u64,
+    /// Minimum gas price the chain accepts, in native units.
+    pub min_gas_price: u64,
+}
+
+impl GovernedParams {
+    pub fn from_config(config: &ConsensusConfig) -> Self {
+        Self {
+            block_interval_ms: config.block_interval_ms,
+            max_block_size: config.max_block_size,
+            max_transactions_per_block: config.max_transactions_per_block,
+            max_block_gas: config.max_block_gas,
+            min_gas_price: 0,
+        }
+    }
+
+    /// Hash of the parameters in effect, committed to by every block
+    /// header as `consensus_hash`.
+    pub fn hash(&self) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(self.block_interval_ms.to_be_bytes());
+        hasher.update((self.max_block_size as u64).to_be_bytes());
+        hasher.update((self.max_transactions_per_block as u64).to_be_bytes());
+        hasher.update(self.max_block_gas.to_be_bytes());
+        hasher.update(self.min_gas_price.to_be_bytes());
+        hasher.finalize().to_vec()
+    }
+
+    /// Set one parameter by its governance key.
+    fn set(&mut self, key: &str, value: u64) -> Result<(), String> {
+        match key {
+            "block_interval_ms" => self.block_interval_ms = value,
+            "max_block_size" => self.max_block_size = value as usize,
+            "max_transactions_per_block" => self.max_transactions_per_block = value as usize,
+            "max_block_gas" => self.max_block_gas = value,
+            "min_gas_price" => self.min_gas_price = value,
+            other => return Err(format!("unknown governed parameter {other}")),
+        }
+        Ok(())
+    }
+
+    fn is_known_key(key: &str) -> bool {
+        matches!(
+            key,
+            "block_interval_ms"
+                | "max_block_size"
+                | "max_transactions_per_block"
+                | "max_block_gas"
+                | "min_gas_price"
+        )
+    }
+}
+
+/// A governance-approved parameter change queued for a future height.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ScheduledChange {
+    pub key: String,
+    pub value: u64,
+    pub effective_height: u64,
+}
+
+/// Governance transactions carried in a transaction's data payload,
+/// alongside the staking payloads.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GovTx {
+    /// Change a governed parameter at `effective_height`.
+    ParamChange {
+        key: String,
+        value: u64,
+        effective_height: u64,
+    },
+    /// Schedule a coordinated upgrade halting at `height`.
+    Upgrade {
+        name: String,
+        height: u64,
+        info: String,
+    },
+    /// Cancel the pending upgrade plan.
+    CancelUpgrade,
+    /// Freeze an account so it can no longer send transactions. Only
+    /// the configured freeze authority may execute this.
+    FreezeAccount { address: String },
+    /// Lift a freeze placed by `FreezeAccount`.
+    UnfreezeAccount { address: String },
+    /// Grant a capability to an account. Only the configured authority
+    /// may execute this.
+    GrantPermission {
+        address: String,
+        permission: Permission,
+    },
+    /// Revoke a capability from an account. Only the configured
+    /// authority may execute this.
+    RevokePermission {
+        address: String,
+        permission: Permission,
+    },
+}
+
+impl GovTx {
+    pub fn parse(tx: &Transaction) -> Option<Self> {
+        if tx.data.is_empty() {
+            return None;
+        }
+        serde_json::from_slice(&tx.data).ok()
+    }
+}
+
+/// The live parameter store: current values plus scheduled changes.
+pub struct ParamStore {
+    current: GovernedParams,
+    scheduled: Vec<ScheduledChange>,
+}
+
+impl ParamStore {
+    pub fn new(config: &ConsensusConfig) -> Self {
+        Self {
+            current: GovernedParams::from_config(config),
+            scheduled: Vec::new(),
+        }
+    }
+
+    pub fn current(&self) -> &GovernedParams {
+        &self.current
+    }
+
+    /// Queue a change, rejecting unknown keys and past heights.
+    pub fn schedule(&mut self, change: ScheduledChange, head_height: u64) -> Result<(), String> {
+        if !GovernedParams::is_known_key(&change.key) {
+            return Err(format!("unknown governed parameter {}", change.key));
+        }
+        if change.effective_height <= head_height {
+            return Err(format!(
+                "effective height {} is not in the future (head {head_height})",
+                change.effective_height
+            ));
+        }
+        self.scheduled.push(change);
+        Ok(())
+    }
+
+    /// Apply every change due at `height`, returning what was applied.
+    pub fn apply_due(&mut self, height: u64) -> Vec<ScheduledChange> {
+        let (due, remaining): (Vec<_>, Vec<_>) = self
+            .scheduled
+            .drain(..)
+            .partition(|c| c.effective_height <= height);
+        self.scheduled = remaining;
+        for change in &due {
+            // Keys were validated at scheduling time.
+            let _ = self.current.set(&change.key, change.value);
+        }
+        due
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scheduled_changes_apply_at_height() {
+        let mut store = ParamStore::new(&ConsensusConfig::default());
+        store
+            .schedule(
+                ScheduledChange {
+                    key: "max_transactions_per_block".into(),
+                    value: 42,
+                    effective_height: 10,
+                },
+                5,
+            )
+            .unwrap();
+        // Unknown keys and past heights are rejected.
+        assert!(store
+            .schedule(
+                ScheduledChange {
+                    key: "bogus".into(),
+                    value: 1,
+                    effective_height: 10,
+                },
+                5,
+            )
+            .is_err());
+        assert!(store
+            .schedule(
+                ScheduledChange {
+                    key: "min_gas_price".into(),
+                    value: 1,
+                    effective_height: 5,
+                },
+                5,
+            )
+            .is_err());
+
+        assert!(store.apply_due(9).is_empty());
+        let applied = store.apply_due(10);
+        assert_eq!(applied.len(), 1);
+        assert_eq!(store.current().max_transactions_per_block, 42);
+    }
+}
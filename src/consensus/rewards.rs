@@ -0,0 +1,58 @@
+/// Fraction of each block's rewards paid to the proposer up front, in
+/// parts-per-million. The rest is split by voting power among signers.
+const PROPOSER_SHARE_PPM: u64 = 100_000;
+
+const PPM: u64 = 1_000_000;
+
+/// Split `total` (block reward plus collected fees) between the proposer
+/// and the validators that signed the commit, proportional to voting
+/// power. The proposer receives a fixed bonus share plus any rounding
+/// dust; with no signers everything goes to the proposer.
+pub fn distribute(total: u64, proposer: &str, signers: &[(String, u64)]) -> Vec<(String, u64)> {
+    if total == 0 {
+        return Vec::new();
+    }
+    let total_power: u64 = signers.iter().map(|(_, power)| power).sum();
+    if signers.is_empty() || total_power == 0 {
+        return vec![(proposer.to_string(), total)];
+    }
+
+    let bonus = total * PROPOSER_SHARE_PPM / PPM;
+    let pool = total - bonus;
+    let mut payouts: Vec<(String, u64)> = Vec::with_capacity(signers.len() + 1);
+    let mut paid = 0u64;
+    for (address, power) in signers {
+        let share = pool * power / total_power;
+        paid += share;
+        payouts.push((address.clone(), share));
+    }
+    // Bonus plus rounding dust goes to the proposer.
+    let proposer_amount = bonus + (pool - paid);
+    match payouts.iter_mut().find(|(address, _)| address == proposer) {
+        Some((_, amount)) => *amount += proposer_amount,
+        None => payouts.push((proposer.to_string(), proposer_amount)),
+    }
+    payouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewards_split_by_power_with_proposer_bonus() {
+        let signers = vec![("val1".to_string(), 3), ("val2".to_string(), 1)];
+        let payouts = distribute(1000, "val1", &signers);
+        let total: u64 = payouts.iter().map(|(_, amount)| amount).sum();
+        assert_eq!(total, 1000);
+        let val1 = payouts.iter().find(|(a, _)| a == "val1").unwrap().1;
+        let val2 = payouts.iter().find(|(a, _)| a == "val2").unwrap().1;
+        // val2 gets a quarter of the 900 pool; val1 gets the rest.
+        assert_eq!(val2, 225);
+        assert_eq!(val1, 775);
+
+        // No signers: the proposer takes everything.
+        assert_eq!(distribute(100, "val1", &[]), vec![("val1".to_string(), 100)]);
+        assert!(distribute(0, "val1", &signers).is_empty());
+    }
+}
@@ -0,0 +1,273 @@
+//! Deterministic multi-node simulation harness.
+//!
+//! Runs several [`ConsensusEngine`] instances in one process, connected
+//! by an in-memory router instead of sockets. Link conditions — added
+//! latency, drop probability, and partitions — can be changed while the
+//! simulation runs, and drops are decided by a seeded RNG, so a failing
+//! consensus scenario replays identically from the same seed.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use super::{ConsensusEngine, ConsensusMessage, ConsensusNetworkManager};
+use crate::config::{ConsensusConfig, Genesis, GenesisValidator};
+use crate::security::state::StateSecurityManager;
+use crate::security::SecurityManager;
+use crate::types::{TransactionPool, TxTracker};
+
+/// Conditions applied to every inter-node link.
+#[derive(Debug, Clone)]
+pub struct LinkConditions {
+    /// Delay added to each delivered message.
+    pub latency: Duration,
+    /// Probability in `[0, 1]` that a message is silently dropped.
+    pub drop_rate: f64,
+}
+
+impl Default for LinkConditions {
+    fn default() -> Self {
+        Self {
+            latency: Duration::ZERO,
+            drop_rate: 0.0,
+        }
+    }
+}
+
+/// Shared routing state: link conditions, the partition map, and the
+/// seeded RNG deciding drops.
+struct Fabric {
+    conditions: LinkConditions,
+    /// Partition group per node; messages only cross equal groups.
+    groups: Vec<usize>,
+    rng: StdRng,
+}
+
+impl Fabric {
+    /// Whether a message from `source` reaches `target` right now, and
+    /// with how much delay. Consumes RNG state, so call once per
+    /// (message, target) pair.
+    fn deliver(&mut self, source: usize, target: usize) -> Option<Duration> {
+        if self.groups[source] != self.groups[target] {
+            return None;
+        }
+        if self.conditions.drop_rate > 0.0 && self.rng.gen_bool(self.conditions.drop_rate.min(1.0)) {
+            return None;
+        }
+        Some(self.conditions.latency)
+    }
+}
+
+/// One simulated validator node.
+pub struct SimNode {
+    pub engine: Arc<ConsensusEngine>,
+    pub security: Arc<SecurityManager>,
+}
+
+/// A set of in-process validator nodes wired through a controllable
+/// in-memory network.
+pub struct Simulation {
+    pub nodes: Vec<SimNode>,
+    fabric: Arc<RwLock<Fabric>>,
+}
+
+impl Simulation {
+    /// Build `validators` nodes sharing one genesis. Keys are derived
+    /// from `seed`, so the same seed yields the same validator set and
+    /// the same drop sequence.
+    pub fn new(validators: usize, seed: u64) -> Self {
+        let keys: Vec<Arc<SecurityManager>> = (0..validators)
+            .map(|index| {
+                let mut hasher = Sha256::new();
+                hasher.update(seed.to_be_bytes());
+                hasher.update((index as u64).to_be_bytes());
+                Arc::new(SecurityManager::from_bytes(&hasher.finalize().into()))
+            })
+            .collect();
+        let genesis = Genesis {
+            chain_id: "artha-sim".into(),
+            genesis_time: 0,
+            consensus: ConsensusConfig::default(),
+            validators: keys
+                .iter()
+                .map(|key| GenesisValidator {
+                    address: key.address(),
+                    public_key: key.public_key(),
+                    bls_public_key: Vec::new(),
+                    power: 1,
+                })
+                .collect(),
+            accounts: Vec::new(),
+        };
+        let nodes = keys
+            .into_iter()
+            .map(|security| {
+                let engine = Arc::new(ConsensusEngine::new(
+                    &genesis,
+                    Arc::new(TransactionPool::new(10_000)),
+                    Arc::new(ConsensusNetworkManager::new()),
+                    Arc::new(TxTracker::default()),
+                    Arc::new(StateSecurityManager::new()),
+                    Arc::clone(&security) as Arc<dyn crate::security::signer::Signer>,
+                ));
+                SimNode { engine, security }
+            })
+            .collect();
+        Self {
+            nodes,
+            fabric: Arc::new(RwLock::new(Fabric {
+                conditions: LinkConditions::default(),
+                groups: vec![0; validators],
+                rng: StdRng::seed_from_u64(seed),
+            })),
+        }
+    }
+
+    /// Spawn the router tasks moving each node's outbound messages to
+    /// every other node, subject to the current link conditions.
+    pub fn start_network(&self) {
+        let networks: Vec<Arc<ConsensusNetworkManager>> = self
+            .nodes
+            .iter()
+            .map(|node| Arc::clone(&node.engine.network))
+            .collect();
+        for source in 0..networks.len() {
+            tokio::spawn(Self::route(
+                source,
+                networks.clone(),
+                Arc::clone(&self.fabric),
+            ));
+        }
+    }
+
+    /// Spawn the full engine loop on every node. Call after
+    /// [`Simulation::start_network`] to run a live devnet in-process.
+    pub fn start_engines(&self) {
+        for node in &self.nodes {
+            tokio::spawn(Arc::clone(&node.engine).run());
+        }
+    }
+
+    async fn route(
+        source: usize,
+        networks: Vec<Arc<ConsensusNetworkManager>>,
+        fabric: Arc<RwLock<Fabric>>,
+    ) {
+        while let Some(message) = networks[source].next_outbound().await {
+            for (target, network) in networks.iter().enumerate() {
+                if target == source {
+                    continue;
+                }
+                let Some(latency) = fabric.write().await.deliver(source, target) else {
+                    continue;
+                };
+                if latency.is_zero() {
+                    network.deliver(message.clone()).await;
+                } else {
+                    let network = Arc::clone(network);
+                    let message = message.clone();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(latency).await;
+                        network.deliver(message).await;
+                    });
+                }
+            }
+        }
+    }
+
+    pub async fn set_latency(&self, latency: Duration) {
+        self.fabric.write().await.conditions.latency = latency;
+    }
+
+    pub async fn set_drop_rate(&self, drop_rate: f64) {
+        self.fabric.write().await.conditions.drop_rate = drop_rate;
+    }
+
+    /// Split the network into the given islands; any node not listed is
+    /// isolated on its own. Messages never cross island boundaries.
+    pub async fn partition(&self, islands: &[&[usize]]) {
+        let mut groups: Vec<usize> = (0..self.nodes.len())
+            .map(|index| islands.len() + index)
+            .collect();
+        for (island, members) in islands.iter().enumerate() {
+            for &member in *members {
+                groups[member] = island;
+            }
+        }
+        self.fabric.write().await.groups = groups;
+    }
+
+    /// Remove all partitions, reconnecting every node.
+    pub async fn heal(&self) {
+        let mut fabric = self.fabric.write().await;
+        fabric.groups = vec![0; self.nodes.len()];
+    }
+
+    /// Broadcast a message from `source` as if its engine had sent it.
+    pub async fn inject(&self, source: usize, message: ConsensusMessage) {
+        self.nodes[source].engine.network.broadcast(message).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::tendermint::{Vote, VoteType};
+
+    fn test_vote(sim: &Simulation, height: u64) -> ConsensusMessage {
+        let mut vote = Vote::new(
+            VoteType::Prevote,
+            height,
+            0,
+            vec![7; 32],
+            sim.nodes[0].security.address(),
+        );
+        vote.signature = sim.nodes[0].security.sign(&vote.signing_bytes("artha-sim"));
+        ConsensusMessage::Vote(vote)
+    }
+
+    async fn recv(sim: &Simulation, node: usize) -> Option<ConsensusMessage> {
+        tokio::time::timeout(
+            Duration::from_millis(200),
+            sim.nodes[node].engine.network.recv_message(),
+        )
+        .await
+        .ok()
+        .flatten()
+    }
+
+    #[tokio::test]
+    async fn partitions_block_delivery_until_healed() {
+        let sim = Simulation::new(3, 42);
+        sim.start_network();
+
+        sim.inject(0, test_vote(&sim, 1)).await;
+        assert!(recv(&sim, 1).await.is_some());
+        assert!(recv(&sim, 2).await.is_some());
+
+        sim.partition(&[&[0, 1], &[2]]).await;
+        sim.inject(0, test_vote(&sim, 2)).await;
+        assert!(recv(&sim, 1).await.is_some());
+        assert!(recv(&sim, 2).await.is_none());
+
+        sim.heal().await;
+        sim.inject(0, test_vote(&sim, 3)).await;
+        assert!(recv(&sim, 2).await.is_some());
+    }
+
+    #[tokio::test]
+    async fn total_drop_rate_loses_every_message() {
+        let sim = Simulation::new(2, 7);
+        sim.start_network();
+        sim.set_drop_rate(1.0).await;
+        sim.inject(0, test_vote(&sim, 1)).await;
+        assert!(recv(&sim, 1).await.is_none());
+        sim.set_drop_rate(0.0).await;
+        sim.inject(0, test_vote(&sim, 2)).await;
+        assert!(recv(&sim, 1).await.is_some());
+    }
+}
@@ -0,0 +1,253 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use super::Validator;
+use crate::types::transaction::now_unix;
+
+/// Why a validator was slashed or jailed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlashReason {
+    DoubleSign,
+    Downtime,
+    InvalidProposal,
+}
+
+/// One applied slash or jail event, persisted for audit queries.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SlashEvent {
+    pub validator: String,
+    pub height: u64,
+    pub reason: SlashReason,
+    /// Voting power (or stake) removed.
+    pub amount: u64,
+    /// Hex hash of the evidence that triggered the slash, if any.
+    pub evidence_hash: String,
+    pub jailed: bool,
+    pub timestamp: u64,
+}
+
+/// Stores applied slash events within a bounded retention window of
+/// blocks, so delegators and explorers can audit validator misbehavior.
+pub struct SlashingStore {
+    events: RwLock<Vec<SlashEvent>>,
+    /// Events older than this many blocks behind the head are pruned.
+    retention_blocks: u64,
+}
+
+impl SlashingStore {
+    pub fn new(retention_blocks: u64) -> Self {
+        Self {
+            events: RwLock::new(Vec::new()),
+            retention_blocks,
+        }
+    }
+
+    /// Record an event and prune anything outside the retention window.
+    pub async fn record(&self, event: SlashEvent) {
+        let mut events = self.events.write().await;
+        let cutoff = event.height.saturating_sub(self.retention_blocks);
+        events.retain(|e| e.height >= cutoff);
+        log::warn!(
+            "slash applied: validator={} height={} reason={:?} amount={}",
+            event.validator,
+            event.height,
+            event.reason,
+            event.amount
+        );
+        events.push(event);
+    }
+
+    /// All retained events, newest last.
+    pub async fn all(&self) -> Vec<SlashEvent> {
+        self.events.read().await.clone()
+    }
+
+    /// Retained events for one validator.
+    pub async fn for_validator(&self, validator: &str) -> Vec<SlashEvent> {
+        self.events
+            .read()
+            .await
+            .iter()
+            .filter(|e| e.validator == validator)
+            .cloned()
+            .collect()
+    }
+}
+
+/// A jailed validator held out of the set until its release height.
+#[derive(Debug, Clone)]
+struct JailedValidator {
+    validator: Validator,
+    until_height: u64,
+}
+
+/// Tracks per-validator signing over a sliding window of blocks and
+/// decides jailing for downtime. Jailed validators are remembered (with
+/// their pre-jail record) so they can rejoin the set on release.
+pub struct LivenessTracker {
+    /// Sliding window length, in blocks.
+    window: usize,
+    /// Minimum fraction of the window a validator must have signed.
+    min_signed_ratio: f64,
+    history: HashMap<String, VecDeque<bool>>,
+    jailed: HashMap<String, JailedValidator>,
+}
+
+impl LivenessTracker {
+    pub fn new(window: usize, min_signed_ratio: f64) -> Self {
+        Self {
+            window,
+            min_signed_ratio,
+            history: HashMap::new(),
+            jailed: HashMap::new(),
+        }
+    }
+
+    /// Record who signed this block. Returns validators whose signed
+    /// ratio over a full window fell below the liveness threshold.
+    pub fn record_block(&mut self, validators: &[Validator], signers: &HashSet<String>) -> Vec<String> {
+        let mut offenders = Vec::new();
+        for validator in validators {
+            if self.jailed.contains_key(&validator.address) {
+                continue;
+            }
+            let record = self.history.entry(validator.address.clone()).or_default();
+            record.push_back(signers.contains(&validator.address));
+            while record.len() > self.window {
+                record.pop_front();
+            }
+            if record.len() == self.window {
+                let signed = record.iter().filter(|s| **s).count();
+                if (signed as f64) < self.min_signed_ratio * self.window as f64 {
+                    offenders.push(validator.address.clone());
+                }
+            }
+        }
+        offenders
+    }
+
+    /// Jail a validator until `until_height`, clearing its window.
+    pub fn jail(&mut self, validator: Validator, until_height: u64) {
+        self.history.remove(&validator.address);
+        self.jailed.insert(
+            validator.address.clone(),
+            JailedValidator {
+                validator,
+                until_height,
+            },
+        );
+    }
+
+    pub fn is_jailed(&self, address: &str) -> bool {
+        self.jailed.contains_key(address)
+    }
+
+    /// Release every validator whose jail term has passed.
+    pub fn due_for_release(&mut self, height: u64) -> Vec<Validator> {
+        let due: Vec<String> = self
+            .jailed
+            .iter()
+            .filter(|(_, j)| j.until_height <= height)
+            .map(|(address, _)| address.clone())
+            .collect();
+        due.into_iter()
+            .filter_map(|address| self.jailed.remove(&address))
+            .map(|j| j.validator)
+            .collect()
+    }
+
+    /// Release one validator if its term has passed (the unjail tx path).
+    pub fn try_release(&mut self, address: &str, height: u64) -> Option<Validator> {
+        if self.jailed.get(address)?.until_height > height {
+            return None;
+        }
+        self.jailed.remove(address).map(|j| j.validator)
+    }
+}
+
+impl SlashEvent {
+    pub fn new(
+        validator: String,
+        height: u64,
+        reason: SlashReason,
+        amount: u64,
+        evidence_hash: String,
+        jailed: bool,
+    ) -> Self {
+        Self {
+            validator,
+            height,
+            reason,
+            amount,
+            evidence_hash,
+            jailed,
+            timestamp: now_unix(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn retention_window_prunes_old_events() {
+        let store = SlashingStore::new(100);
+        store
+            .record(SlashEvent::new(
+                "val1".into(),
+                10,
+                SlashReason::Downtime,
+                1,
+                String::new(),
+                false,
+            ))
+            .await;
+        store
+            .record(SlashEvent::new(
+                "val2".into(),
+                200,
+                SlashReason::DoubleSign,
+                5,
+                String::new(),
+                true,
+            ))
+            .await;
+        let all = store.all().await;
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].validator, "val2");
+        assert!(store.for_validator("val1").await.is_empty());
+    }
+
+    #[test]
+    fn downtime_jails_and_releases_after_term() {
+        let validator = Validator {
+            address: "val1".into(),
+            public_key: vec![1; 32],
+            bls_public_key: Vec::new(),
+            voting_power: 10,
+            proposer_priority: 0,
+        };
+        let mut tracker = LivenessTracker::new(4, 0.5);
+        let nobody = HashSet::new();
+        let validators = vec![validator.clone()];
+        // No offense until the window is full.
+        for _ in 0..3 {
+            assert!(tracker.record_block(&validators, &nobody).is_empty());
+        }
+        let offenders = tracker.record_block(&validators, &nobody);
+        assert_eq!(offenders, vec!["val1".to_string()]);
+
+        tracker.jail(validator.clone(), 50);
+        assert!(tracker.is_jailed("val1"));
+        // Jailed validators are not tracked or re-reported.
+        assert!(tracker.record_block(&validators, &nobody).is_empty());
+        assert!(tracker.try_release("val1", 49).is_none());
+        let released = tracker.try_release("val1", 50).unwrap();
+        assert_eq!(released.voting_power, 10);
+        assert!(!tracker.is_jailed("val1"));
+    }
+}
@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use super::{Validator, ValidatorSet, ValidatorUpdate};
+use crate::types::Transaction;
+
+/// Staking operations carried in a transaction's data payload as JSON.
+/// The sender of the transaction is the validator being created or
+/// edited; a non-staking payload simply fails to parse.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum StakingTx {
+    /// Register the sender as a validator with the given key and power.
+    CreateValidator { public_key: Vec<u8>, power: u64 },
+    /// Change the sender's voting power; zero removes the validator.
+    EditValidator { power: u64 },
+    /// Lock `amount` of the sender's balance as self-bond.
+    Bond { amount: u64 },
+    /// Start unbonding `amount` of the sender's self-bond.
+    Unbond { amount: u64 },
+    /// Lock `amount` of the sender's balance as a delegation.
+    Delegate { validator: String, amount: u64 },
+    /// Start unbonding `amount` of the sender's delegation.
+    Undelegate { validator: String, amount: u64 },
+    /// Rejoin the validator set after a jail term has passed.
+    Unjail,
+}
+
+impl StakingTx {
+    /// Parse a staking operation out of a transaction, if it carries one.
+    pub fn parse(tx: &Transaction) -> Option<Self> {
+        if tx.data.is_empty() {
+            return None;
+        }
+        serde_json::from_slice(&tx.data).ok()
+    }
+
+    /// The validator update this operation produces at end of block.
+    /// Returns `None` for an edit of a validator that does not exist.
+    pub fn validator_update(&self, sender: &str, set: &ValidatorSet) -> Option<ValidatorUpdate> {
+        match self {
+            StakingTx::CreateValidator { public_key, power } => Some(ValidatorUpdate {
+                address: sender.to_string(),
+                public_key: public_key.clone(),
+                voting_power: *power,
+            }),
+            StakingTx::EditValidator { power } => {
+                let existing = set.get(sender)?;
+                Some(ValidatorUpdate {
+                    address: sender.to_string(),
+                    public_key: existing.public_key.clone(),
+                    voting_power: *power,
+                })
+            }
+            // Bonding ops change power through `StakingState`; their
+            // updates are computed at end of block from the new totals.
+            StakingTx::Bond { .. }
+            | StakingTx::Unbond { .. }
+            | StakingTx::Delegate { .. }
+            | StakingTx::Undelegate { .. }
+            | StakingTx::Unjail => None,
+        }
+    }
+}
+
+/// A stake amount released back to `delegator` at `release_height`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UnbondingEntry {
+    pub delegator: String,
+    pub validator: String,
+    pub amount: u64,
+    pub release_height: u64,
+}
+
+/// Bonded stake backing the validator set: self-bonds plus delegations.
+/// A validator's voting power is the sum of both; unbonding amounts stop
+/// counting immediately but only release funds once matured.
+#[derive(Debug, Default)]
+pub struct StakingState {
+    self_bonds: HashMap<String, u64>,
+    /// validator -> delegator -> bonded amount.
+    delegations: HashMap<String, HashMap<String, u64>>,
+    unbonding: Vec<UnbondingEntry>,
+    /// Validators whose power changed since the last end-of-block sweep.
+    touched: HashSet<String>,
+}
+
+impl StakingState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Voting power backed by bonded stake: self-bond plus delegations.
+    pub fn power_of(&self, validator: &str) -> u64 {
+        let self_bond = self.self_bonds.get(validator).copied().unwrap_or(0);
+        let delegated: u64 = self
+            .delegations
+            .get(validator)
+            .map(|d| d.values().sum())
+            .unwrap_or(0);
+        self_bond + delegated
+    }
+
+    pub fn bond(&mut self, validator: &str, amount: u64) {
+        *self.self_bonds.entry(validator.to_string()).or_default() += amount;
+        self.touched.insert(validator.to_string());
+    }
+
+    pub fn unbond(
+        &mut self,
+        validator: &str,
+        amount: u64,
+        release_height: u64,
+    ) -> Result<(), String> {
+        let bonded = self.self_bonds.entry(validator.to_string()).or_default();
+        if *bonded < amount {
+            return Err(format!("self-bond {bonded} is less than {amount}"));
+        }
+        *bonded -= amount;
+        self.unbonding.push(UnbondingEntry {
+            delegator: validator.to_string(),
+            validator: validator.to_string(),
+            amount,
+            release_height,
+        });
+        self.touched.insert(validator.to_string());
+        Ok(())
+    }
+
+    pub fn delegate(&mut self, delegator: &str, validator: &str, amount: u64) {
+        *self
+            .delegations
+            .entry(validator.to_string())
+            .or_default()
+            .entry(delegator.to_string())
+            .or_default() += amount;
+        self.touched.insert(validator.to_string());
+    }
+
+    pub fn undelegate(
+        &mut self,
+        delegator: &str,
+        validator: &str,
+        amount: u64,
+        release_height: u64,
+    ) -> Result<(), String> {
+        let bonded = self
+            .delegations
+            .get_mut(validator)
+            .and_then(|d| d.get_mut(delegator))
+            .ok_or_else(|| format!("no delegation from {delegator} to {validator}"))?;
+        if *bonded < amount {
+            return Err(format!("delegation {bonded} is less than {amount}"));
+        }
+        *bonded -= amount;
+        self.unbonding.push(UnbondingEntry {
+            delegator: delegator.to_string(),
+            validator: validator.to_string(),
+            amount,
+            release_height,
+        });
+        self.touched.insert(validator.to_string());
+        Ok(())
+    }
+
+    /// Remove and return entries whose release height has passed.
+    pub fn mature(&mut self, height: u64) -> Vec<UnbondingEntry> {
+        let (matured, remaining) = self
+            .unbonding
+            .drain(..)
+            .partition(|e| e.release_height <= height);
+        self.unbonding = remaining;
+        matured
+    }
+
+    /// Validators whose power changed since the last sweep.
+    pub fn take_touched(&mut self) -> Vec<String> {
+        self.touched.drain().collect()
+    }
+}
+
+/// Apply end-of-block validator updates to the set: zero power removes,
+/// otherwise the entry is updated or added with fresh proposer priority.
+pub fn apply_validator_updates(set: &mut ValidatorSet, updates: &[ValidatorUpdate]) {
+    for update in updates {
+        if update.voting_power == 0 {
+            set.validators.retain(|v| v.address != update.address);
+            continue;
+        }
+        match set.validators.iter_mut().find(|v| v.address == update.address) {
+            Some(validator) => validator.voting_power = update.voting_power,
+            None => set.validators.push(Validator {
+                address: update.address.clone(),
+                public_key: update.public_key.clone(),
+                bls_public_key: Vec::new(),
+                voting_power: update.voting_power,
+                proposer_priority: 0,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn staking_tx(sender: &str, op: &StakingTx) -> Transaction {
+        let mut tx = Transaction::new(sender.into(), String::new(), 0, 1, 21000, 1, vec![]);
+        tx.data = serde_json::to_vec(op).unwrap();
+        tx
+    }
+
+    #[test]
+    fn staking_ops_change_the_validator_set() {
+        let mut set = ValidatorSet::default();
+        let create = StakingTx::CreateValidator {
+            public_key: vec![7; 32],
+            power: 10,
+        };
+        let tx = staking_tx("val1", &create);
+        let parsed = StakingTx::parse(&tx).unwrap();
+        let update = parsed.validator_update("val1", &set).unwrap();
+        apply_validator_updates(&mut set, &[update]);
+        assert_eq!(set.get("val1").unwrap().voting_power, 10);
+
+        // Editing an unknown validator produces no update.
+        let edit = StakingTx::EditValidator { power: 5 };
+        assert!(edit.validator_update("val2", &set).is_none());
+
+        // Editing to zero removes the validator.
+        let remove = StakingTx::EditValidator { power: 0 }
+            .validator_update("val1", &set)
+            .unwrap();
+        apply_validator_updates(&mut set, &[remove]);
+        assert!(set.is_empty());
+    }
+
+    #[test]
+    fn bonded_power_and_unbonding_maturity() {
+        let mut staking = StakingState::new();
+        staking.bond("val1", 100);
+        staking.delegate("alice", "val1", 50);
+        assert_eq!(staking.power_of("val1"), 150);
+
+        // Unbonding reduces power immediately but releases funds later.
+        staking.unbond("val1", 40, 20).unwrap();
+        staking.undelegate("alice", "val1", 10, 20).unwrap();
+        assert_eq!(staking.power_of("val1"), 100);
+        assert!(staking.mature(19).is_empty());
+        let released = staking.mature(20);
+        assert_eq!(released.len(), 2);
+        assert_eq!(released.iter().map(|e| e.amount).sum::<u64>(), 50);
+
+        // Over-unbonding is rejected.
+        assert!(staking.unbond("val1", 1000, 30).is_err());
+        assert!(staking.undelegate("bob", "val1", 1, 30).is_err());
+    }
+}
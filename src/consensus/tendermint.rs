@@ -0,0 +1,462 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::transaction::now_unix;
+use crate::types::Block;
+
+/// Steps of a Tendermint round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Step {
+    Propose,
+    Prevote,
+    Precommit,
+    Commit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum VoteType {
+    Prevote,
+    Precommit,
+}
+
+/// A consensus vote for a block at (height, round).
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Vote {
+    pub vote_type: VoteType,
+    pub height: u64,
+    pub round: u32,
+    pub block_hash: Vec<u8>,
+    pub validator: String,
+    pub signature: Vec<u8>,
+    /// Optional BLS signature over the commit sign-doc, attached to
+    /// non-nil precommits by validators with a registered BLS key so
+    /// the commit certificate can carry one aggregated signature.
+    #[serde(default)]
+    pub bls_signature: Vec<u8>,
+    pub timestamp: u64,
+}
+
+impl Vote {
+    pub fn new(
+        vote_type: VoteType,
+        height: u64,
+        round: u32,
+        block_hash: Vec<u8>,
+        validator: String,
+    ) -> Self {
+        Self {
+            vote_type,
+            height,
+            round,
+            block_hash,
+            validator,
+            signature: Vec::new(),
+            bls_signature: Vec::new(),
+            timestamp: now_unix(),
+        }
+    }
+
+    /// A vote for no block this round.
+    pub fn new_nil(vote_type: VoteType, height: u64, round: u32, validator: String) -> Self {
+        Self::new(vote_type, height, round, Vec::new(), validator)
+    }
+
+    /// Whether this vote is for nil (no block) rather than a proposal.
+    pub fn is_nil(&self) -> bool {
+        self.block_hash.is_empty()
+    }
+
+    /// Bytes covered by the validator's signature: a canonical sign-doc
+    /// scoped to `chain_id` and the vote type, so votes cannot replay
+    /// across networks or between prevote and precommit.
+    pub fn signing_bytes(&self, chain_id: &str) -> Vec<u8> {
+        let msg_type = match self.vote_type {
+            VoteType::Prevote => "consensus/prevote",
+            VoteType::Precommit => "consensus/precommit",
+        };
+        crate::security::consensus_sign_doc(
+            chain_id,
+            msg_type,
+            self.height,
+            self.round,
+            &self.block_hash,
+        )
+    }
+}
+
+/// Step timeouts and the per-round escalation delta, taken from
+/// [`ConsensusConfig`](crate::config::ConsensusConfig) so operators can
+/// tune liveness for their network's latency.
+#[derive(Debug, Clone, Copy)]
+pub struct RoundTimeouts {
+    pub propose: Duration,
+    pub prevote: Duration,
+    pub precommit: Duration,
+    /// Extra timeout added per round number, so later rounds wait
+    /// longer.
+    pub delta: Duration,
+}
+
+impl Default for RoundTimeouts {
+    fn default() -> Self {
+        Self {
+            propose: Duration::from_millis(3000),
+            prevote: Duration::from_millis(1000),
+            precommit: Duration::from_millis(1000),
+            delta: Duration::from_millis(500),
+        }
+    }
+}
+
+impl From<&crate::config::ConsensusConfig> for RoundTimeouts {
+    fn from(config: &crate::config::ConsensusConfig) -> Self {
+        Self {
+            propose: Duration::from_millis(config.timeout_propose_ms),
+            prevote: Duration::from_millis(config.timeout_prevote_ms),
+            precommit: Duration::from_millis(config.timeout_precommit_ms),
+            delta: Duration::from_millis(config.timeout_delta_ms),
+        }
+    }
+}
+
+/// Per-round consensus state: current step, received votes, timeouts.
+#[derive(Debug, Clone)]
+pub struct RoundState {
+    pub height: u64,
+    pub round: u32,
+    pub step: Step,
+    pub proposal: Option<Block>,
+    /// Prevotes received this round, keyed by validator address.
+    pub prevotes: HashMap<String, Vote>,
+    /// Precommits received this round, keyed by validator address.
+    pub precommits: HashMap<String, Vote>,
+    pub timeouts: RoundTimeouts,
+    /// The +2/3 prevote polka observed this round, if any. An empty hash
+    /// means the polka was for nil ("no block this round").
+    pub polka: Option<Vec<u8>>,
+    /// When the current step was entered; drives the timeout task.
+    pub step_started: Instant,
+}
+
+impl RoundState {
+    pub fn new(height: u64, round: u32) -> Self {
+        Self::with_timeouts(height, round, RoundTimeouts::default())
+    }
+
+    pub fn with_timeouts(height: u64, round: u32, timeouts: RoundTimeouts) -> Self {
+        Self {
+            height,
+            round,
+            step: Step::Propose,
+            proposal: None,
+            prevotes: HashMap::new(),
+            precommits: HashMap::new(),
+            timeouts,
+            polka: None,
+            step_started: Instant::now(),
+        }
+    }
+
+    /// Timeout for the current step, escalated by the round number.
+    /// The commit step has no timeout.
+    pub fn timeout_for_step(&self) -> Option<Duration> {
+        let base = match self.step {
+            Step::Propose => self.timeouts.propose,
+            Step::Prevote => self.timeouts.prevote,
+            Step::Precommit => self.timeouts.precommit,
+            Step::Commit => return None,
+        };
+        Some(base + self.timeouts.delta * self.round)
+    }
+}
+
+/// What the engine must do after a step timed out.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimeoutAction {
+    /// No proposal arrived in time: prevote nil.
+    PrevoteNil { height: u64, round: u32 },
+    /// No prevote polka in time: precommit nil.
+    PrecommitNil { height: u64, round: u32 },
+    /// The round failed entirely: a new round was started.
+    NextRound { height: u64, round: u32 },
+}
+
+/// Tendermint-style BFT state tracked across rounds at one height.
+pub struct TendermintConsensus {
+    pub round_state: RoundState,
+    pub locked_block: Option<Block>,
+    pub locked_round: Option<u32>,
+    pub valid_block: Option<Block>,
+    pub valid_round: Option<u32>,
+    /// Polkas observed at this height, keyed by round. Survives round
+    /// changes so later proposals can reference an earlier polka round.
+    pub polka_rounds: HashMap<u32, Vec<u8>>,
+}
+
+impl TendermintConsensus {
+    pub fn new(height: u64) -> Self {
+        Self::with_timeouts(height, RoundTimeouts::default())
+    }
+
+    pub fn with_timeouts(height: u64, timeouts: RoundTimeouts) -> Self {
+        Self {
+            round_state: RoundState::with_timeouts(height, 0, timeouts),
+            locked_block: None,
+            locked_round: None,
+            valid_block: None,
+            valid_round: None,
+            polka_rounds: HashMap::new(),
+        }
+    }
+
+    /// Record a vote for the current round. Returns false if it was ignored.
+    pub fn add_vote(&mut self, vote: Vote) -> bool {
+        if vote.height != self.round_state.height || vote.round != self.round_state.round {
+            return false;
+        }
+        let votes = match vote.vote_type {
+            VoteType::Prevote => &mut self.round_state.prevotes,
+            VoteType::Precommit => &mut self.round_state.precommits,
+        };
+        votes.insert(vote.validator.clone(), vote);
+        true
+    }
+
+    /// Whether the given voting power total has reached +2/3 of `total_power`.
+    pub fn has_two_thirds(voted_power: u64, total_power: u64) -> bool {
+        voted_power * 3 > total_power * 2
+    }
+
+    /// Detect double signing within the current round's vote maps: a
+    /// validator appearing with two different block hashes.
+    pub fn check_double_signing(&self) -> Vec<String> {
+        let mut offenders = Vec::new();
+        for (validator, prevote) in &self.round_state.prevotes {
+            if let Some(precommit) = self.round_state.precommits.get(validator) {
+                if precommit.block_hash != prevote.block_hash
+                    && !precommit.block_hash.is_empty()
+                    && !prevote.block_hash.is_empty()
+                {
+                    offenders.push(validator.clone());
+                }
+            }
+        }
+        offenders
+    }
+
+    pub fn start_round(&mut self, round: u32) {
+        self.round_state =
+            RoundState::with_timeouts(self.round_state.height, round, self.round_state.timeouts);
+    }
+
+    /// Voting power prevoting for `block_hash` this round, given each
+    /// validator's power looked up through `power_of`.
+    pub fn prevote_power_for(
+        &self,
+        block_hash: &[u8],
+        power_of: impl Fn(&str) -> u64,
+    ) -> u64 {
+        self.round_state
+            .prevotes
+            .values()
+            .filter(|vote| vote.block_hash == block_hash)
+            .map(|vote| power_of(&vote.validator))
+            .sum()
+    }
+
+    /// Apply the proof-of-lock rules after prevotes change: if any block
+    /// has a +2/3 prevote polka this round, lock on it (replacing an
+    /// older lock) and remember it as the valid block. A polka for nil
+    /// releases the lock instead.
+    pub fn update_lock_on_polka(&mut self, total_power: u64, power_of: impl Fn(&str) -> u64) {
+        let mut by_hash: HashMap<&[u8], u64> = HashMap::new();
+        for vote in self.round_state.prevotes.values() {
+            *by_hash.entry(vote.block_hash.as_slice()).or_default() += power_of(&vote.validator);
+        }
+        let polka = by_hash
+            .into_iter()
+            .find(|(_, power)| Self::has_two_thirds(*power, total_power))
+            .map(|(hash, _)| hash.to_vec());
+        let Some(hash) = polka else {
+            return;
+        };
+        let round = self.round_state.round;
+        self.round_state.polka = Some(hash.clone());
+        self.polka_rounds.insert(round, hash.clone());
+        if hash.is_empty() {
+            // Polka for nil: the network moved past whatever we locked on.
+            if self.locked_round.is_some_and(|locked| locked < round) {
+                self.locked_block = None;
+                self.locked_round = None;
+            }
+            return;
+        }
+        if let Some(proposal) = &self.round_state.proposal {
+            if proposal.hash() == hash {
+                self.locked_block = Some(proposal.clone());
+                self.locked_round = Some(round);
+                self.valid_block = Some(proposal.clone());
+                self.valid_round = Some(round);
+            }
+        }
+    }
+
+    /// The hash to precommit once the prevote step resolves: the block
+    /// with a polka this round, or nil when the polka was for nil or no
+    /// polka formed at all.
+    pub fn precommit_decision(&self) -> Vec<u8> {
+        self.round_state.polka.clone().unwrap_or_default()
+    }
+
+    /// The block hash this node may prevote for: while locked, only the
+    /// locked block (or nil) is acceptable unless the proposal carries a
+    /// newer polka round than our lock.
+    pub fn acceptable_prevote(&self, proposal_hash: &[u8], proposal_pol_round: Option<u32>) -> Vec<u8> {
+        match (&self.locked_block, self.locked_round) {
+            (Some(locked), Some(locked_round)) => {
+                if locked.hash() == proposal_hash {
+                    proposal_hash.to_vec()
+                } else if proposal_pol_round.is_some_and(|pol| pol > locked_round) {
+                    // A newer polka justifies unlocking onto the proposal.
+                    proposal_hash.to_vec()
+                } else {
+                    Vec::new()
+                }
+            }
+            _ => proposal_hash.to_vec(),
+        }
+    }
+
+    /// Advance the step if its timeout has elapsed, returning the action
+    /// the engine must take (vote nil, or move to the next round).
+    pub fn advance_step_if_due(&mut self, now: Instant) -> Option<TimeoutAction> {
+        let timeout = self.round_state.timeout_for_step()?;
+        if now.duration_since(self.round_state.step_started) < timeout {
+            return None;
+        }
+        let height = self.round_state.height;
+        let round = self.round_state.round;
+        match self.round_state.step {
+            Step::Propose => {
+                self.round_state.step = Step::Prevote;
+                self.round_state.step_started = now;
+                Some(TimeoutAction::PrevoteNil { height, round })
+            }
+            Step::Prevote => {
+                self.round_state.step = Step::Precommit;
+                self.round_state.step_started = now;
+                Some(TimeoutAction::PrecommitNil { height, round })
+            }
+            Step::Precommit => {
+                self.start_round(round + 1);
+                Some(TimeoutAction::NextRound {
+                    height,
+                    round: round + 1,
+                })
+            }
+            Step::Commit => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_thirds_threshold() {
+        assert!(TendermintConsensus::has_two_thirds(67, 100));
+        assert!(!TendermintConsensus::has_two_thirds(66, 100));
+    }
+
+    #[test]
+    fn nil_polka_drives_nil_precommit() {
+        let mut consensus = TendermintConsensus::new(1);
+        // No polka yet: the only safe precommit is nil.
+        assert!(consensus.precommit_decision().is_empty());
+        for validator in ["val1", "val2", "val3"] {
+            let vote = Vote::new_nil(VoteType::Prevote, 1, 0, validator.into());
+            assert!(vote.is_nil());
+            consensus.add_vote(vote);
+        }
+        consensus.update_lock_on_polka(4, |_| 1);
+        assert_eq!(consensus.round_state.polka, Some(Vec::new()));
+        assert_eq!(consensus.polka_rounds.get(&0), Some(&Vec::new()));
+        assert!(consensus.precommit_decision().is_empty());
+    }
+
+    #[test]
+    fn polka_locks_and_newer_polka_unlocks() {
+        let block = Block::new(1, Vec::new(), Vec::new(), "val1".into(), Vec::new());
+        let hash = block.hash();
+        let mut consensus = TendermintConsensus::new(1);
+        consensus.round_state.proposal = Some(block);
+        for validator in ["val1", "val2", "val3"] {
+            consensus.add_vote(Vote::new(
+                VoteType::Prevote,
+                1,
+                0,
+                hash.clone(),
+                validator.into(),
+            ));
+        }
+        consensus.update_lock_on_polka(4, |_| 1);
+        assert_eq!(consensus.locked_round, Some(0));
+        // While locked, only the locked block (or a proposal with a newer
+        // polka round) is an acceptable prevote.
+        assert_eq!(consensus.acceptable_prevote(&hash, None), hash);
+        assert_eq!(consensus.acceptable_prevote(b"other", None), Vec::<u8>::new());
+        assert_eq!(consensus.acceptable_prevote(b"other", Some(1)), b"other".to_vec());
+        // A later nil polka releases the lock.
+        consensus.start_round(1);
+        for validator in ["val1", "val2", "val3"] {
+            consensus.add_vote(Vote::new(
+                VoteType::Prevote,
+                1,
+                1,
+                Vec::new(),
+                validator.into(),
+            ));
+        }
+        consensus.update_lock_on_polka(4, |_| 1);
+        assert_eq!(consensus.locked_round, None);
+        assert!(consensus.locked_block.is_none());
+    }
+
+    #[test]
+    fn timeouts_advance_steps_and_escalate_rounds() {
+        let mut consensus = TendermintConsensus::new(5);
+        let start = consensus.round_state.step_started;
+        // Nothing fires before the timeout.
+        assert_eq!(consensus.advance_step_if_due(start), None);
+        let after = start + Duration::from_secs(60);
+        assert_eq!(
+            consensus.advance_step_if_due(after),
+            Some(TimeoutAction::PrevoteNil { height: 5, round: 0 })
+        );
+        assert_eq!(
+            consensus.advance_step_if_due(after + Duration::from_secs(60)),
+            Some(TimeoutAction::PrecommitNil { height: 5, round: 0 })
+        );
+        assert_eq!(
+            consensus.advance_step_if_due(after + Duration::from_secs(120)),
+            Some(TimeoutAction::NextRound { height: 5, round: 1 })
+        );
+        // Round 1 waits longer than round 0 did.
+        let round0 = RoundState::new(5, 0).timeout_for_step().unwrap();
+        assert!(consensus.round_state.timeout_for_step().unwrap() > round0);
+    }
+
+    #[test]
+    fn sign_docs_are_domain_separated() {
+        let prevote = Vote::new(VoteType::Prevote, 5, 0, vec![1; 32], "val1".into());
+        let mut precommit = prevote.clone();
+        precommit.vote_type = VoteType::Precommit;
+        // The same slot signed on another chain, or as the other vote
+        // type, produces a different preimage.
+        assert_ne!(prevote.signing_bytes("chain-a"), prevote.signing_bytes("chain-b"));
+        assert_ne!(prevote.signing_bytes("chain-a"), precommit.signing_bytes("chain-a"));
+    }
+}
@@ -0,0 +1,78 @@
+use serde::{Deserialize, Serialize};
+
+/// A governance-approved upgrade: the chain halts at `height` and the
+/// named binary version resumes from persisted state.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UpgradePlan {
+    /// Upgrade name, matched by the new binary.
+    pub name: String,
+    /// Height at which block production stops.
+    pub height: u64,
+    /// Operator-facing hint: download URL or expected version.
+    pub info: String,
+}
+
+/// Holds at most one pending upgrade plan and decides when to halt.
+#[derive(Debug, Default)]
+pub struct UpgradeManager {
+    plan: Option<UpgradePlan>,
+}
+
+impl UpgradeManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule an upgrade. Rejects past heights and double scheduling;
+    /// an existing plan must be cancelled first.
+    pub fn schedule(&mut self, plan: UpgradePlan, head_height: u64) -> Result<(), String> {
+        if plan.height <= head_height {
+            return Err(format!(
+                "upgrade height {} is not in the future (head {head_height})",
+                plan.height
+            ));
+        }
+        if let Some(existing) = &self.plan {
+            return Err(format!("upgrade {} already scheduled", existing.name));
+        }
+        self.plan = Some(plan);
+        Ok(())
+    }
+
+    /// Cancel the pending plan, if any.
+    pub fn cancel(&mut self) -> Option<UpgradePlan> {
+        self.plan.take()
+    }
+
+    pub fn pending(&self) -> Option<&UpgradePlan> {
+        self.plan.as_ref()
+    }
+
+    /// The plan to halt for, if producing `next_height` would reach it.
+    pub fn should_halt(&self, next_height: u64) -> Option<&UpgradePlan> {
+        self.plan.as_ref().filter(|plan| next_height >= plan.height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn halts_at_plan_height() {
+        let mut manager = UpgradeManager::new();
+        let plan = UpgradePlan {
+            name: "v2".into(),
+            height: 100,
+            info: "artha_fs 2.0".into(),
+        };
+        assert!(manager.schedule(plan.clone(), 100).is_err());
+        manager.schedule(plan.clone(), 50).unwrap();
+        // Only one plan at a time.
+        assert!(manager.schedule(plan, 50).is_err());
+        assert!(manager.should_halt(99).is_none());
+        assert_eq!(manager.should_halt(100).unwrap().name, "v2");
+        assert!(manager.cancel().is_some());
+        assert!(manager.should_halt(100).is_none());
+    }
+}
@@ -0,0 +1,103 @@
+//! Smart contract deployment and execution: code storage, deterministic
+//! contract addresses, and the transaction payloads that carry
+//! deployments and calls.
+
+pub mod vm;
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use crate::types::Transaction;
+
+/// WASM module preamble: magic plus version 1.
+const WASM_PREAMBLE: &[u8] = b"\0asm\x01\x00\x00\x00";
+
+/// Contract operations carried in a transaction's data payload as JSON.
+/// A non-contract payload simply fails to parse.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ContractTx {
+    /// Deploy `code` as a new contract owned by the sender.
+    Deploy { code: Vec<u8> },
+    /// Call the contract account at `contract` with `input` bytes.
+    Call { contract: String, input: Vec<u8> },
+}
+
+impl ContractTx {
+    /// Parse a contract operation out of a transaction, if it carries one.
+    pub fn parse(tx: &Transaction) -> Option<Self> {
+        serde_json::from_slice(&tx.data).ok()
+    }
+}
+
+/// Hash identifying a stored code blob.
+pub fn code_hash(code: &[u8]) -> Vec<u8> {
+    Sha256::digest(code).to_vec()
+}
+
+/// Deterministic contract address: every node deploying the same code
+/// from the same (sender, nonce) derives the same account.
+pub fn contract_address(sender: &str, nonce: u64, code_hash: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(sender.as_bytes());
+    hasher.update(nonce.to_be_bytes());
+    hasher.update(code_hash);
+    hex::encode(&hasher.finalize()[..20])
+}
+
+/// Deployed contract code, stored once per hash and shared by every
+/// contract account pointing at it.
+pub struct ContractStore {
+    code: RwLock<HashMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl ContractStore {
+    pub fn new() -> Self {
+        Self {
+            code: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Validate and store a code blob, returning its hash. Only blobs
+    /// with a WASM preamble are accepted.
+    pub async fn insert(&self, code: Vec<u8>) -> Result<Vec<u8>, String> {
+        if !code.starts_with(WASM_PREAMBLE) {
+            return Err("code is not a WASM module".into());
+        }
+        let hash = code_hash(&code);
+        self.code.write().await.insert(hash.clone(), code);
+        Ok(hash)
+    }
+
+    pub async fn get(&self, hash: &[u8]) -> Option<Vec<u8>> {
+        self.code.read().await.get(hash).cloned()
+    }
+}
+
+impl Default for ContractStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn deployments_are_deterministic_and_validated() {
+        let store = ContractStore::new();
+        let code = [WASM_PREAMBLE, &[1, 2, 3]].concat();
+        let hash = store.insert(code.clone()).await.unwrap();
+        assert_eq!(store.get(&hash).await.unwrap(), code);
+        assert!(store.insert(vec![9, 9, 9]).await.is_err());
+
+        let address = contract_address("alice", 1, &hash);
+        assert_eq!(address, contract_address("alice", 1, &hash));
+        assert_ne!(address, contract_address("alice", 2, &hash));
+        assert_eq!(address.len(), 40);
+    }
+}
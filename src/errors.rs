@@ -0,0 +1,202 @@
+//! Crate-wide error code taxonomy.
+//!
+//! Every module-level error enum maps onto a stable, machine-readable
+//! [`ErrorCode`], so causes survive module boundaries and come out through
+//! the API's error envelope instead of being flattened into strings.
+
+use serde::Serialize;
+use thiserror::Error;
+
+use crate::consensus::ConsensusError;
+use crate::network::NetworkError;
+use crate::security::SecurityError;
+use crate::types::TransactionError;
+
+/// Stable error codes exposed to API clients and logs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ErrorCode {
+    // Transactions
+    TxInvalidSignature,
+    TxInvalidNonce,
+    TxDuplicate,
+    TxPoolFull,
+    TxInsufficientBalance,
+    TxInvalid,
+    // Consensus
+    ConsensusNotValidator,
+    ConsensusInvalidProposer,
+    ConsensusInvalidBlock,
+    ConsensusInvalidVote,
+    ConsensusInvalidCommit,
+    ConsensusNetwork,
+    ConsensusInternal,
+    // Networking
+    NetIo,
+    NetPeerNotFound,
+    NetHandshake,
+    NetCodec,
+    NetLimitReached,
+    NetConnectionClosed,
+    // Security
+    SecInvalidKey,
+    SecInvalidSignature,
+    SecAccount,
+    SecBackend,
+    // Generic
+    NotFound,
+    Unauthorized,
+    InvalidRequest,
+    Timeout,
+    Internal,
+}
+
+impl ErrorCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::TxInvalidSignature => "TX_INVALID_SIGNATURE",
+            ErrorCode::TxInvalidNonce => "TX_INVALID_NONCE",
+            ErrorCode::TxDuplicate => "TX_DUPLICATE",
+            ErrorCode::TxPoolFull => "TX_POOL_FULL",
+            ErrorCode::TxInsufficientBalance => "TX_INSUFFICIENT_BALANCE",
+            ErrorCode::TxInvalid => "TX_INVALID",
+            ErrorCode::ConsensusNotValidator => "CONSENSUS_NOT_VALIDATOR",
+            ErrorCode::ConsensusInvalidProposer => "CONSENSUS_INVALID_PROPOSER",
+            ErrorCode::ConsensusInvalidBlock => "CONSENSUS_INVALID_BLOCK",
+            ErrorCode::ConsensusInvalidVote => "CONSENSUS_INVALID_VOTE",
+            ErrorCode::ConsensusInvalidCommit => "CONSENSUS_INVALID_COMMIT",
+            ErrorCode::ConsensusNetwork => "CONSENSUS_NETWORK",
+            ErrorCode::ConsensusInternal => "CONSENSUS_INTERNAL",
+            ErrorCode::NetIo => "NET_IO",
+            ErrorCode::NetPeerNotFound => "NET_PEER_NOT_FOUND",
+            ErrorCode::NetHandshake => "NET_HANDSHAKE",
+            ErrorCode::NetCodec => "NET_CODEC",
+            ErrorCode::NetLimitReached => "NET_LIMIT_REACHED",
+            ErrorCode::NetConnectionClosed => "NET_CONNECTION_CLOSED",
+            ErrorCode::SecInvalidKey => "SEC_INVALID_KEY",
+            ErrorCode::SecInvalidSignature => "SEC_INVALID_SIGNATURE",
+            ErrorCode::SecAccount => "SEC_ACCOUNT",
+            ErrorCode::SecBackend => "SEC_BACKEND",
+            ErrorCode::NotFound => "NOT_FOUND",
+            ErrorCode::Unauthorized => "UNAUTHORIZED",
+            ErrorCode::InvalidRequest => "INVALID_REQUEST",
+            ErrorCode::Timeout => "TIMEOUT",
+            ErrorCode::Internal => "INTERNAL",
+        }
+    }
+}
+
+/// Mapping from a concrete error to its stable code.
+pub trait ErrorCoded {
+    fn code(&self) -> ErrorCode;
+}
+
+impl ErrorCoded for TransactionError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            TransactionError::InvalidSignature => ErrorCode::TxInvalidSignature,
+            TransactionError::InvalidNonce { .. } => ErrorCode::TxInvalidNonce,
+            TransactionError::Duplicate(_) => ErrorCode::TxDuplicate,
+            TransactionError::PoolFull => ErrorCode::TxPoolFull,
+            TransactionError::InsufficientBalance { .. } => ErrorCode::TxInsufficientBalance,
+            TransactionError::Invalid(_) => ErrorCode::TxInvalid,
+        }
+    }
+}
+
+impl ErrorCoded for ConsensusError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            ConsensusError::NotValidator => ErrorCode::ConsensusNotValidator,
+            ConsensusError::InvalidProposer { .. } => ErrorCode::ConsensusInvalidProposer,
+            ConsensusError::InvalidBlock(_) => ErrorCode::ConsensusInvalidBlock,
+            ConsensusError::InvalidVote(_) => ErrorCode::ConsensusInvalidVote,
+            ConsensusError::InvalidCommit(_) => ErrorCode::ConsensusInvalidCommit,
+            ConsensusError::Network(_) => ErrorCode::ConsensusNetwork,
+            ConsensusError::Internal(_) => ErrorCode::ConsensusInternal,
+        }
+    }
+}
+
+impl ErrorCoded for NetworkError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            NetworkError::Io(_) => ErrorCode::NetIo,
+            NetworkError::PeerNotFound(_) => ErrorCode::NetPeerNotFound,
+            NetworkError::Handshake(_) => ErrorCode::NetHandshake,
+            NetworkError::Codec(_) => ErrorCode::NetCodec,
+            NetworkError::LimitReached(_) => ErrorCode::NetLimitReached,
+            NetworkError::ConnectionClosed => ErrorCode::NetConnectionClosed,
+        }
+    }
+}
+
+impl ErrorCoded for SecurityError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            SecurityError::InvalidKey(_) => ErrorCode::SecInvalidKey,
+            SecurityError::InvalidSignature => ErrorCode::SecInvalidSignature,
+            SecurityError::Account(_) => ErrorCode::SecAccount,
+            SecurityError::Backend(_) => ErrorCode::SecBackend,
+        }
+    }
+}
+
+/// Top-level error covering every subsystem, preserving the source error
+/// (and therefore its code and message) through `#[from]` conversions.
+#[derive(Debug, Error)]
+pub enum NodeError {
+    #[error(transparent)]
+    Transaction(#[from] TransactionError),
+    #[error(transparent)]
+    Consensus(#[from] ConsensusError),
+    #[error(transparent)]
+    Network(#[from] NetworkError),
+    #[error(transparent)]
+    Security(#[from] SecurityError),
+}
+
+impl ErrorCoded for NodeError {
+    fn code(&self) -> ErrorCode {
+        match self {
+            NodeError::Transaction(e) => e.code(),
+            NodeError::Consensus(e) => e.code(),
+            NodeError::Network(e) => e.code(),
+            NodeError::Security(e) => e.code(),
+        }
+    }
+}
+
+/// JSON error body returned by the API.
+#[derive(Debug, Serialize)]
+pub struct ErrorEnvelope {
+    pub code: &'static str,
+    pub error: String,
+}
+
+impl ErrorEnvelope {
+    pub fn new(code: ErrorCode, error: impl Into<String>) -> Self {
+        Self {
+            code: code.as_str(),
+            error: error.into(),
+        }
+    }
+
+    pub fn from_err<E: ErrorCoded + std::fmt::Display>(err: &E) -> Self {
+        Self::new(err.code(), err.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn node_error_preserves_source_code() {
+        let err: NodeError = TransactionError::PoolFull.into();
+        assert_eq!(err.code(), ErrorCode::TxPoolFull);
+        let envelope = ErrorEnvelope::from_err(&err);
+        assert_eq!(envelope.code, "TX_POOL_FULL");
+        assert_eq!(envelope.error, "transaction pool is full");
+    }
+}
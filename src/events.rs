@@ -0,0 +1,102 @@
+//! Typed pub/sub bus for chain events.
+//!
+//! The consensus engine publishes here as blocks finalize; WebSocket
+//! streams, metric refreshers, and indexer sinks subscribe without the
+//! engine knowing any of them exist. A slow subscriber lags and loses
+//! the oldest events rather than backpressuring consensus.
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use crate::consensus::ValidatorUpdate;
+
+/// Events published as blocks are finalized, in commit order.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChainEvent {
+    /// A block was committed.
+    NewBlock {
+        height: u64,
+        block_hash: String,
+        proposer: String,
+        transactions: usize,
+        timestamp: u64,
+    },
+    /// A transaction inside a committed block.
+    TxCommitted { hash: String, height: u64 },
+    /// The validator set changed at end-of-block.
+    ValidatorSetUpdated {
+        height: u64,
+        updates: Vec<ValidatorUpdate>,
+    },
+    /// Misbehavior evidence was committed on-chain.
+    EvidenceConfirmed {
+        height: u64,
+        validator: String,
+        evidence_hash: String,
+    },
+}
+
+/// Buffered events per subscriber before lagging sets in.
+const EVENT_CAPACITY: usize = 1024;
+
+/// Broadcast fan-out of [`ChainEvent`]s.
+pub struct EventBus {
+    sender: broadcast::Sender<ChainEvent>,
+}
+
+impl EventBus {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_CAPACITY);
+        Self { sender }
+    }
+
+    /// Publish an event to every current subscriber. With no
+    /// subscribers the event is simply dropped.
+    pub fn publish(&self, event: ChainEvent) {
+        let _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<ChainEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl Default for EventBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn events_reach_every_subscriber_in_order() {
+        let bus = EventBus::new();
+        let mut first = bus.subscribe();
+        let mut second = bus.subscribe();
+        bus.publish(ChainEvent::TxCommitted {
+            hash: "abc".into(),
+            height: 1,
+        });
+        bus.publish(ChainEvent::NewBlock {
+            height: 1,
+            block_hash: "ff".into(),
+            proposer: "val0".into(),
+            transactions: 1,
+            timestamp: 0,
+        });
+        for rx in [&mut first, &mut second] {
+            assert!(matches!(
+                rx.recv().await.unwrap(),
+                ChainEvent::TxCommitted { height: 1, .. }
+            ));
+            assert!(matches!(
+                rx.recv().await.unwrap(),
+                ChainEvent::NewBlock { height: 1, .. }
+            ));
+        }
+    }
+}
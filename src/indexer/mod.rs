@@ -0,0 +1,216 @@
+//! Durable event sink for external indexers.
+//!
+//! Chain events are appended to a persistent outbox column as they are
+//! published, and a background worker delivers them in order to the
+//! configured transports — a webhook URL, or a Kafka topic when built
+//! with the `kafka` feature. An entry is only deleted after every
+//! transport acknowledges it, so delivery is at-least-once across
+//! crashes and restarts; consumers deduplicate by height and tx hash.
+
+#[cfg(feature = "postgres")]
+pub mod postgres;
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::{broadcast, Notify};
+
+use crate::config::IndexerConfig;
+use crate::events::ChainEvent;
+use crate::storage::{Column, KvStore};
+
+/// How long the deliverer sleeps with an empty outbox before rescanning
+/// anyway, as a safety net against missed wakeups.
+const IDLE_RESCAN: Duration = Duration::from_secs(5);
+/// Retry backoff bounds for a failing transport.
+const MIN_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Writes chain events through a durable outbox to external sinks.
+pub struct IndexerSink {
+    store: Arc<dyn KvStore>,
+    config: IndexerConfig,
+    /// Next outbox sequence number; resumes past persisted entries.
+    next_seq: AtomicU64,
+    pending: Notify,
+    client: reqwest::Client,
+    #[cfg(feature = "kafka")]
+    producer: Option<rdkafka::producer::FutureProducer>,
+}
+
+impl IndexerSink {
+    pub fn new(store: Arc<dyn KvStore>, config: IndexerConfig) -> Self {
+        // Resume numbering after the highest persisted entry so order
+        // is preserved across restarts.
+        let next_seq = store
+            .scan_prefix(&Column::Outbox.key(&[]))
+            .ok()
+            .and_then(|entries| {
+                let (key, _) = entries.last()?;
+                let seq_bytes = key.get(key.len().checked_sub(8)?..)?;
+                Some(u64::from_be_bytes(seq_bytes.try_into().ok()?) + 1)
+            })
+            .unwrap_or(0);
+        #[cfg(feature = "kafka")]
+        let producer = config.kafka_brokers.as_ref().and_then(|brokers| {
+            rdkafka::config::ClientConfig::new()
+                .set("bootstrap.servers", brokers)
+                .create()
+                .map_err(|err| log::error!("cannot create kafka producer: {err}"))
+                .ok()
+        });
+        Self {
+            store,
+            config,
+            next_seq: AtomicU64::new(next_seq),
+            pending: Notify::new(),
+            client: reqwest::Client::new(),
+            #[cfg(feature = "kafka")]
+            producer,
+        }
+    }
+
+    /// Append an event to the outbox and wake the deliverer. The event
+    /// is durable once this returns.
+    pub fn enqueue(&self, event: &ChainEvent) {
+        let payload = match serde_json::to_vec(event) {
+            Ok(payload) => payload,
+            Err(err) => {
+                log::error!("cannot encode chain event: {err}");
+                return;
+            }
+        };
+        let seq = self.next_seq.fetch_add(1, Ordering::SeqCst);
+        if let Err(err) = self
+            .store
+            .put(&Column::Outbox.key(&seq.to_be_bytes()), &payload)
+        {
+            log::error!("cannot persist outbox entry {seq}: {err}");
+            return;
+        }
+        self.pending.notify_one();
+    }
+
+    /// Drain the engine's event stream into the outbox while a spawned
+    /// worker delivers it. Runs until the event bus closes.
+    pub async fn run(self: Arc<Self>, mut events: broadcast::Receiver<ChainEvent>) {
+        tokio::spawn(Arc::clone(&self).deliver_loop());
+        loop {
+            match events.recv().await {
+                Ok(event) => self.enqueue(&event),
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    log::warn!("indexer sink lagged, {missed} events not enqueued");
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    }
+
+    /// Deliver outbox entries oldest-first, deleting each only after
+    /// the transports acknowledge it; back off while a transport fails.
+    async fn deliver_loop(self: Arc<Self>) {
+        let mut backoff = MIN_BACKOFF;
+        loop {
+            let entries = self
+                .store
+                .scan_prefix(&Column::Outbox.key(&[]))
+                .unwrap_or_default();
+            if entries.is_empty() {
+                tokio::select! {
+                    _ = self.pending.notified() => {}
+                    _ = tokio::time::sleep(IDLE_RESCAN) => {}
+                }
+                continue;
+            }
+            let mut stalled = false;
+            for (key, payload) in entries {
+                if self.deliver(&payload).await {
+                    backoff = MIN_BACKOFF;
+                    if let Err(err) = self.store.delete(&key) {
+                        log::error!("cannot remove delivered outbox entry: {err}");
+                    }
+                } else {
+                    stalled = true;
+                    break;
+                }
+            }
+            if stalled {
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+
+    /// One attempt to push `payload` through every configured
+    /// transport; true only when all of them acknowledged.
+    async fn deliver(&self, payload: &[u8]) -> bool {
+        if let Some(url) = &self.config.webhook_url {
+            let delivered = self
+                .client
+                .post(url)
+                .header("content-type", "application/json")
+                .body(payload.to_vec())
+                .send()
+                .await
+                .is_ok_and(|response| response.status().is_success());
+            if !delivered {
+                return false;
+            }
+        }
+        #[cfg(feature = "kafka")]
+        if let Some(producer) = &self.producer {
+            use rdkafka::producer::FutureRecord;
+            let record = FutureRecord::<(), [u8]>::to(&self.config.kafka_topic).payload(payload);
+            if producer
+                .send(record, Duration::from_secs(5))
+                .await
+                .is_err()
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::memory::MemoryStore;
+
+    #[tokio::test]
+    async fn outbox_persists_in_order_and_resumes_sequence() {
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let sink = IndexerSink::new(Arc::clone(&store), IndexerConfig::default());
+        sink.enqueue(&ChainEvent::TxCommitted {
+            hash: "a".into(),
+            height: 1,
+        });
+        sink.enqueue(&ChainEvent::TxCommitted {
+            hash: "b".into(),
+            height: 1,
+        });
+        drop(sink);
+
+        // A restarted sink appends after the persisted entries instead
+        // of overwriting them.
+        let sink = IndexerSink::new(Arc::clone(&store), IndexerConfig::default());
+        sink.enqueue(&ChainEvent::TxCommitted {
+            hash: "c".into(),
+            height: 2,
+        });
+        let entries = store.scan_prefix(&Column::Outbox.key(&[])).unwrap();
+        assert_eq!(entries.len(), 3);
+        let hashes: Vec<String> = entries
+            .iter()
+            .map(|(_, payload)| {
+                serde_json::from_slice::<serde_json::Value>(payload).unwrap()["hash"]
+                    .as_str()
+                    .unwrap()
+                    .to_string()
+            })
+            .collect();
+        assert_eq!(hashes, vec!["a", "b", "c"]);
+    }
+}
@@ -0,0 +1,179 @@
+//! PostgreSQL indexing backend.
+//!
+//! Mirrors committed chain data into relational tables — blocks,
+//! transactions, transfers, and validator set changes — as blocks
+//! commit, so explorers can query SQL directly instead of building
+//! their own ETL. Inserts are idempotent (`ON CONFLICT DO NOTHING`),
+//! matching the event bus's at-least-once delivery.
+
+use std::sync::Arc;
+
+use tokio::sync::broadcast;
+use tokio_postgres::{Client, NoTls};
+
+use crate::consensus::ConsensusEngine;
+use crate::events::ChainEvent;
+
+/// Schema created on connect; `IF NOT EXISTS` keeps restarts cheap.
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS blocks (
+    height     BIGINT PRIMARY KEY,
+    hash       TEXT NOT NULL,
+    proposer   TEXT NOT NULL,
+    timestamp  BIGINT NOT NULL,
+    tx_count   BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS transactions (
+    hash       TEXT PRIMARY KEY,
+    height     BIGINT NOT NULL REFERENCES blocks(height),
+    sender     TEXT NOT NULL,
+    recipient  TEXT NOT NULL,
+    amount     BIGINT NOT NULL,
+    denom      TEXT NOT NULL,
+    nonce      BIGINT NOT NULL,
+    gas_limit  BIGINT NOT NULL,
+    gas_price  BIGINT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS transfers (
+    tx_hash    TEXT NOT NULL,
+    height     BIGINT NOT NULL,
+    sender     TEXT NOT NULL,
+    recipient  TEXT NOT NULL,
+    amount     BIGINT NOT NULL,
+    denom      TEXT NOT NULL,
+    PRIMARY KEY (tx_hash, recipient)
+);
+CREATE TABLE IF NOT EXISTS validator_updates (
+    height       BIGINT NOT NULL,
+    address      TEXT NOT NULL,
+    voting_power BIGINT NOT NULL,
+    PRIMARY KEY (height, address)
+);
+";
+
+/// Writes committed chain data into Postgres as events arrive.
+pub struct PostgresIndexer {
+    client: Client,
+    engine: Arc<ConsensusEngine>,
+}
+
+impl PostgresIndexer {
+    /// Connect and ensure the schema exists. The connection task is
+    /// spawned here and lives as long as the indexer.
+    pub async fn connect(
+        url: &str,
+        engine: Arc<ConsensusEngine>,
+    ) -> Result<Self, tokio_postgres::Error> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls).await?;
+        tokio::spawn(async move {
+            if let Err(err) = connection.await {
+                log::error!("postgres connection failed: {err}");
+            }
+        });
+        client.batch_execute(SCHEMA).await?;
+        Ok(Self { client, engine })
+    }
+
+    /// Consume the event stream until the bus closes, indexing each
+    /// committed block and validator change.
+    pub async fn run(self, mut events: broadcast::Receiver<ChainEvent>) {
+        loop {
+            match events.recv().await {
+                Ok(ChainEvent::NewBlock { height, .. }) => {
+                    if let Err(err) = self.index_block(height).await {
+                        log::error!("failed to index block {height}: {err}");
+                    }
+                }
+                Ok(ChainEvent::ValidatorSetUpdated { height, updates }) => {
+                    for update in updates {
+                        if let Err(err) = self
+                            .client
+                            .execute(
+                                "INSERT INTO validator_updates (height, address, voting_power) \
+                                 VALUES ($1, $2, $3) ON CONFLICT DO NOTHING",
+                                &[
+                                    &(height as i64),
+                                    &update.address,
+                                    &(update.voting_power as i64),
+                                ],
+                            )
+                            .await
+                        {
+                            log::error!("failed to index validator update: {err}");
+                        }
+                    }
+                }
+                Ok(_) => {}
+                Err(broadcast::error::RecvError::Lagged(missed)) => {
+                    log::warn!("postgres indexer lagged, {missed} events skipped");
+                }
+                Err(broadcast::error::RecvError::Closed) => return,
+            }
+        }
+    }
+
+    /// Insert one committed block with its transactions and transfers,
+    /// read back from the engine's retained state.
+    async fn index_block(&self, height: u64) -> Result<(), tokio_postgres::Error> {
+        let block = {
+            let state = self.engine.state.read().await;
+            state
+                .blocks
+                .iter()
+                .find(|block| block.header.height == height)
+                .cloned()
+        };
+        let Some(block) = block else {
+            log::warn!("block {height} already pruned, skipping index");
+            return Ok(());
+        };
+        self.client
+            .execute(
+                "INSERT INTO blocks (height, hash, proposer, timestamp, tx_count) \
+                 VALUES ($1, $2, $3, $4, $5) ON CONFLICT DO NOTHING",
+                &[
+                    &(height as i64),
+                    &hex::encode(block.hash()),
+                    &block.header.proposer,
+                    &(block.header.timestamp as i64),
+                    &(block.transactions.len() as i64),
+                ],
+            )
+            .await?;
+        for tx in &block.transactions {
+            self.client
+                .execute(
+                    "INSERT INTO transactions \
+                     (hash, height, sender, recipient, amount, denom, nonce, gas_limit, gas_price) \
+                     VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9) ON CONFLICT DO NOTHING",
+                    &[
+                        &tx.hash(),
+                        &(height as i64),
+                        &tx.sender,
+                        &tx.recipient,
+                        &(tx.amount as i64),
+                        &tx.denom,
+                        &(tx.nonce as i64),
+                        &(tx.gas_limit as i64),
+                        &(tx.gas_price as i64),
+                    ],
+                )
+                .await?;
+            self.client
+                .execute(
+                    "INSERT INTO transfers (tx_hash, height, sender, recipient, amount, denom) \
+                     VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT DO NOTHING",
+                    &[
+                        &tx.hash(),
+                        &(height as i64),
+                        &tx.sender,
+                        &tx.recipient,
+                        &(tx.amount as i64),
+                        &tx.denom,
+                    ],
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,19 @@
+//! Artha: an experimental proof-of-stake blockchain node.
+
+pub mod api;
+pub mod bridge;
+pub mod client;
+pub mod config;
+pub mod consensus;
+pub mod contracts;
+pub mod errors;
+pub mod events;
+pub mod indexer;
+pub mod metrics;
+pub mod network;
+pub mod security;
+pub mod storage;
+pub mod telemetry;
+pub mod tokens;
+pub mod types;
+pub mod wallet;
@@ -0,0 +1,1136 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use actix_web::{middleware, web, App, HttpServer};
+use clap::{Parser, Subcommand};
+use serde::{Deserialize, Serialize};
+
+use artha_fs::api::{self, ApiState};
+use artha_fs::client::{RpcClient, SignedTxBlob, TxBuilder};
+use artha_fs::config::{Genesis, NodeConfig, SignerConfig};
+use artha_fs::consensus::{genesis_hash, ConsensusEngine, ConsensusNetworkManager};
+use artha_fs::metrics::Metrics;
+use artha_fs::network::connection::ConnectionManager;
+use artha_fs::network::dialer::PersistentDialer;
+use artha_fs::network::health::HealthMonitor;
+use artha_fs::network::p2p::NodeIdentity;
+use artha_fs::network::reputation::ReputationTracker;
+use artha_fs::network::{light, NetworkManager, NetworkMessage, PeerInfo};
+use artha_fs::security::network::NetworkSecurityManager;
+use artha_fs::security::state::StateSecurityManager;
+use artha_fs::security::bls::BlsKeypair;
+use artha_fs::security::signer::Signer;
+use artha_fs::security::{PendingKey, SecurityManager, PENDING_KEY_FILE};
+use artha_fs::storage::{Column, KvStore};
+use artha_fs::types::fees::FixedRateOracle;
+use artha_fs::types::{FeePolicy, Transaction, TransactionPool, TxTracker};
+use artha_fs::wallet::Wallet;
+
+#[derive(Parser)]
+#[command(name = "artha", version, about = "Artha proof-of-stake blockchain node")]
+struct Cli {
+    /// Node home directory holding config, genesis, keys, and data.
+    #[arg(long, global = true, default_value = ".artha")]
+    home: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write a fresh config and single-validator genesis into the home
+    /// directory.
+    Init {
+        /// Chain identifier for the new network.
+        #[arg(long, default_value = "artha-devnet")]
+        chain_id: String,
+    },
+    /// Run the node.
+    Start {
+        /// Config file (TOML or JSON); defaults to <home>/config.json.
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Generate keys, a shared genesis, and per-node configs for a
+    /// local multi-validator devnet.
+    Testnet {
+        /// Number of validator nodes to generate.
+        #[arg(long, default_value_t = 4)]
+        validators: usize,
+        /// Chain identifier for the new network.
+        #[arg(long, default_value = "artha-testnet")]
+        chain_id: String,
+        /// First API port; node i binds this plus i.
+        #[arg(long, default_value_t = 8080)]
+        api_port: u16,
+        /// First P2P port; node i binds this plus i.
+        #[arg(long, default_value_t = 26656)]
+        p2p_port: u16,
+    },
+    /// Manage named keys under <home>/keys.
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommand,
+    },
+    /// Build, sign, and submit transactions against a running node.
+    Tx {
+        #[command(subcommand)]
+        command: TxCommand,
+    },
+    /// Submit signed transactions at a fixed rate and report commit
+    /// latency percentiles and sustained TPS.
+    Bench {
+        /// Name of the sending key in <home>/keys.
+        #[arg(long)]
+        from: String,
+        /// Recipient address; defaults to a throwaway account.
+        #[arg(long)]
+        to: Option<String>,
+        /// Transactions submitted per second.
+        #[arg(long, default_value_t = 50)]
+        rate: u64,
+        /// How long to keep submitting, in seconds.
+        #[arg(long, default_value_t = 10)]
+        duration_secs: u64,
+        /// API address of the node to submit through.
+        #[arg(long, default_value = "http://127.0.0.1:8080")]
+        node: String,
+    },
+    /// Query a running node's API.
+    Query {
+        #[command(subcommand)]
+        command: QueryCommand,
+    },
+}
+
+#[derive(Subcommand)]
+enum KeysCommand {
+    /// Generate a new mnemonic-backed key.
+    Add { name: String },
+    /// List stored keys and their addresses.
+    List,
+    /// Print a key's backup phrase.
+    Export { name: String },
+}
+
+#[derive(Subcommand)]
+enum TxCommand {
+    /// Sign a transfer with a stored key and submit it.
+    Send {
+        /// Name of the sending key in <home>/keys.
+        #[arg(long)]
+        from: String,
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        amount: u64,
+        #[arg(long)]
+        nonce: u64,
+        #[arg(long, default_value_t = 21_000)]
+        gas_limit: u64,
+        #[arg(long, default_value_t = 1)]
+        gas_price: u64,
+        /// API address of the node to submit through.
+        #[arg(long, default_value = "http://127.0.0.1:8080")]
+        node: String,
+    },
+    /// Sign a transfer without broadcasting it, printing a hex blob for
+    /// `POST /api/tx/raw`. With --offline no network access happens at
+    /// all, so the key can live on an air-gapped machine.
+    Sign {
+        /// Name of the signing key in <home>/keys.
+        #[arg(long)]
+        from: String,
+        #[arg(long)]
+        to: String,
+        #[arg(long)]
+        amount: u64,
+        /// Chain the blob is bound to; nodes on other chains reject it.
+        #[arg(long)]
+        chain_id: String,
+        /// Required with --offline; fetched from the node otherwise.
+        #[arg(long)]
+        nonce: Option<u64>,
+        #[arg(long, default_value_t = 21_000)]
+        gas_limit: u64,
+        #[arg(long, default_value_t = 1)]
+        gas_price: u64,
+        /// Never touch the network; fail instead of fetching the nonce.
+        #[arg(long)]
+        offline: bool,
+        /// Node consulted for the nonce when not signing offline.
+        #[arg(long, default_value = "http://127.0.0.1:8080")]
+        node: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum QueryCommand {
+    /// Fetch an account's balance and nonce.
+    Account {
+        address: String,
+        #[arg(long, default_value = "http://127.0.0.1:8080")]
+        node: String,
+    },
+    /// Fetch a block by height.
+    Block {
+        height: u64,
+        #[arg(long, default_value = "http://127.0.0.1:8080")]
+        node: String,
+    },
+}
+
+/// A named key in the keyring: the mnemonic is the source of truth, the
+/// address is stored alongside for listing without derivation.
+#[derive(Serialize, Deserialize)]
+struct KeyRecord {
+    name: String,
+    address: String,
+    phrase: String,
+}
+
+/// File in the keys directory holding the validator's BLS keypair.
+const BLS_KEY_FILE: &str = "bls.json";
+
+/// The validator's BLS keypair on disk: the secret scalar is the source
+/// of truth, the public key sits alongside for genesis assembly and
+/// staking registration.
+#[derive(Serialize, Deserialize)]
+struct BlsKeyRecord {
+    public_key: String,
+    private_key: String,
+}
+
+/// Persist a BLS keypair as `<keys_dir>/bls.json`.
+fn write_bls_key(keys_dir: &Path, keypair: &BlsKeypair) -> std::io::Result<()> {
+    let record = BlsKeyRecord {
+        public_key: hex::encode(keypair.public_key()),
+        private_key: hex::encode(keypair.to_bytes()),
+    };
+    std::fs::write(
+        keys_dir.join(BLS_KEY_FILE),
+        serde_json::to_vec_pretty(&record)?,
+    )
+}
+
+/// The validator's BLS keypair from `keys/bls.json`, when present and
+/// well-formed.
+fn load_bls_key(data_dir: &Path) -> Option<BlsKeypair> {
+    let raw = std::fs::read(data_dir.join("keys").join(BLS_KEY_FILE)).ok()?;
+    let record: BlsKeyRecord = serde_json::from_slice(&raw).ok()?;
+    let seed = hex::decode(&record.private_key).ok()?;
+    BlsKeypair::from_bytes(&<[u8; 32]>::try_from(seed).ok()?)
+}
+
+#[actix_web::main]
+async fn main() -> std::io::Result<()> {
+    let cli = Cli::parse();
+    let home = PathBuf::from(&cli.home);
+    match cli.command {
+        Command::Init { chain_id } => init(&home, &chain_id),
+        Command::Start { config } => {
+            artha_fs::telemetry::init();
+            let mut config = load_config(&home, config.as_deref())?;
+            config.apply_env_overrides();
+            let security = build_signer(&config, &home)?;
+            run_node(config, security).await
+        }
+        Command::Testnet {
+            validators,
+            chain_id,
+            api_port,
+            p2p_port,
+        } => testnet(&home, validators, &chain_id, api_port, p2p_port),
+        Command::Keys { command } => keys(&home, command),
+        Command::Tx { command } => tx(&home, command).await,
+        Command::Bench {
+            from,
+            to,
+            rate,
+            duration_secs,
+            node,
+        } => bench(&home, &from, to, rate, duration_secs, &node).await,
+        Command::Query { command } => query(command).await,
+    }
+}
+
+/// Write config.json, genesis.json, and the validator key. Refuses to
+/// overwrite an existing chain.
+fn init(home: &Path, chain_id: &str) -> std::io::Result<()> {
+    let config_path = home.join("config.json");
+    if config_path.exists() {
+        eprintln!("{} already exists; not overwriting", config_path.display());
+        return Ok(());
+    }
+    std::fs::create_dir_all(home.join("keys"))?;
+    let mut config = NodeConfig {
+        data_dir: home.to_string_lossy().into_owned(),
+        ..NodeConfig::default()
+    };
+    config.network.network_id = chain_id.to_string();
+    std::fs::write(&config_path, serde_json::to_vec_pretty(&config)?)?;
+
+    let wallet = Wallet::generate();
+    let validator = wallet.account(0);
+    let record = KeyRecord {
+        name: "validator".into(),
+        address: validator.address(),
+        phrase: wallet.phrase(),
+    };
+    std::fs::write(
+        home.join("keys").join("validator.json"),
+        serde_json::to_vec_pretty(&record)?,
+    )?;
+
+    // The BLS key rides alongside the ed25519 identity so commits on
+    // this chain can carry aggregated signatures from the start.
+    let bls = BlsKeypair::generate();
+    write_bls_key(&home.join("keys"), &bls)?;
+
+    let genesis = Genesis::single_node(
+        chain_id.to_string(),
+        validator.address(),
+        validator.public_key(),
+        config.consensus.clone(),
+    )
+    .with_bls_key(bls.public_key());
+    std::fs::write(
+        home.join("genesis.json"),
+        serde_json::to_vec_pretty(&genesis)?,
+    )?;
+    println!("initialized chain {chain_id} in {}", home.display());
+    println!("validator address: {}", record.address);
+    println!("validator backup phrase (store it safely):\n{}", record.phrase);
+    Ok(())
+}
+
+/// Generate a ready-to-run local testnet: one key, config, and data
+/// directory per validator under `<home>/node<i>`, all sharing a genesis
+/// that lists every validator and pre-funds its account. Each node's
+/// config names the others as seed peers, and ports are offset by node
+/// index so the whole network runs on one machine.
+fn testnet(
+    home: &Path,
+    validators: usize,
+    chain_id: &str,
+    api_port: u16,
+    p2p_port: u16,
+) -> std::io::Result<()> {
+    if validators == 0 {
+        eprintln!("--validators must be at least 1");
+        return Ok(());
+    }
+    if home.join("node0").exists() {
+        eprintln!("{}/node0 already exists; not overwriting", home.display());
+        return Ok(());
+    }
+
+    let wallets: Vec<Wallet> = (0..validators).map(|_| Wallet::generate()).collect();
+    let bls_keys: Vec<BlsKeypair> = (0..validators).map(|_| BlsKeypair::generate()).collect();
+    let genesis = Genesis {
+        chain_id: chain_id.to_string(),
+        genesis_time: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+        consensus: artha_fs::config::ConsensusConfig::default(),
+        validators: wallets
+            .iter()
+            .zip(&bls_keys)
+            .map(|(wallet, bls)| {
+                let account = wallet.account(0);
+                artha_fs::config::GenesisValidator {
+                    address: account.address(),
+                    public_key: account.public_key(),
+                    bls_public_key: bls.public_key(),
+                    power: 1,
+                }
+            })
+            .collect(),
+        accounts: wallets
+            .iter()
+            .map(|wallet| artha_fs::config::GenesisAccount {
+                address: wallet.address(0),
+                balance: 1_000_000,
+            })
+            .collect(),
+    };
+    let genesis_json = serde_json::to_vec_pretty(&genesis)?;
+
+    let p2p_address = |index: usize| format!("127.0.0.1:{}", p2p_port + index as u16);
+    for (index, wallet) in wallets.iter().enumerate() {
+        let node_dir = home.join(format!("node{index}"));
+        std::fs::create_dir_all(node_dir.join("keys"))?;
+
+        let mut config = NodeConfig {
+            api_address: format!("127.0.0.1:{}", api_port + index as u16),
+            data_dir: node_dir.to_string_lossy().into_owned(),
+            ..NodeConfig::default()
+        };
+        config.network.network_id = chain_id.to_string();
+        config.network.listen_address = p2p_address(index);
+        config.network.seed_nodes = (0..validators).filter(|i| *i != index).map(p2p_address).collect();
+        std::fs::write(
+            node_dir.join("config.json"),
+            serde_json::to_vec_pretty(&config)?,
+        )?;
+        std::fs::write(node_dir.join("genesis.json"), &genesis_json)?;
+
+        let record = KeyRecord {
+            name: "validator".into(),
+            address: wallet.address(0),
+            phrase: wallet.phrase(),
+        };
+        std::fs::write(
+            node_dir.join("keys").join("validator.json"),
+            serde_json::to_vec_pretty(&record)?,
+        )?;
+        write_bls_key(&node_dir.join("keys"), &bls_keys[index])?;
+        println!("node{index}: validator {} api {}", record.address, config.api_address);
+    }
+    println!(
+        "generated {validators}-validator testnet {chain_id} in {}",
+        home.display()
+    );
+    println!("start each node with: artha --home {}/node<i> start", home.display());
+    Ok(())
+}
+
+/// Load the node config: an explicit `--config` path (TOML or JSON)
+/// wins, then `<home>/config.json`, then defaults rooted at `home`.
+fn load_config(home: &Path, explicit: Option<&Path>) -> std::io::Result<NodeConfig> {
+    let path = match explicit {
+        Some(path) => path.to_path_buf(),
+        None => {
+            let default = home.join("config.json");
+            if !default.exists() {
+                return Ok(NodeConfig {
+                    data_dir: home.to_string_lossy().into_owned(),
+                    ..NodeConfig::default()
+                });
+            }
+            default
+        }
+    };
+    NodeConfig::load(&path)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+}
+
+/// Build the signing backend selected in the config: the on-disk
+/// validator key held in memory, or a YubiHSM session when the node is
+/// configured (and built) for one.
+fn build_signer(config: &NodeConfig, home: &Path) -> std::io::Result<Arc<dyn Signer>> {
+    match &config.signer {
+        SignerConfig::Memory => Ok(Arc::new(load_validator_key(home))),
+        #[cfg(feature = "hsm")]
+        SignerConfig::Yubihsm {
+            addr,
+            port,
+            auth_key_id,
+            key_id,
+        } => {
+            let password = std::env::var("ARTHA_HSM_PASSWORD").map_err(|_| {
+                std::io::Error::other("signer.backend = \"yubihsm\" requires ARTHA_HSM_PASSWORD")
+            })?;
+            artha_fs::security::signer::hsm::YubiHsmSigner::connect(
+                addr,
+                *port,
+                *auth_key_id,
+                &password,
+                *key_id,
+            )
+            .map(|signer| Arc::new(signer) as Arc<dyn Signer>)
+            .map_err(|err| std::io::Error::other(format!("cannot open HSM session: {err}")))
+        }
+        #[cfg(not(feature = "hsm"))]
+        SignerConfig::Yubihsm { .. } => Err(std::io::Error::other(
+            "signer.backend = \"yubihsm\" requires a binary built with the hsm feature",
+        )),
+    }
+}
+
+/// The validator signing key: a pending rotated key when one has been
+/// persisted by the rotation endpoint, otherwise account 0 of the
+/// stored validator mnemonic, or an ephemeral key when none has been
+/// initialized.
+fn load_validator_key(home: &Path) -> SecurityManager {
+    // Activating the pending key completes a two-phase rotation; the
+    // operator is expected to have registered it via staking first.
+    let pending_path = home.join("keys").join(PENDING_KEY_FILE);
+    if let Some(manager) = std::fs::read(&pending_path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<PendingKey>(&raw).ok())
+        .and_then(|pending| pending.to_manager().ok())
+    {
+        log::info!(
+            "activating rotated validator key {} from {}",
+            manager.address(),
+            pending_path.display()
+        );
+        return manager;
+    }
+    let path = home.join("keys").join("validator.json");
+    match std::fs::read(&path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<KeyRecord>(&raw).ok())
+        .and_then(|record| Wallet::from_phrase(&record.phrase).ok())
+    {
+        Some(wallet) => wallet.account(0),
+        None => {
+            log::warn!("no validator key at {}, using an ephemeral key", path.display());
+            SecurityManager::new()
+        }
+    }
+}
+
+fn keys(home: &Path, command: KeysCommand) -> std::io::Result<()> {
+    let keys_dir = home.join("keys");
+    std::fs::create_dir_all(&keys_dir)?;
+    match command {
+        KeysCommand::Add { name } => {
+            let path = keys_dir.join(format!("{name}.json"));
+            if path.exists() {
+                eprintln!("key {name} already exists");
+                return Ok(());
+            }
+            let wallet = Wallet::generate();
+            let record = KeyRecord {
+                name: name.clone(),
+                address: wallet.address(0),
+                phrase: wallet.phrase(),
+            };
+            std::fs::write(&path, serde_json::to_vec_pretty(&record)?)?;
+            println!("{}: {}", record.name, record.address);
+            println!("backup phrase (store it safely):\n{}", record.phrase);
+        }
+        KeysCommand::List => {
+            for entry in std::fs::read_dir(&keys_dir)? {
+                let raw = std::fs::read(entry?.path())?;
+                if let Ok(record) = serde_json::from_slice::<KeyRecord>(&raw) {
+                    println!("{}: {}", record.name, record.address);
+                }
+            }
+        }
+        KeysCommand::Export { name } => match load_key(home, &name) {
+            Some(record) => println!("{}", record.phrase),
+            None => eprintln!("no key named {name}"),
+        },
+    }
+    Ok(())
+}
+
+fn load_key(home: &Path, name: &str) -> Option<KeyRecord> {
+    let raw = std::fs::read(home.join("keys").join(format!("{name}.json"))).ok()?;
+    serde_json::from_slice(&raw).ok()
+}
+
+async fn tx(home: &Path, command: TxCommand) -> std::io::Result<()> {
+    match command {
+        TxCommand::Send {
+            from,
+            to,
+            amount,
+            nonce,
+            gas_limit,
+            gas_price,
+            node,
+        } => {
+            let Some(record) = load_key(home, &from) else {
+                eprintln!("no key named {from}");
+                return Ok(());
+            };
+            let signer = Wallet::from_phrase(&record.phrase)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?
+                .account(0);
+            let mut tx = Transaction::new(
+                signer.address(),
+                to,
+                amount,
+                nonce,
+                gas_limit,
+                gas_price,
+                Vec::new(),
+            );
+            tx.signature = signer.sign(&tx.signing_bytes());
+            let response = reqwest::Client::new()
+                .post(format!("{node}/api/transaction"))
+                .json(&tx)
+                .send()
+                .await
+                .map_err(to_io_err)?;
+            println!("{}", response.text().await.map_err(to_io_err)?);
+        }
+        TxCommand::Sign {
+            from,
+            to,
+            amount,
+            chain_id,
+            nonce,
+            gas_limit,
+            gas_price,
+            offline,
+            node,
+        } => {
+            let Some(record) = load_key(home, &from) else {
+                eprintln!("no key named {from}");
+                return Ok(());
+            };
+            let signer = Wallet::from_phrase(&record.phrase)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?
+                .account(0);
+            let nonce = match (nonce, offline) {
+                (Some(nonce), _) => nonce,
+                (None, true) => {
+                    eprintln!("--offline requires an explicit --nonce");
+                    return Ok(());
+                }
+                (None, false) => RpcClient::new(&node)
+                    .next_nonce(&signer.address())
+                    .await
+                    .map_err(|err| std::io::Error::other(err.to_string()))?,
+            };
+            let tx = TxBuilder::transfer(to, amount)
+                .nonce(nonce)
+                .gas_limit(gas_limit)
+                .gas_price(gas_price)
+                .sign(&signer)
+                .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?;
+            let blob = SignedTxBlob { chain_id, tx };
+            println!("{}", blob.encode());
+        }
+    }
+    Ok(())
+}
+
+/// Load-test a node: sign and submit transfers at `rate` per second for
+/// `duration_secs`, then wait for each to commit (watching its lifecycle
+/// via `/api/tx/{hash}/status`) and report sustained TPS plus commit
+/// latency percentiles.
+async fn bench(
+    home: &Path,
+    from: &str,
+    to: Option<String>,
+    rate: u64,
+    duration_secs: u64,
+    node: &str,
+) -> std::io::Result<()> {
+    let Some(record) = load_key(home, from) else {
+        eprintln!("no key named {from}");
+        return Ok(());
+    };
+    let signer = Wallet::from_phrase(&record.phrase)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))?
+        .account(0);
+    let recipient = to.unwrap_or_else(|| Wallet::generate().address(0));
+    let client = reqwest::Client::new();
+
+    // Continue from the sender's on-chain nonce so the run can repeat
+    // without restarting the node.
+    let mut nonce = client
+        .get(format!("{node}/api/account/{}", signer.address()))
+        .send()
+        .await
+        .ok()
+        .and_then(|response| response.error_for_status().ok());
+    let mut nonce = match nonce.take() {
+        Some(response) => response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|account| account["nonce"].as_u64())
+            .unwrap_or(0),
+        None => 0,
+    };
+
+    let total = rate * duration_secs;
+    println!("submitting {total} txs at {rate}/s against {node}");
+    let (results_tx, mut results_rx) = tokio::sync::mpsc::channel(total.max(1) as usize);
+    let mut ticker = tokio::time::interval(std::time::Duration::from_micros(1_000_000 / rate.max(1)));
+    let started = std::time::Instant::now();
+    let mut submitted = 0u64;
+    for _ in 0..total {
+        ticker.tick().await;
+        nonce += 1;
+        let mut tx = Transaction::new(
+            signer.address(),
+            recipient.clone(),
+            1,
+            nonce,
+            21_000,
+            1,
+            Vec::new(),
+        );
+        tx.signature = signer.sign(&tx.signing_bytes());
+        let hash = tx.hash();
+        let response = client
+            .post(format!("{node}/api/transaction"))
+            .json(&tx)
+            .send()
+            .await;
+        if !response.is_ok_and(|r| r.status().is_success()) {
+            continue;
+        }
+        submitted += 1;
+        tokio::spawn(watch_commit(
+            client.clone(),
+            node.to_string(),
+            hash,
+            std::time::Instant::now(),
+            results_tx.clone(),
+        ));
+    }
+    drop(results_tx);
+
+    let mut latencies = Vec::new();
+    let mut failed = 0u64;
+    while let Some(latency) = results_rx.recv().await {
+        match latency {
+            Some(latency) => latencies.push(latency),
+            None => failed += 1,
+        }
+    }
+    let elapsed = started.elapsed();
+    latencies.sort_unstable();
+    println!(
+        "submitted {submitted}, committed {}, failed/timed out {failed} in {:.1}s",
+        latencies.len(),
+        elapsed.as_secs_f64(),
+    );
+    if latencies.is_empty() {
+        return Ok(());
+    }
+    println!("tps: {:.1}", latencies.len() as f64 / elapsed.as_secs_f64());
+    for (label, pct) in [("p50", 0.50), ("p90", 0.90), ("p99", 0.99)] {
+        let index = ((latencies.len() - 1) as f64 * pct).round() as usize;
+        println!("commit latency {label}: {:?}", latencies[index]);
+    }
+    Ok(())
+}
+
+/// Poll one transaction's lifecycle until it commits (reporting latency
+/// from submission), fails, or times out after 60 seconds.
+async fn watch_commit(
+    client: reqwest::Client,
+    node: String,
+    hash: String,
+    submitted: std::time::Instant,
+    results: tokio::sync::mpsc::Sender<Option<std::time::Duration>>,
+) {
+    let deadline = submitted + std::time::Duration::from_secs(60);
+    while std::time::Instant::now() < deadline {
+        let status = client
+            .get(format!("{node}/api/tx/{hash}/status"))
+            .send()
+            .await
+            .ok();
+        if let Some(response) = status {
+            if let Ok(record) = response.json::<serde_json::Value>().await {
+                match record["state"].as_str() {
+                    Some("committed") => {
+                        let _ = results.send(Some(submitted.elapsed())).await;
+                        return;
+                    }
+                    Some("failed" | "evicted") => break,
+                    _ => {}
+                }
+            }
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    let _ = results.send(None).await;
+}
+
+async fn query(command: QueryCommand) -> std::io::Result<()> {
+    let url = match command {
+        QueryCommand::Account { address, node } => format!("{node}/api/account/{address}"),
+        QueryCommand::Block { height, node } => format!("{node}/api/block/{height}"),
+    };
+    let response = reqwest::get(&url).await.map_err(to_io_err)?;
+    println!("{}", response.text().await.map_err(to_io_err)?);
+    Ok(())
+}
+
+fn to_io_err(err: reqwest::Error) -> std::io::Error {
+    std::io::Error::other(err)
+}
+
+async fn run_node(config: NodeConfig, security: Arc<dyn Signer>) -> std::io::Result<()> {
+    // Proxy mode: expose only the public API, forwarding to backends.
+    if let Some(proxy_config) = config.proxy.clone() {
+        return artha_fs::api::proxy::run_proxy(&config.api_address, proxy_config).await;
+    }
+
+    let identity = Arc::new(NodeIdentity::load_or_generate(std::path::Path::new(
+        &config.data_dir,
+    ))?);
+    // Probe the signing backend up front; a node that cannot reach its
+    // key should fail at startup, not at its first proposal.
+    let validator_address = security
+        .address()
+        .map_err(|err| std::io::Error::other(format!("signer backend unavailable: {err}")))?;
+    let validator_public_key = security
+        .public_key()
+        .map_err(|err| std::io::Error::other(format!("signer backend unavailable: {err}")))?;
+    log::info!("node id: {}", identity.node_id());
+    log::info!("validator address: {validator_address}");
+    // The BLS key is optional: without one the node signs plain ed25519
+    // precommits and its commits cannot be aggregated.
+    let bls_key = load_bls_key(std::path::Path::new(&config.data_dir));
+    if bls_key.is_none() {
+        log::info!("no BLS key at keys/{BLS_KEY_FILE}; commit aggregation disabled");
+    }
+
+    // Initialize the chain from <data_dir>/genesis.json when present;
+    // otherwise fall back to a fresh single-validator devnet genesis.
+    let genesis_path = std::path::Path::new(&config.data_dir).join("genesis.json");
+    let genesis = if genesis_path.exists() {
+        match Genesis::load(&genesis_path) {
+            Ok(genesis) => genesis,
+            Err(err) => {
+                log::error!("{err}");
+                return Ok(());
+            }
+        }
+    } else {
+        log::info!(
+            "no genesis at {}, starting single-node devnet",
+            genesis_path.display()
+        );
+        Genesis::single_node(
+            config.network.network_id.clone(),
+            validator_address.clone(),
+            validator_public_key.clone(),
+            config.consensus.clone(),
+        )
+        .with_bls_key(
+            bls_key
+                .as_ref()
+                .map(|keypair| keypair.public_key())
+                .unwrap_or_default(),
+        )
+    };
+    log::info!("chain_id: {}", genesis.chain_id);
+    let validators = genesis.validator_set();
+
+    let fee_policy = FeePolicy::new(
+        genesis.consensus.fee_denoms.clone(),
+        Box::new(FixedRateOracle::native_only()),
+    );
+    let pool = Arc::new(TransactionPool::with_fee_policy(10_000, fee_policy));
+    pool.set_min_gas_price(config.min_gas_price);
+    let tracker = Arc::new(TxTracker::default());
+    let state = Arc::new(StateSecurityManager::new());
+    for account in &genesis.accounts {
+        state.set_balance(&account.address, account.balance).await;
+    }
+    state.commit_version(0).await;
+    pool.attach_state(Arc::clone(&state)).await;
+    let metrics = Metrics::handle();
+
+    let chain_hash = hex::encode(genesis_hash(
+        &genesis.chain_id,
+        &genesis.consensus,
+        &validators,
+    ));
+    let reputation = Arc::new(ReputationTracker::new());
+    let network = Arc::new(NetworkManager::new(
+        config.network.clone(),
+        chain_hash,
+        Arc::clone(&reputation),
+    ));
+    let network_security = Arc::new(NetworkSecurityManager::new(Arc::clone(&reputation)));
+    let connections = Arc::new(ConnectionManager::new(
+        Arc::clone(&network),
+        Arc::clone(&network_security),
+        Arc::clone(&identity),
+    ));
+    tokio::spawn({
+        let connections = Arc::clone(&connections);
+        async move {
+            if let Err(err) = connections.start().await {
+                log::error!("p2p listener failed: {err}");
+            }
+        }
+    });
+    let health = Arc::new(HealthMonitor::new(
+        Arc::clone(&network),
+        Arc::clone(&connections),
+    ));
+    tokio::spawn(Arc::clone(&health).run());
+    let dialer = Arc::new(PersistentDialer::new(
+        Arc::clone(&network),
+        Arc::clone(&connections),
+    ));
+    tokio::spawn(dialer.run());
+
+    let consensus_network = Arc::new(ConsensusNetworkManager::new());
+    let engine = Arc::new(ConsensusEngine::new(
+        &genesis,
+        Arc::clone(&pool),
+        Arc::clone(&consensus_network),
+        Arc::clone(&tracker),
+        Arc::clone(&state),
+        Arc::clone(&security),
+    ));
+    if let Some(keypair) = bls_key {
+        engine.set_bls_key(keypair).await;
+    }
+    // Restore the finality floor and replay any blocks the state store
+    // missed (e.g. after a crash mid-commit) before joining consensus.
+    let db_path = std::path::Path::new(&config.data_dir).join("db");
+    let store = match artha_fs::storage::open(&config.storage, &db_path) {
+        Ok(store) => store,
+        Err(err) => {
+            log::error!("cannot open storage backend at {}: {err}", db_path.display());
+            return Ok(());
+        }
+    };
+    engine.recover_from(Arc::clone(&store)).await;
+    restore_mempool(&store, &pool).await;
+    redial_address_book(&store, &connections).await;
+    network_security.attach_store(Arc::clone(&store)).await;
+    // Push committed chain events through the durable outbox to any
+    // configured indexer sink.
+    if let Some(indexer_config) = config.indexer.clone() {
+        let sink = Arc::new(artha_fs::indexer::IndexerSink::new(
+            Arc::clone(&store),
+            indexer_config,
+        ));
+        tokio::spawn(sink.run(engine.events.subscribe()));
+    }
+    #[cfg(feature = "postgres")]
+    if let Some(url) = config
+        .indexer
+        .as_ref()
+        .and_then(|indexer| indexer.postgres_url.clone())
+    {
+        match artha_fs::indexer::postgres::PostgresIndexer::connect(&url, Arc::clone(&engine)).await
+        {
+            Ok(indexer) => {
+                tokio::spawn(indexer.run(engine.events.subscribe()));
+            }
+            Err(err) => log::error!("cannot connect postgres indexer: {err}"),
+        }
+    }
+    let engine_task = tokio::spawn(Arc::clone(&engine).run());
+
+    // Dispatch inbound P2P messages: consensus traffic feeds the engine's
+    // lanes, gossiped blocks go to the fork-aware chain store, and light
+    // client sync requests are answered directly.
+    tokio::spawn({
+        let network = Arc::clone(&network);
+        let connections = Arc::clone(&connections);
+        let consensus_network = Arc::clone(&consensus_network);
+        let engine = Arc::clone(&engine);
+        async move {
+            while let Some((peer_id, message)) = network.recv_message().await {
+                match message {
+                    NetworkMessage::Consensus(message) => {
+                        consensus_network.deliver(message).await;
+                    }
+                    NetworkMessage::Block(block) => {
+                        engine.receive_block(block).await;
+                    }
+                    request @ (NetworkMessage::HeaderRequest { .. }
+                    | NetworkMessage::ValidatorSetRequest { .. }) => {
+                        if let Some(response) = light::respond(&engine, &request).await {
+                            if let Err(err) = connections.send_to(&peer_id, &response).await {
+                                log::debug!("failed to answer sync request from {peer_id}: {err}");
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    });
+
+    let api_state = web::Data::new(ApiState {
+        engine: Arc::clone(&engine),
+        pool: Arc::clone(&pool),
+        state: Arc::clone(&state),
+        network: Arc::clone(&network),
+        metrics: Arc::clone(&metrics),
+        tracker: Arc::clone(&tracker),
+        health: Arc::clone(&health),
+        multisig: Arc::new(artha_fs::security::state::MultisigCollector::new()),
+        keys_dir: std::path::Path::new(&config.data_dir).join("keys"),
+        connections: Arc::clone(&connections),
+        network_security: Arc::clone(&network_security),
+        admin_token: config.admin_token.clone(),
+        api_keys: config.api_keys.clone(),
+        faucet_amount: config.faucet_amount,
+    });
+    log::info!("api listening on {}", config.api_address);
+    let cors_origins = config.cors_allowed_origins.clone();
+    let server = HttpServer::new(move || {
+        App::new()
+            .app_data(api_state.clone())
+            .wrap(cors_policy(&cors_origins))
+            .wrap(middleware::from_fn(track_api_latency))
+            .configure(api::routes)
+    });
+    let result = match &config.tls {
+        Some(tls) => {
+            server
+                .bind_rustls_0_23(&config.api_address, rustls_config(tls)?)?
+                .run()
+                .await
+        }
+        None => server.bind(&config.api_address)?.run().await,
+    };
+    // The server future resolves on ctrl-c; wind the rest of the node
+    // down in order before reporting the server's exit status.
+    graceful_shutdown(&pool, &engine, &network, &connections, &store, engine_task).await;
+    result
+}
+
+/// State-column key holding the mempool snapshot written on shutdown.
+const MEMPOOL_SNAPSHOT_KEY: &[u8] = b"mempool";
+
+/// Re-admit transactions snapshotted by the previous shutdown, then
+/// drop the snapshot so a crash cannot replay it twice. Admission
+/// re-runs the usual checks, so anything mined in the meantime is
+/// simply rejected.
+async fn restore_mempool(store: &Arc<dyn KvStore>, pool: &Arc<TransactionPool>) {
+    let key = Column::State.key(MEMPOOL_SNAPSHOT_KEY);
+    let Ok(Some(bytes)) = store.get(&key) else {
+        return;
+    };
+    let Ok(txs) = serde_json::from_slice::<Vec<Transaction>>(&bytes) else {
+        log::warn!("discarding unreadable mempool snapshot");
+        let _ = store.delete(&key);
+        return;
+    };
+    let mut restored = 0usize;
+    for tx in txs {
+        if pool.add_transaction(tx).await.is_ok() {
+            restored += 1;
+        }
+    }
+    if restored > 0 {
+        log::info!("restored {restored} transactions from the mempool snapshot");
+    }
+    let _ = store.delete(&key);
+}
+
+/// Dial the peers persisted by the previous shutdown so a restarted
+/// node rejoins its old neighborhood without waiting for the seeds.
+async fn redial_address_book(store: &Arc<dyn KvStore>, connections: &Arc<ConnectionManager>) {
+    let Ok(entries) = store.scan_prefix(&Column::Peers.key(b"")) else {
+        return;
+    };
+    for (_, value) in entries {
+        let Ok(peer) = serde_json::from_slice::<PeerInfo>(&value) else {
+            continue;
+        };
+        if let Err(err) = connections.connect(&peer.address).await {
+            log::debug!("address book redial to {} failed: {err}", peer.address);
+        }
+    }
+}
+
+/// Wind the node down in dependency order: stop admitting work, stop
+/// the consensus loop, snapshot the mempool and address book, sync
+/// storage, and only then say goodbye to peers.
+async fn graceful_shutdown(
+    pool: &Arc<TransactionPool>,
+    engine: &Arc<ConsensusEngine>,
+    network: &Arc<NetworkManager>,
+    connections: &Arc<ConnectionManager>,
+    store: &Arc<dyn KvStore>,
+    engine_task: tokio::task::JoinHandle<()>,
+) {
+    log::info!("shutting down");
+    pool.seal();
+    engine.begin_shutdown();
+    // Let the consensus loop finish its current step; it checks the
+    // shutdown flag once per interval, so bound the wait.
+    if tokio::time::timeout(std::time::Duration::from_secs(5), engine_task)
+        .await
+        .is_err()
+    {
+        log::warn!("consensus loop did not stop within 5s, continuing shutdown");
+    }
+    let pending = pool.pending().await;
+    if !pending.is_empty() {
+        match serde_json::to_vec(&pending) {
+            Ok(bytes) => {
+                let key = Column::State.key(MEMPOOL_SNAPSHOT_KEY);
+                if let Err(err) = store.put(&key, &bytes) {
+                    log::warn!("cannot snapshot mempool: {err}");
+                } else {
+                    log::info!("snapshotted {} pending transactions", pending.len());
+                }
+            }
+            Err(err) => log::warn!("cannot serialize mempool snapshot: {err}"),
+        }
+    }
+    for peer in network.peers().await {
+        match serde_json::to_vec(&peer) {
+            Ok(bytes) => {
+                let key = Column::Peers.key(peer.id.as_bytes());
+                if let Err(err) = store.put(&key, &bytes) {
+                    log::warn!("cannot persist address book entry for {}: {err}", peer.id);
+                }
+            }
+            Err(err) => log::warn!("cannot serialize peer {}: {err}", peer.id),
+        }
+    }
+    if let Err(err) = store.flush() {
+        log::warn!("storage flush failed during shutdown: {err}");
+    }
+    connections.shutdown("node shutting down").await;
+    log::info!("shutdown complete");
+}
+
+/// The CORS middleware matching the configured origin list: off when
+/// empty, permissive for `"*"`, otherwise exactly the listed origins.
+fn cors_policy(origins: &[String]) -> actix_cors::Cors {
+    if origins.is_empty() {
+        return actix_cors::Cors::default();
+    }
+    if origins.iter().any(|origin| origin == "*") {
+        return actix_cors::Cors::permissive();
+    }
+    origins
+        .iter()
+        .fold(actix_cors::Cors::default(), |cors, origin| {
+            cors.allowed_origin(origin)
+        })
+        .allow_any_method()
+        .allow_any_header()
+        .max_age(3600)
+}
+
+/// Build the rustls server config from the configured PEM files.
+fn rustls_config(tls: &artha_fs::config::TlsConfig) -> std::io::Result<rustls::ServerConfig> {
+    let certs = rustls_pemfile::certs(&mut std::io::BufReader::new(std::fs::File::open(
+        &tls.cert_path,
+    )?))
+    .collect::<Result<Vec<_>, _>>()?;
+    let key = rustls_pemfile::private_key(&mut std::io::BufReader::new(std::fs::File::open(
+        &tls.key_path,
+    )?))?
+    .ok_or_else(|| {
+        std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("no private key found in {}", tls.key_path),
+        )
+    })?;
+    rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err.to_string()))
+}
+
+/// Record every API request's handling time into the metrics registry.
+async fn track_api_latency(
+    req: actix_web::dev::ServiceRequest,
+    next: middleware::Next<impl actix_web::body::MessageBody>,
+) -> Result<actix_web::dev::ServiceResponse<impl actix_web::body::MessageBody>, actix_web::Error> {
+    let start = std::time::Instant::now();
+    let response = next.call(req).await;
+    Metrics::handle().api_latency.observe(start.elapsed());
+    response
+}
@@ -0,0 +1,334 @@
+//! Node metrics in Prometheus exposition format.
+//!
+//! Modules record into a process-wide [`Metrics`] registry obtained via
+//! [`Metrics::handle`]; the API serves the encoded text from
+//! `/api/metrics` for scraping.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// Bucket bounds (seconds) for consensus round durations.
+const ROUND_BUCKETS: &[f64] = &[0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0];
+
+/// Bucket bounds (seconds) for API request latency.
+const API_BUCKETS: &[f64] = &[0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0];
+
+/// Bucket bounds (seconds) for individual consensus steps.
+const STEP_BUCKETS: &[f64] = &[0.01, 0.05, 0.1, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+/// Bucket bounds (round counts) for rounds needed per height.
+const ROUND_COUNT_BUCKETS: &[f64] = &[1.0, 2.0, 3.0, 4.0, 6.0, 10.0];
+
+/// Fixed-bucket histogram. Observations are durations; the running sum
+/// is kept in microseconds so every counter stays lock-free.
+pub struct Histogram {
+    bounds: &'static [f64],
+    buckets: Vec<AtomicU64>,
+    sum_micros: AtomicU64,
+    count: AtomicU64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Self {
+            bounds,
+            buckets: bounds.iter().map(|_| AtomicU64::new(0)).collect(),
+            sum_micros: AtomicU64::new(0),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn observe(&self, duration: Duration) {
+        self.observe_value(duration.as_secs_f64());
+    }
+
+    /// Record a raw value (seconds for timing series, plain counts for
+    /// count-valued series like rounds per height).
+    pub fn observe_value(&self, value: f64) {
+        // Buckets are cumulative in the exposition format, so an
+        // observation lands in every bucket whose bound covers it.
+        for (bucket, bound) in self.buckets.iter().zip(self.bounds) {
+            if value <= *bound {
+                bucket.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        self.sum_micros
+            .fetch_add((value * 1e6) as u64, Ordering::Relaxed);
+        self.count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn encode(&self, out: &mut String, name: &str, help: &str) {
+        use std::fmt::Write;
+        let _ = writeln!(out, "# HELP {name} {help}");
+        let _ = writeln!(out, "# TYPE {name} histogram");
+        for (bucket, bound) in self.buckets.iter().zip(self.bounds) {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{le=\"{bound}\"}} {}",
+                bucket.load(Ordering::Relaxed)
+            );
+        }
+        let count = self.count.load(Ordering::Relaxed);
+        let _ = writeln!(out, "{name}_bucket{{le=\"+Inf\"}} {count}");
+        let _ = writeln!(
+            out,
+            "{name}_sum {}",
+            self.sum_micros.load(Ordering::Relaxed) as f64 / 1e6
+        );
+        let _ = writeln!(out, "{name}_count {count}");
+    }
+}
+
+/// Process-wide metric registry: counters, gauges, and histograms
+/// recorded from consensus, networking, and the API.
+pub struct Metrics {
+    pub blocks_committed: AtomicU64,
+    pub transactions_processed: AtomicU64,
+    pub block_height: AtomicU64,
+    pub mempool_size: AtomicU64,
+    pub peer_count: AtomicU64,
+    pub bytes_sent: AtomicU64,
+    pub bytes_received: AtomicU64,
+    pub round_duration: Histogram,
+    pub api_latency: Histogram,
+    /// Per-step timings, recorded as each consensus step completes.
+    pub propose_duration: Histogram,
+    pub prevote_duration: Histogram,
+    pub precommit_duration: Histogram,
+    pub commit_duration: Histogram,
+    /// Rounds needed to commit each height (1 = no failed rounds).
+    pub rounds_per_height: Histogram,
+    /// Step timeouts fired, per step.
+    pub timeouts_propose: AtomicU64,
+    pub timeouts_prevote: AtomicU64,
+    pub timeouts_precommit: AtomicU64,
+    /// Heights the stall watchdog escalated past a wedged round.
+    pub stalled_rounds: AtomicU64,
+    /// Persistent peers with a live connection right now.
+    pub persistent_peers_connected: AtomicU64,
+    /// Failed dials to persistent peers.
+    pub persistent_dial_failures: AtomicU64,
+    /// Commit instant of the previous block, for round duration.
+    last_commit: Mutex<Option<Instant>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self {
+            blocks_committed: AtomicU64::new(0),
+            transactions_processed: AtomicU64::new(0),
+            block_height: AtomicU64::new(0),
+            mempool_size: AtomicU64::new(0),
+            peer_count: AtomicU64::new(0),
+            bytes_sent: AtomicU64::new(0),
+            bytes_received: AtomicU64::new(0),
+            round_duration: Histogram::new(ROUND_BUCKETS),
+            api_latency: Histogram::new(API_BUCKETS),
+            propose_duration: Histogram::new(STEP_BUCKETS),
+            prevote_duration: Histogram::new(STEP_BUCKETS),
+            precommit_duration: Histogram::new(STEP_BUCKETS),
+            commit_duration: Histogram::new(STEP_BUCKETS),
+            rounds_per_height: Histogram::new(ROUND_COUNT_BUCKETS),
+            timeouts_propose: AtomicU64::new(0),
+            timeouts_prevote: AtomicU64::new(0),
+            timeouts_precommit: AtomicU64::new(0),
+            stalled_rounds: AtomicU64::new(0),
+            persistent_peers_connected: AtomicU64::new(0),
+            persistent_dial_failures: AtomicU64::new(0),
+            last_commit: Mutex::new(None),
+        }
+    }
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared process-wide registry. Modules record through this
+    /// handle so instrumentation does not thread through constructors.
+    pub fn handle() -> Arc<Metrics> {
+        static GLOBAL: OnceLock<Arc<Metrics>> = OnceLock::new();
+        Arc::clone(GLOBAL.get_or_init(|| Arc::new(Metrics::new())))
+    }
+
+    /// Record one committed block and the wall time since the previous
+    /// commit (the effective round duration).
+    pub fn record_block(&self, height: u64, tx_count: u64) {
+        self.blocks_committed.fetch_add(1, Ordering::Relaxed);
+        self.transactions_processed
+            .fetch_add(tx_count, Ordering::Relaxed);
+        self.block_height.store(height, Ordering::Relaxed);
+        let mut last = self.last_commit.lock().unwrap();
+        if let Some(previous) = last.replace(Instant::now()) {
+            self.round_duration.observe(previous.elapsed());
+        }
+    }
+
+    pub fn add_bytes_sent(&self, bytes: u64) {
+        self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn add_bytes_received(&self, bytes: u64) {
+        self.bytes_received.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    pub fn set_mempool_size(&self, size: u64) {
+        self.mempool_size.store(size, Ordering::Relaxed);
+    }
+
+    pub fn set_peer_count(&self, count: u64) {
+        self.peer_count.store(count, Ordering::Relaxed);
+    }
+
+    pub fn set_persistent_peers_connected(&self, count: u64) {
+        self.persistent_peers_connected
+            .store(count, Ordering::Relaxed);
+    }
+
+    /// Encode the registry in the Prometheus text exposition format.
+    pub fn encode(&self) -> String {
+        use std::fmt::Write;
+        let mut out = String::new();
+        let counters: [(&str, &str, &AtomicU64); 6] = [
+            (
+                "artha_blocks_committed_total",
+                "Blocks committed by this node.",
+                &self.blocks_committed,
+            ),
+            (
+                "artha_transactions_processed_total",
+                "Transactions executed in committed blocks.",
+                &self.transactions_processed,
+            ),
+            (
+                "artha_p2p_bytes_sent_total",
+                "Bytes written to peer connections.",
+                &self.bytes_sent,
+            ),
+            (
+                "artha_p2p_bytes_received_total",
+                "Bytes read from peer connections.",
+                &self.bytes_received,
+            ),
+            (
+                "artha_consensus_stalled_rounds_total",
+                "Rounds the stall watchdog forcibly escalated.",
+                &self.stalled_rounds,
+            ),
+            (
+                "artha_p2p_persistent_dial_failures_total",
+                "Failed dials to configured persistent peers.",
+                &self.persistent_dial_failures,
+            ),
+        ];
+        for (name, help, value) in counters {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} counter");
+            let _ = writeln!(out, "{name} {}", value.load(Ordering::Relaxed));
+        }
+        let gauges: [(&str, &str, &AtomicU64); 4] = [
+            (
+                "artha_block_height",
+                "Latest committed block height.",
+                &self.block_height,
+            ),
+            (
+                "artha_mempool_size",
+                "Transactions waiting in the mempool.",
+                &self.mempool_size,
+            ),
+            (
+                "artha_peer_count",
+                "Connected peers.",
+                &self.peer_count,
+            ),
+            (
+                "artha_p2p_persistent_peers_connected",
+                "Configured persistent peers with a live connection.",
+                &self.persistent_peers_connected,
+            ),
+        ];
+        for (name, help, value) in gauges {
+            let _ = writeln!(out, "# HELP {name} {help}");
+            let _ = writeln!(out, "# TYPE {name} gauge");
+            let _ = writeln!(out, "{name} {}", value.load(Ordering::Relaxed));
+        }
+        let _ = writeln!(
+            out,
+            "# HELP artha_consensus_timeouts_total Consensus step timeouts fired."
+        );
+        let _ = writeln!(out, "# TYPE artha_consensus_timeouts_total counter");
+        for (step, value) in [
+            ("propose", &self.timeouts_propose),
+            ("prevote", &self.timeouts_prevote),
+            ("precommit", &self.timeouts_precommit),
+        ] {
+            let _ = writeln!(
+                out,
+                "artha_consensus_timeouts_total{{step=\"{step}\"}} {}",
+                value.load(Ordering::Relaxed)
+            );
+        }
+        self.round_duration.encode(
+            &mut out,
+            "artha_consensus_round_duration_seconds",
+            "Wall time between consecutive block commits.",
+        );
+        self.propose_duration.encode(
+            &mut out,
+            "artha_consensus_propose_duration_seconds",
+            "Time spent in the propose step.",
+        );
+        self.prevote_duration.encode(
+            &mut out,
+            "artha_consensus_prevote_duration_seconds",
+            "Time spent in the prevote step.",
+        );
+        self.precommit_duration.encode(
+            &mut out,
+            "artha_consensus_precommit_duration_seconds",
+            "Time spent in the precommit step.",
+        );
+        self.commit_duration.encode(
+            &mut out,
+            "artha_consensus_commit_duration_seconds",
+            "Time spent executing and persisting a committed block.",
+        );
+        self.rounds_per_height.encode(
+            &mut out,
+            "artha_consensus_rounds_per_height",
+            "Rounds needed to commit each height (1 = first round).",
+        );
+        self.api_latency.encode(
+            &mut out,
+            "artha_api_request_duration_seconds",
+            "API request handling latency.",
+        );
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exposition_includes_counters_gauges_and_histograms() {
+        let metrics = Metrics::new();
+        metrics.record_block(7, 3);
+        metrics.add_bytes_sent(128);
+        metrics.set_peer_count(4);
+        metrics.api_latency.observe(Duration::from_millis(2));
+        let text = metrics.encode();
+        assert!(text.contains("# TYPE artha_blocks_committed_total counter"));
+        assert!(text.contains("artha_block_height 7"));
+        assert!(text.contains("artha_transactions_processed_total 3"));
+        assert!(text.contains("artha_peer_count 4"));
+        // 2ms falls inside the 5ms bucket and everything above it.
+        assert!(text.contains("artha_api_request_duration_seconds_bucket{le=\"0.005\"} 1"));
+        assert!(text.contains("artha_api_request_duration_seconds_bucket{le=\"0.001\"} 0"));
+        assert!(text.contains("artha_api_request_duration_seconds_count 1"));
+    }
+}
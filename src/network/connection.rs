@@ -0,0 +1,512 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{watch, RwLock};
+
+use super::p2p::NodeIdentity;
+use super::proto::WireCodec;
+use super::queue::{LaneStats, MessageLanes, Priority};
+use super::{NetworkError, NetworkManager, NetworkMessage, PeerInfo};
+use crate::metrics::Metrics;
+use crate::security::network::NetworkSecurityManager;
+use crate::security::SecurityManager;
+use crate::types::transaction::now_unix;
+
+/// Maximum encoded message size accepted from the wire.
+const MAX_FRAME_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Capacity of each per-connection send lane.
+const SEND_QUEUE_CAPACITY: usize = 256;
+
+/// Whether we accepted a connection or dialed it ourselves. The two
+/// classes have separate limits, so inbound flooding cannot evict peers
+/// we chose to connect to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Inbound,
+    Outbound,
+}
+
+/// A live connection to one peer.
+///
+/// Outgoing messages are queued into per-priority lanes and written by a
+/// dedicated task, so consensus traffic preempts block and transaction
+/// frames instead of queueing behind them.
+pub struct Connection {
+    pub peer_id: String,
+    pub remote_address: String,
+    pub direction: Direction,
+    lanes: Arc<MessageLanes<NetworkMessage>>,
+    /// Dropping this stops the writer task.
+    _shutdown: watch::Sender<bool>,
+}
+
+impl Connection {
+    pub fn new(
+        peer_id: String,
+        remote_address: String,
+        direction: Direction,
+        mut writer: OwnedWriteHalf,
+        codec: WireCodec,
+    ) -> Self {
+        let lanes = Arc::new(MessageLanes::new(SEND_QUEUE_CAPACITY));
+        let (shutdown_tx, mut shutdown_rx) = watch::channel(false);
+        let writer_lanes = Arc::clone(&lanes);
+        let writer_peer = peer_id.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    message = writer_lanes.recv() => {
+                        let Some(message) = message else { break };
+                        if let Err(err) = write_frame(&mut writer, &message, codec).await {
+                            log::debug!("write to {writer_peer} failed: {err}");
+                            break;
+                        }
+                    }
+                    changed = shutdown_rx.changed() => {
+                        if changed.is_err() {
+                            break;
+                        }
+                    }
+                }
+            }
+        });
+        Self {
+            peer_id,
+            remote_address,
+            direction,
+            lanes,
+            _shutdown: shutdown_tx,
+        }
+    }
+
+    /// Queue a frame for sending. High-priority (consensus) messages apply
+    /// backpressure; normal messages are dropped (and counted) when the
+    /// lane is full so a slow peer can't stall the broadcast path.
+    pub async fn send(&self, message: &NetworkMessage) -> Result<(), NetworkError> {
+        match message.priority() {
+            Priority::High => self.lanes.send(Priority::High, message.clone()).await,
+            Priority::Normal => {
+                if !self.lanes.try_send(Priority::Normal, message.clone()) {
+                    log::trace!("send lane to {} full, dropping frame", self.peer_id);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop counters for this connection's send lanes.
+    pub fn send_stats(&self) -> LaneStats {
+        self.lanes.stats()
+    }
+}
+
+/// Write one length-prefixed frame to a stream half.
+pub async fn write_frame(
+    writer: &mut OwnedWriteHalf,
+    message: &NetworkMessage,
+    codec: WireCodec,
+) -> Result<(), NetworkError> {
+    let payload = codec.encode(message)?;
+    writer.write_all(&(payload.len() as u32).to_be_bytes()).await?;
+    writer.write_all(&payload).await?;
+    writer.flush().await?;
+    Metrics::handle().add_bytes_sent(4 + payload.len() as u64);
+    Ok(())
+}
+
+/// Read one length-prefixed frame from a stream half, returning the
+/// message and its encoded size.
+pub async fn read_frame(
+    reader: &mut OwnedReadHalf,
+    codec: WireCodec,
+) -> Result<(NetworkMessage, usize), NetworkError> {
+    let mut len_bytes = [0u8; 4];
+    reader.read_exact(&mut len_bytes).await?;
+    let len = u32::from_be_bytes(len_bytes);
+    if len > MAX_FRAME_SIZE {
+        return Err(NetworkError::Codec(format!("frame too large: {len}")));
+    }
+    let mut payload = vec![0u8; len as usize];
+    reader.read_exact(&mut payload).await?;
+    let message = codec.decode(&payload)?;
+    Metrics::handle().add_bytes_received(4 + len as u64);
+    Ok((message, len as usize))
+}
+
+/// Accepts inbound connections and pumps messages between peers and the
+/// network manager.
+pub struct ConnectionManager {
+    network: Arc<NetworkManager>,
+    security: Arc<NetworkSecurityManager>,
+    connections: Arc<RwLock<HashMap<String, Arc<Connection>>>>,
+    /// Identity key; its public half is our node id, and it answers
+    /// allowlist challenges.
+    identity: Arc<NodeIdentity>,
+}
+
+impl ConnectionManager {
+    pub fn new(
+        network: Arc<NetworkManager>,
+        security: Arc<NetworkSecurityManager>,
+        identity: Arc<NodeIdentity>,
+    ) -> Self {
+        Self {
+            network,
+            security,
+            connections: Arc::new(RwLock::new(HashMap::new())),
+            identity,
+        }
+    }
+
+    /// Bind the P2P listener and serve connections until shutdown.
+    pub async fn start(self: Arc<Self>) -> Result<(), NetworkError> {
+        let listener = TcpListener::bind(&self.network.config.listen_address).await?;
+        log::info!("p2p listening on {}", self.network.config.listen_address);
+
+        let broadcaster = Arc::clone(&self);
+        tokio::spawn(async move {
+            while let Some(message) = broadcaster.network.next_outbound().await {
+                broadcaster.broadcast(&message).await;
+            }
+        });
+
+        loop {
+            let (stream, addr) = listener.accept().await?;
+            if self.security.is_banned(&addr.ip().to_string()).await {
+                log::debug!("rejecting banned peer {addr}");
+                continue;
+            }
+            let manager = Arc::clone(&self);
+            tokio::spawn(async move {
+                if let Err(err) = manager
+                    .handle_connection(stream, addr.to_string(), Direction::Inbound)
+                    .await
+                {
+                    log::debug!("connection from {addr} ended: {err}");
+                }
+            });
+        }
+    }
+
+    /// Dial a remote peer and start exchanging messages. Fails without
+    /// dialing when the outbound limit is already reached.
+    pub async fn connect(self: &Arc<Self>, address: &str) -> Result<(), NetworkError> {
+        let max_outbound = self.network.config.max_outbound_peers;
+        if self.connection_count_in(Direction::Outbound).await >= max_outbound {
+            return Err(NetworkError::LimitReached(format!(
+                "already at {max_outbound} outbound peers"
+            )));
+        }
+        let stream = TcpStream::connect(address).await?;
+        let manager = Arc::clone(self);
+        let address = address.to_string();
+        tokio::spawn(async move {
+            if let Err(err) = manager
+                .handle_connection(stream, address.clone(), Direction::Outbound)
+                .await
+            {
+                log::debug!("connection to {address} ended: {err}");
+            }
+        });
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "peer_connection", skip_all, fields(peer = %remote_address))]
+    async fn handle_connection(
+        self: &Arc<Self>,
+        stream: TcpStream,
+        remote_address: String,
+        direction: Direction,
+    ) -> Result<(), NetworkError> {
+        let (mut reader, mut writer) = stream.into_split();
+
+        // Exchange handshakes: send ours, then expect theirs. Handshake
+        // frames are always bincode; the advertised codecs only govern
+        // the frames that follow.
+        let ours = NetworkMessage::Handshake {
+            network_id: self.network.config.network_id.clone(),
+            genesis_hash: self.network.genesis_hash.clone(),
+            node_id: self.identity.node_id(),
+            listen_address: self.network.config.listen_address.clone(),
+            height: 0,
+            codecs: super::proto::SUPPORTED_CODECS
+                .iter()
+                .map(|c| c.to_string())
+                .collect(),
+        };
+        write_frame(&mut writer, &ours, WireCodec::Bincode).await?;
+
+        let (peer_id, codec) = match read_frame(&mut reader, WireCodec::Bincode).await?.0 {
+            NetworkMessage::Handshake {
+                network_id,
+                genesis_hash,
+                node_id,
+                codecs,
+                ..
+            } => {
+                if network_id != self.network.config.network_id {
+                    return Err(NetworkError::Handshake(format!(
+                        "network_id mismatch: {network_id}"
+                    )));
+                }
+                // A matching network_id string is not enough: refuse peers
+                // initialized from a different genesis.
+                if genesis_hash != self.network.genesis_hash {
+                    return Err(NetworkError::Handshake(format!(
+                        "genesis hash mismatch: {genesis_hash}"
+                    )));
+                }
+                (
+                    node_id,
+                    WireCodec::negotiate(super::proto::SUPPORTED_CODECS, &codecs),
+                )
+            }
+            _ => return Err(NetworkError::Handshake("expected handshake".into())),
+        };
+        log::debug!("negotiated {codec:?} with {peer_id}");
+
+        if !self.network.config.allowed_peers.is_empty() {
+            self.authenticate_peer(&mut reader, &mut writer, &peer_id, codec)
+                .await?;
+        }
+
+        let connection = Arc::new(Connection::new(
+            peer_id.clone(),
+            remote_address.clone(),
+            direction,
+            writer,
+            codec,
+        ));
+        self.connections
+            .write()
+            .await
+            .insert(peer_id.clone(), Arc::clone(&connection));
+        self.network
+            .add_peer(PeerInfo {
+                id: peer_id.clone(),
+                address: remote_address.clone(),
+                last_seen: now_unix(),
+                height: 0,
+            })
+            .await;
+        self.security.observe_peer(&remote_address).await;
+        self.evict_if_over_limit().await;
+
+        let result = self
+            .read_loop(&mut reader, &peer_id, &remote_address, codec)
+            .await;
+        self.connections.write().await.remove(&peer_id);
+        self.network.remove_peer(&peer_id).await;
+        result
+    }
+
+    /// Private-network gate: refuse peers whose node id is not in the
+    /// configured allowlist, and make the rest prove possession of the
+    /// key behind it by signing a fresh nonce. Every member of a private
+    /// deployment runs this exchange, so each side sends its own
+    /// challenge before answering the peer's.
+    async fn authenticate_peer(
+        &self,
+        reader: &mut OwnedReadHalf,
+        writer: &mut OwnedWriteHalf,
+        peer_id: &str,
+        codec: WireCodec,
+    ) -> Result<(), NetworkError> {
+        let allowed = &self.network.config.allowed_peers;
+        if !allowed.iter().any(|key| key.eq_ignore_ascii_case(peer_id)) {
+            return Err(NetworkError::Handshake(format!(
+                "peer {peer_id} not in allowlist"
+            )));
+        }
+        let public_key = hex::decode(peer_id)
+            .map_err(|_| NetworkError::Handshake("node id is not a hex public key".into()))?;
+        let mut nonce = vec![0u8; 32];
+        OsRng.fill_bytes(&mut nonce);
+        write_frame(
+            writer,
+            &NetworkMessage::AuthChallenge {
+                nonce: nonce.clone(),
+            },
+            codec,
+        )
+        .await?;
+        let theirs = match read_frame(reader, codec).await?.0 {
+            NetworkMessage::AuthChallenge { nonce } => nonce,
+            _ => return Err(NetworkError::Handshake("expected auth challenge".into())),
+        };
+        write_frame(
+            writer,
+            &NetworkMessage::AuthResponse {
+                signature: self.identity.sign(&theirs),
+            },
+            codec,
+        )
+        .await?;
+        let signature = match read_frame(reader, codec).await?.0 {
+            NetworkMessage::AuthResponse { signature } => signature,
+            _ => return Err(NetworkError::Handshake("expected auth response".into())),
+        };
+        if !SecurityManager::verify(&public_key, &nonce, &signature) {
+            return Err(NetworkError::Handshake(format!(
+                "peer {peer_id} failed the allowlist challenge"
+            )));
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(name = "peer_read_loop", skip_all, fields(peer_id = %peer_id))]
+    async fn read_loop(
+        self: &Arc<Self>,
+        reader: &mut OwnedReadHalf,
+        peer_id: &str,
+        remote_address: &str,
+        codec: WireCodec,
+    ) -> Result<(), NetworkError> {
+        loop {
+            let (message, size) = read_frame(reader, codec).await?;
+            if !self.security.check_rate(remote_address, size).await {
+                log::debug!("peer {peer_id} exceeded rate limit, dropping frame");
+                self.security.record_violation(remote_address).await;
+                continue;
+            }
+            self.network.touch_peer(peer_id).await;
+            match message {
+                NetworkMessage::Ping(nonce) => {
+                    if let Some(conn) = self.connections.read().await.get(peer_id) {
+                        conn.send(&NetworkMessage::Pong(nonce)).await?;
+                    }
+                }
+                NetworkMessage::Pong(_) => {}
+                NetworkMessage::Goodbye { reason } => {
+                    log::info!("peer {peer_id} said goodbye: {reason}");
+                    return Ok(());
+                }
+                other => {
+                    self.network.deliver(peer_id.to_string(), other).await;
+                }
+            }
+        }
+    }
+
+    /// Send a message to one connected peer, e.g. a sync response.
+    pub async fn send_to(&self, peer_id: &str, message: &NetworkMessage) -> Result<(), NetworkError> {
+        let connection = self
+            .connections
+            .read()
+            .await
+            .get(peer_id)
+            .cloned()
+            .ok_or_else(|| NetworkError::PeerNotFound(peer_id.to_string()))?;
+        connection.send(message).await
+    }
+
+    /// Connections in one direction, by peer id.
+    async fn connection_count_in(&self, direction: Direction) -> usize {
+        self.connections
+            .read()
+            .await
+            .values()
+            .filter(|connection| connection.direction == direction)
+            .count()
+    }
+
+    /// Enforce the connection limits after a new peer lands. Each
+    /// direction has its own cap, so an attacker flooding the inbound
+    /// slots can only compete with other inbound peers: the
+    /// lowest-reputation peer in the overflowing class is dropped,
+    /// which may be the newcomer itself when it scores worst.
+    async fn evict_if_over_limit(&self) {
+        let config = &self.network.config;
+        let (mut inbound, mut outbound) = (Vec::new(), Vec::new());
+        for connection in self.connections.read().await.values() {
+            match connection.direction {
+                Direction::Inbound => inbound.push(connection.peer_id.clone()),
+                Direction::Outbound => outbound.push(connection.peer_id.clone()),
+            }
+        }
+        let mut victims = Vec::new();
+        if inbound.len() > config.max_inbound_peers {
+            victims.extend(self.network.reputation.worst_peer(&inbound).await);
+        }
+        if outbound.len() > config.max_outbound_peers {
+            victims.extend(self.network.reputation.worst_peer(&outbound).await);
+        }
+        // The overall cap still applies when both classes are under
+        // their own limits.
+        if victims.is_empty() && inbound.len() + outbound.len() > config.max_peers {
+            let all: Vec<String> = inbound.into_iter().chain(outbound).collect();
+            victims.extend(self.network.reputation.worst_peer(&all).await);
+        }
+        for victim in victims {
+            log::info!("evicting lowest-reputation peer {victim}");
+            self.disconnect(&victim).await;
+        }
+    }
+
+    /// Fan a message out to the best-reputation peers (all peers when the
+    /// connection count is within the fanout).
+    pub async fn broadcast(&self, message: &NetworkMessage) {
+        const GOSSIP_FANOUT: usize = 16;
+        let connections = self.connections.read().await;
+        let targets: Vec<Arc<Connection>> = if connections.len() <= GOSSIP_FANOUT {
+            connections.values().cloned().collect()
+        } else {
+            let candidates: Vec<String> = connections.keys().cloned().collect();
+            self.network
+                .reputation
+                .best_peers(&candidates, GOSSIP_FANOUT)
+                .await
+                .iter()
+                .filter_map(|id| connections.get(id).cloned())
+                .collect()
+        };
+        for connection in targets {
+            if let Err(err) = connection.send(message).await {
+                log::debug!("failed to send to {}: {err}", connection.peer_id);
+            }
+        }
+    }
+
+    /// Drop the connection to one peer, e.g. an admin removal. Returns
+    /// false when no such peer is connected.
+    pub async fn disconnect(&self, peer_id: &str) -> bool {
+        let removed = self.connections.write().await.remove(peer_id).is_some();
+        if removed {
+            self.network.remove_peer(peer_id).await;
+        }
+        removed
+    }
+
+    pub async fn connection_count(&self) -> usize {
+        self.connections.read().await.len()
+    }
+
+    /// Whether any live connection's remote address is `address`, e.g.
+    /// to skip redialing a persistent peer that is already up.
+    pub async fn is_connected_to(&self, address: &str) -> bool {
+        self.connections
+            .read()
+            .await
+            .values()
+            .any(|connection| connection.remote_address == address)
+    }
+
+    /// Close every connection politely: send a goodbye so peers treat
+    /// the drop as intentional, then tear the connections down.
+    pub async fn shutdown(&self, reason: &str) {
+        self.broadcast(&NetworkMessage::Goodbye {
+            reason: reason.to_string(),
+        })
+        .await;
+        let peer_ids: Vec<String> = self.connections.read().await.keys().cloned().collect();
+        for peer_id in peer_ids {
+            self.disconnect(&peer_id).await;
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use rand::rngs::OsRng;
+use rand::RngCore;
+use tokio::time::Instant;
+
+use super::connection::ConnectionManager;
+use super::NetworkManager;
+use crate::metrics::Metrics;
+
+/// How often the dialer checks persistent-peer connectivity.
+const CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// First retry delay after a dial failure.
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Retry delays stop doubling here.
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Per-peer retry bookkeeping.
+struct DialState {
+    backoff: Duration,
+    /// Do not retry before this instant.
+    next_attempt: Instant,
+}
+
+/// Keeps connections to the configured persistent peers (sentries,
+/// fellow validators) alive: a dropped one is redialed with jittered
+/// exponential backoff until it comes back, with no operator action.
+pub struct PersistentDialer {
+    network: Arc<NetworkManager>,
+    connections: Arc<ConnectionManager>,
+}
+
+impl PersistentDialer {
+    pub fn new(network: Arc<NetworkManager>, connections: Arc<ConnectionManager>) -> Self {
+        Self {
+            network,
+            connections,
+        }
+    }
+
+    /// Check connectivity on an interval, redialing peers whose backoff
+    /// has elapsed. Returns immediately when no persistent peers are
+    /// configured.
+    pub async fn run(self: Arc<Self>) {
+        let peers = self.network.config.persistent_peers.clone();
+        if peers.is_empty() {
+            return;
+        }
+        let metrics = Metrics::handle();
+        let mut states: HashMap<&str, DialState> = peers
+            .iter()
+            .map(|address| {
+                (
+                    address.as_str(),
+                    DialState {
+                        backoff: INITIAL_BACKOFF,
+                        next_attempt: Instant::now(),
+                    },
+                )
+            })
+            .collect();
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let mut connected = 0u64;
+            for address in &peers {
+                let state = states
+                    .get_mut(address.as_str())
+                    .expect("every persistent peer has a dial state");
+                if self.connections.is_connected_to(address).await {
+                    connected += 1;
+                    state.backoff = INITIAL_BACKOFF;
+                    continue;
+                }
+                if Instant::now() < state.next_attempt {
+                    continue;
+                }
+                if let Err(err) = self.connections.connect(address).await {
+                    metrics
+                        .persistent_dial_failures
+                        .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                    log::debug!("dial to persistent peer {address} failed: {err}");
+                }
+                // Back off even after a successful dial: the handshake
+                // may still fail, and a live connection resets the
+                // delay on the next tick anyway.
+                state.next_attempt = Instant::now() + jittered(state.backoff);
+                state.backoff = (state.backoff * 2).min(MAX_BACKOFF);
+            }
+            metrics.set_persistent_peers_connected(connected);
+        }
+    }
+}
+
+/// The delay plus up to half of itself again, so nodes restarting
+/// together do not synchronize their retries.
+fn jittered(backoff: Duration) -> Duration {
+    let half_millis = backoff.as_millis() as u64 / 2;
+    backoff + Duration::from_millis(OsRng.next_u64() % (half_millis + 1))
+}
@@ -0,0 +1,130 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Serialize;
+use tokio::sync::RwLock;
+
+use super::connection::ConnectionManager;
+use super::NetworkManager;
+
+/// How often the monitor samples network health.
+const CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Below this score the monitor triggers recovery actions.
+const RECOVERY_THRESHOLD: f64 = 0.5;
+
+/// A snapshot of aggregate network health. `score` runs from 0.0
+/// (isolated) to 1.0 (fully healthy).
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthReport {
+    pub score: f64,
+    pub peer_count: usize,
+    pub min_peers: usize,
+    /// Inbound messages dropped since the previous sample.
+    pub backlog_drops: u64,
+    /// Fraction of recent dials that failed.
+    pub dial_failure_rate: f64,
+}
+
+/// Watches peer connectivity, queue backlog, and dial outcomes, and
+/// re-bootstraps from the seed nodes when the aggregate score degrades.
+pub struct HealthMonitor {
+    network: Arc<NetworkManager>,
+    connections: Arc<ConnectionManager>,
+    dial_attempts: AtomicU64,
+    dial_failures: AtomicU64,
+    /// Inbound drop counter at the previous sample, to compute deltas.
+    last_inbound_drops: AtomicU64,
+    latest: RwLock<HealthReport>,
+}
+
+impl HealthMonitor {
+    pub fn new(network: Arc<NetworkManager>, connections: Arc<ConnectionManager>) -> Self {
+        let min_peers = network.config.min_peers;
+        Self {
+            network,
+            connections,
+            dial_attempts: AtomicU64::new(0),
+            dial_failures: AtomicU64::new(0),
+            last_inbound_drops: AtomicU64::new(0),
+            latest: RwLock::new(HealthReport {
+                score: 1.0,
+                peer_count: 0,
+                min_peers,
+                backlog_drops: 0,
+                dial_failure_rate: 0.0,
+            }),
+        }
+    }
+
+    /// The most recent health report, for the status API and metrics.
+    pub async fn report(&self) -> HealthReport {
+        self.latest.read().await.clone()
+    }
+
+    /// Sample the current health and update the latest report.
+    async fn sample(&self) -> HealthReport {
+        let peer_count = self.network.peer_count().await;
+        let min_peers = self.network.config.min_peers.max(1);
+
+        let (inbound, _) = self.network.queue_stats();
+        let total_drops = inbound.dropped_total();
+        let backlog_drops =
+            total_drops.saturating_sub(self.last_inbound_drops.swap(total_drops, Ordering::Relaxed));
+
+        let attempts = self.dial_attempts.swap(0, Ordering::Relaxed);
+        let failures = self.dial_failures.swap(0, Ordering::Relaxed);
+        let dial_failure_rate = if attempts == 0 {
+            0.0
+        } else {
+            failures as f64 / attempts as f64
+        };
+
+        // Peer coverage dominates: with no peers nothing else matters.
+        let peer_score = (peer_count as f64 / min_peers as f64).min(1.0);
+        let backlog_score = if backlog_drops == 0 { 1.0 } else { 0.0 };
+        let dial_score = 1.0 - dial_failure_rate;
+        let score = 0.5 * peer_score + 0.25 * backlog_score + 0.25 * dial_score;
+
+        let report = HealthReport {
+            score,
+            peer_count,
+            min_peers: self.network.config.min_peers,
+            backlog_drops,
+            dial_failure_rate,
+        };
+        *self.latest.write().await = report.clone();
+        report
+    }
+
+    /// Re-bootstrap from the configured seed nodes, counting outcomes so
+    /// the next sample reflects dial health.
+    async fn recover(&self) {
+        for seed in &self.network.config.seed_nodes {
+            self.dial_attempts.fetch_add(1, Ordering::Relaxed);
+            if let Err(err) = self.connections.connect(seed).await {
+                self.dial_failures.fetch_add(1, Ordering::Relaxed);
+                log::debug!("re-bootstrap dial to {seed} failed: {err}");
+            }
+        }
+    }
+
+    /// Sample health on an interval and trigger recovery when degraded.
+    pub async fn run(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(CHECK_INTERVAL);
+        loop {
+            ticker.tick().await;
+            let report = self.sample().await;
+            if report.score < RECOVERY_THRESHOLD {
+                log::warn!(
+                    "network health degraded (score {:.2}, {} peers, {} drops), re-bootstrapping",
+                    report.score,
+                    report.peer_count,
+                    report.backlog_drops
+                );
+                self.recover().await;
+            }
+        }
+    }
+}
@@ -0,0 +1,206 @@
+//! Light client sync: follow the chain by fetching and verifying only
+//! signed headers and validator sets, without downloading or executing
+//! block bodies.
+
+use serde::{Deserialize, Serialize};
+
+use crate::consensus::tendermint::TendermintConsensus;
+use crate::consensus::{commit_signing_bytes, Commit, ValidatorSet};
+use crate::security::{bls, SecurityManager};
+use crate::types::block::BlockHeader;
+
+/// A block header plus the commit certificate that finalized it —
+/// everything a light client needs to verify one height.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedHeader {
+    pub header: BlockHeader,
+    pub commit: Commit,
+}
+
+/// Tracks a trusted chain head and verifies signed headers against the
+/// validator set fetched for each height.
+pub struct LightClient {
+    pub chain_id: String,
+    pub trusted_height: u64,
+    pub trusted_hash: Vec<u8>,
+    /// Validator set expected to have signed the next header.
+    pub validators: ValidatorSet,
+}
+
+impl LightClient {
+    /// Start from a trusted validator set (typically from genesis or a
+    /// recent social checkpoint).
+    pub fn new(chain_id: String, validators: ValidatorSet) -> Self {
+        Self {
+            chain_id,
+            trusted_height: 0,
+            trusted_hash: Vec::new(),
+            validators,
+        }
+    }
+
+    /// Install the validator set to verify the next header against,
+    /// e.g. after fetching it for a height past a set change.
+    pub fn update_validators(&mut self, validators: ValidatorSet) {
+        self.validators = validators;
+    }
+
+    /// Verify a signed header against the trusted validator set and, on
+    /// success, advance the trusted head to it.
+    pub fn verify_header(&mut self, signed: &SignedHeader) -> Result<(), String> {
+        let header = &signed.header;
+        if header.height <= self.trusted_height {
+            return Err(format!(
+                "header height {} not past trusted height {}",
+                header.height, self.trusted_height
+            ));
+        }
+        let block_hash = header.hash();
+        if signed.commit.height != header.height || signed.commit.block_hash != block_hash {
+            return Err("commit does not cover this header".into());
+        }
+        self.verify_commit(&signed.commit)?;
+        self.trusted_height = header.height;
+        self.trusted_hash = block_hash;
+        Ok(())
+    }
+
+    /// Check that the commit carries +2/3 of the trusted set's power,
+    /// via either the aggregated BLS path or per-vote signatures.
+    fn verify_commit(&self, commit: &Commit) -> Result<(), String> {
+        let total = self.validators.total_power();
+        let mut power = 0u64;
+        if !commit.bls_signature.is_empty() {
+            let mut keys = Vec::with_capacity(commit.bls_signers.len());
+            for address in &commit.bls_signers {
+                let validator = self
+                    .validators
+                    .get(address)
+                    .ok_or_else(|| format!("unknown signer {address}"))?;
+                if validator.bls_public_key.is_empty() {
+                    return Err(format!("{address} has no BLS key"));
+                }
+                keys.push(validator.bls_public_key.clone());
+                power += validator.voting_power;
+            }
+            let message = commit_signing_bytes(
+                &self.chain_id,
+                commit.height,
+                commit.round,
+                &commit.block_hash,
+            );
+            if !bls::verify_aggregate(&keys, &message, &commit.bls_signature) {
+                return Err("bad aggregated BLS signature".into());
+            }
+        } else {
+            for vote in &commit.votes {
+                let validator = self
+                    .validators
+                    .get(&vote.validator)
+                    .ok_or_else(|| format!("unknown signer {}", vote.validator))?;
+                if vote.block_hash != commit.block_hash {
+                    return Err("vote for wrong block".into());
+                }
+                if !SecurityManager::verify(
+                    &validator.public_key,
+                    &vote.signing_bytes(&self.chain_id),
+                    &vote.signature,
+                ) {
+                    return Err(format!("bad signature from {}", vote.validator));
+                }
+                power += validator.voting_power;
+            }
+        }
+        if !TendermintConsensus::has_two_thirds(power, total) {
+            return Err("insufficient voting power".into());
+        }
+        Ok(())
+    }
+}
+
+/// Answer a light client sync request from a full node's engine state.
+/// Returns `None` for messages that are not sync requests.
+pub async fn respond(
+    engine: &crate::consensus::ConsensusEngine,
+    message: &super::NetworkMessage,
+) -> Option<super::NetworkMessage> {
+    use super::NetworkMessage;
+    match message {
+        NetworkMessage::HeaderRequest { height } => Some(NetworkMessage::HeaderResponse {
+            height: *height,
+            header: engine.signed_header(*height).await.map(Box::new),
+        }),
+        NetworkMessage::ValidatorSetRequest { height } => {
+            Some(NetworkMessage::ValidatorSetResponse {
+                height: *height,
+                validators: engine.validator_set_at(*height).await,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::tendermint::{Vote, VoteType};
+    use crate::consensus::Validator;
+    use crate::types::Block;
+
+    #[test]
+    fn light_client_advances_on_valid_commit_and_rejects_forgeries() {
+        let chain_id = "artha-test";
+        let keys: Vec<SecurityManager> = (0..2).map(|_| SecurityManager::new()).collect();
+        let validators = ValidatorSet::new(
+            keys.iter()
+                .map(|key| Validator {
+                    address: key.address(),
+                    public_key: key.public_key(),
+                    bls_public_key: Vec::new(),
+                    voting_power: 1,
+                    proposer_priority: 0,
+                })
+                .collect(),
+        );
+        let mut client = LightClient::new(chain_id.into(), validators);
+
+        let block = Block::new(1, vec![0; 32], vec![0; 32], keys[0].address(), Vec::new());
+        let votes: Vec<Vote> = keys
+            .iter()
+            .map(|key| {
+                let mut vote = Vote::new(
+                    VoteType::Precommit,
+                    1,
+                    0,
+                    block.hash(),
+                    key.address(),
+                );
+                vote.signature = key.sign(&vote.signing_bytes(chain_id));
+                vote
+            })
+            .collect();
+        let signed = SignedHeader {
+            header: block.header.clone(),
+            commit: Commit {
+                height: 1,
+                round: 0,
+                block_hash: block.hash(),
+                votes,
+                bls_signers: Vec::new(),
+                bls_signature: Vec::new(),
+            },
+        };
+
+        // One vote alone lacks +2/3 power.
+        let mut minority = signed.clone();
+        minority.commit.votes.truncate(1);
+        assert!(client.verify_header(&minority).is_err());
+
+        client.verify_header(&signed).unwrap();
+        assert_eq!(client.trusted_height, 1);
+        assert_eq!(client.trusted_hash, block.hash());
+
+        // A replay of an already-trusted height is rejected.
+        assert!(client.verify_header(&signed).is_err());
+    }
+}
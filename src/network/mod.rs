@@ -0,0 +1,306 @@
+pub mod connection;
+pub mod dialer;
+pub mod health;
+pub mod light;
+pub mod p2p;
+pub mod proto;
+pub mod queue;
+pub mod rate_limit;
+pub mod reputation;
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::config::NetworkConfig;
+use crate::consensus::ConsensusMessage;
+use crate::types::transaction::now_unix;
+use crate::types::{Block, Transaction};
+use queue::{LaneStats, MessageLanes, Priority};
+use rate_limit::TokenBucket;
+use reputation::{ReputationTracker, ScoreInput};
+
+#[derive(Debug, Error)]
+pub enum NetworkError {
+    #[error("io error: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("peer not found: {0}")]
+    PeerNotFound(String),
+    #[error("handshake failed: {0}")]
+    Handshake(String),
+    #[error("codec error: {0}")]
+    Codec(String),
+    #[error("connection limit reached: {0}")]
+    LimitReached(String),
+    #[error("connection closed")]
+    ConnectionClosed,
+}
+
+/// Wire messages exchanged between nodes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum NetworkMessage {
+    Handshake {
+        network_id: String,
+        /// Hex hash of the chain's genesis; peers must match exactly.
+        genesis_hash: String,
+        node_id: String,
+        listen_address: String,
+        height: u64,
+        /// Codec names the sender can speak in addition to bincode;
+        /// post-handshake frames use the negotiated intersection.
+        #[serde(default)]
+        codecs: Vec<String>,
+    },
+    Block(Block),
+    Transaction(Transaction),
+    Consensus(ConsensusMessage),
+    /// Light client sync: request the signed header at a height.
+    HeaderRequest { height: u64 },
+    /// Response with the header and its commit, `None` when unknown.
+    HeaderResponse {
+        height: u64,
+        header: Option<Box<light::SignedHeader>>,
+    },
+    /// Light client sync: request the validator set in effect at a height.
+    ValidatorSetRequest { height: u64 },
+    /// Response with the validator set, `None` when unknown.
+    ValidatorSetResponse {
+        height: u64,
+        validators: Option<crate::consensus::ValidatorSet>,
+    },
+    Ping(u64),
+    Pong(u64),
+    /// Polite close: the sender is dropping the connection on purpose
+    /// (shutdown, restart), so the peer should not count it against us.
+    Goodbye { reason: String },
+    /// Allowlist mode: prove you hold the key behind your node id by
+    /// signing this nonce.
+    AuthChallenge { nonce: Vec<u8> },
+    /// Signature over the peer's challenge nonce with the identity key.
+    AuthResponse { signature: Vec<u8> },
+}
+
+/// Basic information about a connected peer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerInfo {
+    pub id: String,
+    pub address: String,
+    pub last_seen: u64,
+    pub height: u64,
+}
+
+impl NetworkMessage {
+    /// Hash used for duplicate suppression in the gossip path.
+    pub fn gossip_hash(&self) -> [u8; 32] {
+        let bytes = bincode::serialize(self).unwrap_or_default();
+        Sha256::digest(&bytes).into()
+    }
+
+    /// Scheduling class: consensus traffic preempts everything else.
+    pub fn priority(&self) -> Priority {
+        match self {
+            NetworkMessage::Consensus(_) => Priority::High,
+            _ => Priority::Normal,
+        }
+    }
+}
+
+/// Time-bounded cache of recently seen message hashes, used to suppress
+/// duplicate gossip so the same vote or transaction isn't re-processed and
+/// re-broadcast in a loop.
+pub struct SeenCache {
+    entries: RwLock<HashMap<[u8; 32], Instant>>,
+    ttl: Duration,
+    max_entries: usize,
+}
+
+impl SeenCache {
+    pub fn new(ttl: Duration, max_entries: usize) -> Self {
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            ttl,
+            max_entries,
+        }
+    }
+
+    /// Mark a hash as seen. Returns true if it was new, false if it was
+    /// already in the cache (and still fresh).
+    pub async fn insert_if_new(&self, hash: [u8; 32]) -> bool {
+        let now = Instant::now();
+        let mut entries = self.entries.write().await;
+        if let Some(seen_at) = entries.get(&hash) {
+            if now.duration_since(*seen_at) < self.ttl {
+                return false;
+            }
+        }
+        // Opportunistically drop expired entries when the cache is full.
+        if entries.len() >= self.max_entries {
+            let ttl = self.ttl;
+            entries.retain(|_, seen_at| now.duration_since(*seen_at) < ttl);
+            // Still full after pruning: drop the oldest entry.
+            if entries.len() >= self.max_entries {
+                if let Some(oldest) = entries
+                    .iter()
+                    .min_by_key(|(_, seen_at)| **seen_at)
+                    .map(|(hash, _)| *hash)
+                {
+                    entries.remove(&oldest);
+                }
+            }
+        }
+        entries.insert(hash, now);
+        true
+    }
+
+    pub async fn len(&self) -> usize {
+        self.entries.read().await.len()
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.entries.read().await.is_empty()
+    }
+}
+
+impl Default for SeenCache {
+    fn default() -> Self {
+        // A few minutes covers gossip round-trips without unbounded growth.
+        Self::new(Duration::from_secs(120), 100_000)
+    }
+}
+
+/// Default capacity of each message lane.
+const MESSAGE_QUEUE_CAPACITY: usize = 1024;
+
+/// Tracks peers and queues messages between the node and its connections.
+pub struct NetworkManager {
+    pub config: NetworkConfig,
+    /// Hex hash of the chain's genesis, advertised and checked at handshake.
+    pub genesis_hash: String,
+    peers: Arc<RwLock<HashMap<String, PeerInfo>>>,
+    /// Incoming (peer_id, message) pairs, consumed by the node.
+    inbound: MessageLanes<(String, NetworkMessage)>,
+    /// Outgoing messages, consumed by the connection manager for broadcast.
+    outbound: MessageLanes<NetworkMessage>,
+    /// Node-wide ingress rate limit across all peers.
+    pub rate_limit: TokenBucket,
+    /// Duplicate-suppression cache shared by the inbound and outbound paths.
+    pub seen: SeenCache,
+    /// Shared peer reputation subsystem.
+    pub reputation: Arc<ReputationTracker>,
+}
+
+impl NetworkManager {
+    pub fn new(
+        config: NetworkConfig,
+        genesis_hash: String,
+        reputation: Arc<ReputationTracker>,
+    ) -> Self {
+        Self {
+            config,
+            genesis_hash,
+            peers: Arc::new(RwLock::new(HashMap::new())),
+            inbound: MessageLanes::new(MESSAGE_QUEUE_CAPACITY),
+            outbound: MessageLanes::new(MESSAGE_QUEUE_CAPACITY),
+            rate_limit: TokenBucket::default(),
+            seen: SeenCache::default(),
+            reputation,
+        }
+    }
+
+    pub async fn add_peer(&self, info: PeerInfo) {
+        self.peers.write().await.insert(info.id.clone(), info);
+    }
+
+    pub async fn remove_peer(&self, id: &str) {
+        self.peers.write().await.remove(id);
+    }
+
+    pub async fn peers(&self) -> Vec<PeerInfo> {
+        self.peers.read().await.values().cloned().collect()
+    }
+
+    pub async fn peer_count(&self) -> usize {
+        self.peers.read().await.len()
+    }
+
+    pub async fn touch_peer(&self, id: &str) {
+        if let Some(peer) = self.peers.write().await.get_mut(id) {
+            peer.last_seen = now_unix();
+        }
+    }
+
+    /// Queue a message for broadcast to all connected peers, applying
+    /// backpressure when the lane is full. Messages we have already
+    /// gossiped recently are dropped.
+    pub async fn broadcast(&self, message: NetworkMessage) {
+        if !self.seen.insert_if_new(message.gossip_hash()).await {
+            return;
+        }
+        let priority = message.priority();
+        self.outbound.send(priority, message).await;
+    }
+
+    /// Receive the next message queued for broadcast.
+    pub async fn next_outbound(&self) -> Option<NetworkMessage> {
+        self.outbound.recv().await
+    }
+
+    /// Deliver an incoming message from a connection. Duplicates of
+    /// recently seen messages are dropped before they reach the node, and
+    /// the message is dropped (and counted) if the inbound lane is full.
+    pub async fn deliver(&self, peer_id: String, message: NetworkMessage) {
+        let size = bincode::serialized_size(&message).unwrap_or(0) as usize;
+        if !self.rate_limit.allow(size) {
+            log::debug!("node-wide ingress limit exceeded, dropping message from {peer_id}");
+            return;
+        }
+        if !self.seen.insert_if_new(message.gossip_hash()).await {
+            log::trace!("dropping duplicate message from {peer_id}");
+            return;
+        }
+        self.reputation.record(&peer_id, ScoreInput::ValidMessage).await;
+        let priority = message.priority();
+        if !self.inbound.try_send(priority, (peer_id, message)) {
+            log::warn!("inbound message lane full, dropping message");
+        }
+    }
+
+    /// Receive the next incoming message, waiting until one arrives.
+    pub async fn recv_message(&self) -> Option<(String, NetworkMessage)> {
+        self.inbound.recv().await
+    }
+
+    /// Drop counters for the inbound and outbound lanes.
+    pub fn queue_stats(&self) -> (LaneStats, LaneStats) {
+        (self.inbound.stats(), self.outbound.stats())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn seen_cache_suppresses_duplicates() {
+        let cache = SeenCache::new(Duration::from_secs(60), 10);
+        let hash = [7u8; 32];
+        assert!(cache.insert_if_new(hash).await);
+        assert!(!cache.insert_if_new(hash).await);
+        assert!(cache.insert_if_new([8u8; 32]).await);
+    }
+
+    #[tokio::test]
+    async fn seen_cache_readmits_after_ttl() {
+        let cache = SeenCache::new(Duration::from_millis(10), 10);
+        let hash = [7u8; 32];
+        assert!(cache.insert_if_new(hash).await);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(cache.insert_if_new(hash).await);
+    }
+}
@@ -0,0 +1,108 @@
+use std::path::Path;
+
+use ed25519_dalek::SigningKey;
+use rand::rngs::OsRng;
+use serde::{Deserialize, Serialize};
+
+/// Name of the identity file inside the node's data directory.
+const NODE_KEY_FILE: &str = "node_key.json";
+
+/// The node's network identity.
+///
+/// Generated once and persisted as `node_key.json` in the data
+/// directory, so the node id stays stable across restarts and peers'
+/// reputations and address books keep pointing at the same node.
+pub struct NodeIdentity {
+    signing_key: SigningKey,
+}
+
+/// On-disk form of the identity: the private key seed as hex, plus the
+/// derived node id for operator convenience. Only the seed is read back.
+#[derive(Serialize, Deserialize)]
+struct NodeKeyFile {
+    node_id: String,
+    private_key: String,
+}
+
+impl NodeIdentity {
+    pub fn generate() -> Self {
+        Self {
+            signing_key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self {
+            signing_key: SigningKey::from_bytes(bytes),
+        }
+    }
+
+    /// Load the identity from `node_key.json` under `dir`, generating
+    /// and persisting a fresh one on first launch.
+    pub fn load_or_generate(dir: &Path) -> std::io::Result<Self> {
+        let path = dir.join(NODE_KEY_FILE);
+        if let Ok(raw) = std::fs::read(&path) {
+            let file: NodeKeyFile = serde_json::from_slice(&raw).map_err(|err| {
+                std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!("corrupt {}: {err}", path.display()),
+                )
+            })?;
+            let seed: [u8; 32] = hex::decode(&file.private_key)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidData,
+                        format!("corrupt {}: bad private key", path.display()),
+                    )
+                })?;
+            return Ok(Self::from_bytes(&seed));
+        }
+        let identity = Self::generate();
+        std::fs::create_dir_all(dir)?;
+        let file = NodeKeyFile {
+            node_id: identity.node_id(),
+            private_key: hex::encode(identity.signing_key.to_bytes()),
+        };
+        std::fs::write(&path, serde_json::to_vec_pretty(&file).unwrap_or_default())?;
+        Ok(identity)
+    }
+
+    /// Node id: hex of the identity public key.
+    pub fn node_id(&self) -> String {
+        hex::encode(self.signing_key.verifying_key().to_bytes())
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.signing_key.verifying_key().to_bytes().to_vec()
+    }
+
+    /// Sign `message` with the identity key, e.g. to answer an
+    /// allowlist handshake challenge.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        use ed25519_dalek::Signer;
+        self.signing_key.sign(message).to_bytes().to_vec()
+    }
+}
+
+impl Default for NodeIdentity {
+    fn default() -> Self {
+        Self::generate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_survives_restart() {
+        let dir = std::env::temp_dir().join(format!("artha-node-key-{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        let first = NodeIdentity::load_or_generate(&dir).unwrap();
+        let second = NodeIdentity::load_or_generate(&dir).unwrap();
+        assert_eq!(first.node_id(), second.node_id());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,797 @@
+//! Protobuf wire format, mirroring `proto/wire.proto`.
+//!
+//! The message types here are hand-written prost mirrors of the proto
+//! schema (the build does not shell out to protoc), with lossless
+//! conversions to and from the native types. Bincode remains the
+//! default framing; peers that both advertise `"protobuf"` in their
+//! handshake switch to this encoding for every later frame, which gives
+//! other implementations a stable, forward-compatible wire contract.
+
+use prost::Message;
+
+use crate::consensus::evidence::DuplicateVoteEvidence;
+use crate::consensus::tendermint::VoteType;
+use crate::network::light;
+use crate::network::{NetworkError, NetworkMessage};
+
+/// Codec names we can speak in addition to bincode, advertised in the
+/// handshake.
+pub const SUPPORTED_CODECS: &[&str] = &["protobuf"];
+
+/// Encoding used for post-handshake frames on one connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WireCodec {
+    /// The legacy encoding, and the one every handshake frame uses.
+    #[default]
+    Bincode,
+    Protobuf,
+}
+
+impl WireCodec {
+    /// Pick the codec for a connection from both sides' advertised
+    /// lists. Bincode is the floor: it wins unless both peers speak
+    /// protobuf.
+    pub fn negotiate(ours: &[&str], theirs: &[String]) -> Self {
+        if ours.contains(&"protobuf") && theirs.iter().any(|c| c == "protobuf") {
+            WireCodec::Protobuf
+        } else {
+            WireCodec::Bincode
+        }
+    }
+
+    pub fn encode(&self, message: &NetworkMessage) -> Result<Vec<u8>, NetworkError> {
+        match self {
+            WireCodec::Bincode => {
+                bincode::serialize(message).map_err(|e| NetworkError::Codec(e.to_string()))
+            }
+            WireCodec::Protobuf => Ok(pb::NetworkMessage::from(message).encode_to_vec()),
+        }
+    }
+
+    pub fn decode(&self, payload: &[u8]) -> Result<NetworkMessage, NetworkError> {
+        match self {
+            WireCodec::Bincode => {
+                bincode::deserialize(payload).map_err(|e| NetworkError::Codec(e.to_string()))
+            }
+            WireCodec::Protobuf => pb::NetworkMessage::decode(payload)
+                .map_err(|e| NetworkError::Codec(e.to_string()))?
+                .try_into()
+                .map_err(NetworkError::Codec),
+        }
+    }
+}
+
+/// Generated-style message types; field tags match `proto/wire.proto`.
+pub mod pb {
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Transaction {
+        #[prost(string, tag = "1")]
+        pub id: String,
+        #[prost(string, tag = "2")]
+        pub sender: String,
+        #[prost(string, tag = "3")]
+        pub recipient: String,
+        #[prost(uint64, tag = "4")]
+        pub amount: u64,
+        #[prost(string, tag = "5")]
+        pub denom: String,
+        #[prost(uint64, tag = "6")]
+        pub nonce: u64,
+        #[prost(uint64, tag = "7")]
+        pub gas_limit: u64,
+        #[prost(uint64, tag = "8")]
+        pub gas_price: u64,
+        #[prost(string, tag = "9")]
+        pub fee_denom: String,
+        #[prost(string, tag = "10")]
+        pub fee_payer: String,
+        #[prost(bytes = "vec", tag = "11")]
+        pub fee_payer_signature: Vec<u8>,
+        #[prost(bytes = "vec", tag = "12")]
+        pub data: Vec<u8>,
+        #[prost(enumeration = "SignatureScheme", tag = "13")]
+        pub scheme: i32,
+        #[prost(bytes = "vec", tag = "14")]
+        pub signature: Vec<u8>,
+        #[prost(bytes = "vec", repeated, tag = "15")]
+        pub signatures: Vec<Vec<u8>>,
+        #[prost(uint64, tag = "16")]
+        pub timestamp: u64,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum SignatureScheme {
+        Ed25519 = 0,
+        Secp256k1 = 1,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct BlockHeader {
+        #[prost(uint64, tag = "1")]
+        pub height: u64,
+        #[prost(uint64, tag = "2")]
+        pub timestamp: u64,
+        #[prost(bytes = "vec", tag = "3")]
+        pub prev_hash: Vec<u8>,
+        #[prost(bytes = "vec", tag = "4")]
+        pub state_root: Vec<u8>,
+        #[prost(bytes = "vec", tag = "5")]
+        pub tx_root: Vec<u8>,
+        #[prost(bytes = "vec", tag = "6")]
+        pub validator_hash: Vec<u8>,
+        #[prost(bytes = "vec", tag = "7")]
+        pub consensus_hash: Vec<u8>,
+        #[prost(bytes = "vec", tag = "8")]
+        pub evidence_root: Vec<u8>,
+        #[prost(string, tag = "9")]
+        pub proposer: String,
+        #[prost(bytes = "vec", tag = "10")]
+        pub last_commit_hash: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Block {
+        #[prost(message, optional, tag = "1")]
+        pub header: Option<BlockHeader>,
+        #[prost(message, repeated, tag = "2")]
+        pub transactions: Vec<Transaction>,
+        #[prost(message, repeated, tag = "3")]
+        pub evidence: Vec<Evidence>,
+        #[prost(message, optional, boxed, tag = "4")]
+        pub last_commit: Option<Box<Commit>>,
+    }
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, prost::Enumeration)]
+    #[repr(i32)]
+    pub enum VoteType {
+        Prevote = 0,
+        Precommit = 1,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Vote {
+        #[prost(enumeration = "VoteType", tag = "1")]
+        pub vote_type: i32,
+        #[prost(uint64, tag = "2")]
+        pub height: u64,
+        #[prost(uint32, tag = "3")]
+        pub round: u32,
+        #[prost(bytes = "vec", tag = "4")]
+        pub block_hash: Vec<u8>,
+        #[prost(string, tag = "5")]
+        pub validator: String,
+        #[prost(bytes = "vec", tag = "6")]
+        pub signature: Vec<u8>,
+        #[prost(uint64, tag = "7")]
+        pub timestamp: u64,
+        #[prost(bytes = "vec", tag = "8")]
+        pub bls_signature: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Commit {
+        #[prost(uint64, tag = "1")]
+        pub height: u64,
+        #[prost(uint32, tag = "2")]
+        pub round: u32,
+        #[prost(bytes = "vec", tag = "3")]
+        pub block_hash: Vec<u8>,
+        #[prost(message, repeated, tag = "4")]
+        pub votes: Vec<Vote>,
+        #[prost(string, repeated, tag = "5")]
+        pub bls_signers: Vec<String>,
+        #[prost(bytes = "vec", tag = "6")]
+        pub bls_signature: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct DuplicateVoteEvidence {
+        #[prost(message, optional, tag = "1")]
+        pub vote_a: Option<Vote>,
+        #[prost(message, optional, tag = "2")]
+        pub vote_b: Option<Vote>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Evidence {
+        #[prost(oneof = "evidence::Sum", tags = "1")]
+        pub sum: Option<evidence::Sum>,
+    }
+
+    pub mod evidence {
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum Sum {
+            #[prost(message, tag = "1")]
+            DoubleSign(super::DuplicateVoteEvidence),
+        }
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Proposal {
+        #[prost(uint64, tag = "1")]
+        pub height: u64,
+        #[prost(uint32, tag = "2")]
+        pub round: u32,
+        #[prost(message, optional, tag = "3")]
+        pub block: Option<Block>,
+        #[prost(string, tag = "4")]
+        pub proposer: String,
+        #[prost(bytes = "vec", tag = "5")]
+        pub signature: Vec<u8>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ConsensusMessage {
+        #[prost(oneof = "consensus_message::Sum", tags = "1, 2, 3, 4")]
+        pub sum: Option<consensus_message::Sum>,
+    }
+
+    pub mod consensus_message {
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum Sum {
+            #[prost(message, tag = "1")]
+            Proposal(super::Proposal),
+            #[prost(message, tag = "2")]
+            Vote(super::Vote),
+            #[prost(message, tag = "3")]
+            Commit(super::Commit),
+            #[prost(message, tag = "4")]
+            Evidence(super::Evidence),
+        }
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Validator {
+        #[prost(string, tag = "1")]
+        pub address: String,
+        #[prost(bytes = "vec", tag = "2")]
+        pub public_key: Vec<u8>,
+        #[prost(bytes = "vec", tag = "3")]
+        pub bls_public_key: Vec<u8>,
+        #[prost(uint64, tag = "4")]
+        pub voting_power: u64,
+        #[prost(sint64, tag = "5")]
+        pub proposer_priority: i64,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ValidatorSet {
+        #[prost(message, repeated, tag = "1")]
+        pub validators: Vec<Validator>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct SignedHeader {
+        #[prost(message, optional, tag = "1")]
+        pub header: Option<BlockHeader>,
+        #[prost(message, optional, tag = "2")]
+        pub commit: Option<Commit>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct Handshake {
+        #[prost(string, tag = "1")]
+        pub network_id: String,
+        #[prost(string, tag = "2")]
+        pub genesis_hash: String,
+        #[prost(string, tag = "3")]
+        pub node_id: String,
+        #[prost(string, tag = "4")]
+        pub listen_address: String,
+        #[prost(uint64, tag = "5")]
+        pub height: u64,
+        #[prost(string, repeated, tag = "6")]
+        pub codecs: Vec<String>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct HeaderResponse {
+        #[prost(uint64, tag = "1")]
+        pub height: u64,
+        #[prost(message, optional, tag = "2")]
+        pub header: Option<SignedHeader>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct ValidatorSetResponse {
+        #[prost(uint64, tag = "1")]
+        pub height: u64,
+        #[prost(message, optional, tag = "2")]
+        pub validators: Option<ValidatorSet>,
+    }
+
+    #[derive(Clone, PartialEq, prost::Message)]
+    pub struct NetworkMessage {
+        #[prost(oneof = "network_message::Msg", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13")]
+        pub msg: Option<network_message::Msg>,
+    }
+
+    pub mod network_message {
+        #[derive(Clone, PartialEq, prost::Oneof)]
+        pub enum Msg {
+            #[prost(message, tag = "1")]
+            Handshake(super::Handshake),
+            #[prost(message, tag = "2")]
+            Block(super::Block),
+            #[prost(message, tag = "3")]
+            Transaction(super::Transaction),
+            #[prost(message, tag = "4")]
+            Consensus(super::ConsensusMessage),
+            #[prost(uint64, tag = "5")]
+            HeaderRequest(u64),
+            #[prost(message, tag = "6")]
+            HeaderResponse(super::HeaderResponse),
+            #[prost(uint64, tag = "7")]
+            ValidatorSetRequest(u64),
+            #[prost(message, tag = "8")]
+            ValidatorSetResponse(super::ValidatorSetResponse),
+            #[prost(uint64, tag = "9")]
+            Ping(u64),
+            #[prost(uint64, tag = "10")]
+            Pong(u64),
+            #[prost(string, tag = "11")]
+            Goodbye(String),
+            #[prost(bytes = "vec", tag = "12")]
+            AuthChallenge(Vec<u8>),
+            #[prost(bytes = "vec", tag = "13")]
+            AuthResponse(Vec<u8>),
+        }
+    }
+}
+
+impl From<&crate::types::Transaction> for pb::Transaction {
+    fn from(tx: &crate::types::Transaction) -> Self {
+        Self {
+            id: tx.id.clone(),
+            sender: tx.sender.clone(),
+            recipient: tx.recipient.clone(),
+            amount: tx.amount,
+            denom: tx.denom.clone(),
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            gas_price: tx.gas_price,
+            fee_denom: tx.fee_denom.clone(),
+            fee_payer: tx.fee_payer.clone(),
+            fee_payer_signature: tx.fee_payer_signature.clone(),
+            data: tx.data.clone(),
+            scheme: match tx.scheme {
+                crate::security::scheme::SignatureScheme::Ed25519 => {
+                    pb::SignatureScheme::Ed25519 as i32
+                }
+                crate::security::scheme::SignatureScheme::Secp256k1 => {
+                    pb::SignatureScheme::Secp256k1 as i32
+                }
+            },
+            signature: tx.signature.clone(),
+            signatures: tx.signatures.clone(),
+            timestamp: tx.timestamp,
+        }
+    }
+}
+
+impl From<pb::Transaction> for crate::types::Transaction {
+    fn from(tx: pb::Transaction) -> Self {
+        Self {
+            id: tx.id,
+            sender: tx.sender,
+            recipient: tx.recipient,
+            amount: tx.amount,
+            denom: tx.denom,
+            nonce: tx.nonce,
+            gas_limit: tx.gas_limit,
+            gas_price: tx.gas_price,
+            fee_denom: tx.fee_denom,
+            fee_payer: tx.fee_payer,
+            fee_payer_signature: tx.fee_payer_signature,
+            data: tx.data,
+            scheme: match pb::SignatureScheme::try_from(tx.scheme) {
+                Ok(pb::SignatureScheme::Secp256k1) => {
+                    crate::security::scheme::SignatureScheme::Secp256k1
+                }
+                _ => crate::security::scheme::SignatureScheme::Ed25519,
+            },
+            signature: tx.signature,
+            signatures: tx.signatures,
+            timestamp: tx.timestamp,
+        }
+    }
+}
+
+impl From<&crate::types::BlockHeader> for pb::BlockHeader {
+    fn from(header: &crate::types::BlockHeader) -> Self {
+        Self {
+            height: header.height,
+            timestamp: header.timestamp,
+            prev_hash: header.prev_hash.clone(),
+            state_root: header.state_root.clone(),
+            tx_root: header.tx_root.clone(),
+            validator_hash: header.validator_hash.clone(),
+            consensus_hash: header.consensus_hash.clone(),
+            evidence_root: header.evidence_root.clone(),
+            proposer: header.proposer.clone(),
+            last_commit_hash: header.last_commit_hash.clone(),
+        }
+    }
+}
+
+impl From<pb::BlockHeader> for crate::types::BlockHeader {
+    fn from(header: pb::BlockHeader) -> Self {
+        Self {
+            height: header.height,
+            timestamp: header.timestamp,
+            prev_hash: header.prev_hash,
+            state_root: header.state_root,
+            tx_root: header.tx_root,
+            validator_hash: header.validator_hash,
+            consensus_hash: header.consensus_hash,
+            evidence_root: header.evidence_root,
+            proposer: header.proposer,
+            last_commit_hash: header.last_commit_hash,
+        }
+    }
+}
+
+impl From<&crate::types::Block> for pb::Block {
+    fn from(block: &crate::types::Block) -> Self {
+        Self {
+            header: Some((&block.header).into()),
+            transactions: block.transactions.iter().map(Into::into).collect(),
+            evidence: block.evidence.iter().map(Into::into).collect(),
+            last_commit: block
+                .last_commit
+                .as_deref()
+                .map(|commit| Box::new(commit.into())),
+        }
+    }
+}
+
+impl TryFrom<pb::Block> for crate::types::Block {
+    type Error = String;
+
+    fn try_from(block: pb::Block) -> Result<Self, String> {
+        Ok(Self {
+            header: block.header.ok_or("block missing header")?.into(),
+            transactions: block.transactions.into_iter().map(Into::into).collect(),
+            evidence: block
+                .evidence
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            last_commit: block
+                .last_commit
+                .map(|commit| Box::new((*commit).into())),
+        })
+    }
+}
+
+impl From<&crate::consensus::tendermint::Vote> for pb::Vote {
+    fn from(vote: &crate::consensus::tendermint::Vote) -> Self {
+        Self {
+            vote_type: match vote.vote_type {
+                VoteType::Prevote => pb::VoteType::Prevote as i32,
+                VoteType::Precommit => pb::VoteType::Precommit as i32,
+            },
+            height: vote.height,
+            round: vote.round,
+            block_hash: vote.block_hash.clone(),
+            validator: vote.validator.clone(),
+            signature: vote.signature.clone(),
+            timestamp: vote.timestamp,
+            bls_signature: vote.bls_signature.clone(),
+        }
+    }
+}
+
+impl From<pb::Vote> for crate::consensus::tendermint::Vote {
+    fn from(vote: pb::Vote) -> Self {
+        Self {
+            vote_type: match pb::VoteType::try_from(vote.vote_type) {
+                Ok(pb::VoteType::Precommit) => VoteType::Precommit,
+                _ => VoteType::Prevote,
+            },
+            height: vote.height,
+            round: vote.round,
+            block_hash: vote.block_hash,
+            validator: vote.validator,
+            signature: vote.signature,
+            bls_signature: vote.bls_signature,
+            timestamp: vote.timestamp,
+        }
+    }
+}
+
+impl From<&crate::consensus::Commit> for pb::Commit {
+    fn from(commit: &crate::consensus::Commit) -> Self {
+        Self {
+            height: commit.height,
+            round: commit.round,
+            block_hash: commit.block_hash.clone(),
+            votes: commit.votes.iter().map(Into::into).collect(),
+            bls_signers: commit.bls_signers.clone(),
+            bls_signature: commit.bls_signature.clone(),
+        }
+    }
+}
+
+impl From<pb::Commit> for crate::consensus::Commit {
+    fn from(commit: pb::Commit) -> Self {
+        Self {
+            height: commit.height,
+            round: commit.round,
+            block_hash: commit.block_hash,
+            votes: commit.votes.into_iter().map(Into::into).collect(),
+            bls_signers: commit.bls_signers,
+            bls_signature: commit.bls_signature,
+        }
+    }
+}
+
+impl From<&crate::consensus::evidence::Evidence> for pb::Evidence {
+    fn from(evidence: &crate::consensus::evidence::Evidence) -> Self {
+        match evidence {
+            crate::consensus::evidence::Evidence::DoubleSign(ev) => Self {
+                sum: Some(pb::evidence::Sum::DoubleSign(pb::DuplicateVoteEvidence {
+                    vote_a: Some((&ev.vote_a).into()),
+                    vote_b: Some((&ev.vote_b).into()),
+                })),
+            },
+        }
+    }
+}
+
+impl TryFrom<pb::Evidence> for crate::consensus::evidence::Evidence {
+    type Error = String;
+
+    fn try_from(evidence: pb::Evidence) -> Result<Self, String> {
+        match evidence.sum.ok_or("evidence missing sum")? {
+            pb::evidence::Sum::DoubleSign(ev) => {
+                Ok(Self::DoubleSign(DuplicateVoteEvidence {
+                    vote_a: ev.vote_a.ok_or("evidence missing vote_a")?.into(),
+                    vote_b: ev.vote_b.ok_or("evidence missing vote_b")?.into(),
+                }))
+            }
+        }
+    }
+}
+
+impl From<&crate::consensus::ConsensusMessage> for pb::ConsensusMessage {
+    fn from(message: &crate::consensus::ConsensusMessage) -> Self {
+        use crate::consensus::ConsensusMessage as Cm;
+        let sum = match message {
+            Cm::Proposal {
+                height,
+                round,
+                block,
+                proposer,
+                signature,
+            } => pb::consensus_message::Sum::Proposal(pb::Proposal {
+                height: *height,
+                round: *round,
+                block: Some(block.into()),
+                proposer: proposer.clone(),
+                signature: signature.clone(),
+            }),
+            Cm::Vote(vote) => pb::consensus_message::Sum::Vote(vote.into()),
+            Cm::Commit(commit) => pb::consensus_message::Sum::Commit(commit.into()),
+            Cm::Evidence(evidence) => pb::consensus_message::Sum::Evidence(evidence.into()),
+        };
+        Self { sum: Some(sum) }
+    }
+}
+
+impl TryFrom<pb::ConsensusMessage> for crate::consensus::ConsensusMessage {
+    type Error = String;
+
+    fn try_from(message: pb::ConsensusMessage) -> Result<Self, String> {
+        Ok(match message.sum.ok_or("consensus message missing sum")? {
+            pb::consensus_message::Sum::Proposal(proposal) => Self::Proposal {
+                height: proposal.height,
+                round: proposal.round,
+                block: proposal.block.ok_or("proposal missing block")?.try_into()?,
+                proposer: proposal.proposer,
+                signature: proposal.signature,
+            },
+            pb::consensus_message::Sum::Vote(vote) => Self::Vote(vote.into()),
+            pb::consensus_message::Sum::Commit(commit) => Self::Commit(commit.into()),
+            pb::consensus_message::Sum::Evidence(evidence) => {
+                Self::Evidence(evidence.try_into()?)
+            }
+        })
+    }
+}
+
+impl From<&crate::consensus::Validator> for pb::Validator {
+    fn from(validator: &crate::consensus::Validator) -> Self {
+        Self {
+            address: validator.address.clone(),
+            public_key: validator.public_key.clone(),
+            bls_public_key: validator.bls_public_key.clone(),
+            voting_power: validator.voting_power,
+            proposer_priority: validator.proposer_priority,
+        }
+    }
+}
+
+impl From<pb::Validator> for crate::consensus::Validator {
+    fn from(validator: pb::Validator) -> Self {
+        Self {
+            address: validator.address,
+            public_key: validator.public_key,
+            bls_public_key: validator.bls_public_key,
+            voting_power: validator.voting_power,
+            proposer_priority: validator.proposer_priority,
+        }
+    }
+}
+
+impl From<&crate::consensus::ValidatorSet> for pb::ValidatorSet {
+    fn from(set: &crate::consensus::ValidatorSet) -> Self {
+        Self {
+            validators: set.validators.iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<pb::ValidatorSet> for crate::consensus::ValidatorSet {
+    fn from(set: pb::ValidatorSet) -> Self {
+        Self {
+            validators: set.validators.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<&light::SignedHeader> for pb::SignedHeader {
+    fn from(signed: &light::SignedHeader) -> Self {
+        Self {
+            header: Some((&signed.header).into()),
+            commit: Some((&signed.commit).into()),
+        }
+    }
+}
+
+impl TryFrom<pb::SignedHeader> for light::SignedHeader {
+    type Error = String;
+
+    fn try_from(signed: pb::SignedHeader) -> Result<Self, String> {
+        Ok(Self {
+            header: signed.header.ok_or("signed header missing header")?.into(),
+            commit: signed.commit.ok_or("signed header missing commit")?.into(),
+        })
+    }
+}
+
+impl From<&NetworkMessage> for pb::NetworkMessage {
+    fn from(message: &NetworkMessage) -> Self {
+        use pb::network_message::Msg;
+        let msg = match message {
+            NetworkMessage::Handshake {
+                network_id,
+                genesis_hash,
+                node_id,
+                listen_address,
+                height,
+                codecs,
+            } => Msg::Handshake(pb::Handshake {
+                network_id: network_id.clone(),
+                genesis_hash: genesis_hash.clone(),
+                node_id: node_id.clone(),
+                listen_address: listen_address.clone(),
+                height: *height,
+                codecs: codecs.clone(),
+            }),
+            NetworkMessage::Block(block) => Msg::Block(block.into()),
+            NetworkMessage::Transaction(tx) => Msg::Transaction(tx.into()),
+            NetworkMessage::Consensus(consensus) => Msg::Consensus(consensus.into()),
+            NetworkMessage::HeaderRequest { height } => Msg::HeaderRequest(*height),
+            NetworkMessage::HeaderResponse { height, header } => {
+                Msg::HeaderResponse(pb::HeaderResponse {
+                    height: *height,
+                    header: header.as_deref().map(Into::into),
+                })
+            }
+            NetworkMessage::ValidatorSetRequest { height } => Msg::ValidatorSetRequest(*height),
+            NetworkMessage::ValidatorSetResponse { height, validators } => {
+                Msg::ValidatorSetResponse(pb::ValidatorSetResponse {
+                    height: *height,
+                    validators: validators.as_ref().map(Into::into),
+                })
+            }
+            NetworkMessage::Ping(nonce) => Msg::Ping(*nonce),
+            NetworkMessage::Pong(nonce) => Msg::Pong(*nonce),
+            NetworkMessage::Goodbye { reason } => Msg::Goodbye(reason.clone()),
+            NetworkMessage::AuthChallenge { nonce } => Msg::AuthChallenge(nonce.clone()),
+            NetworkMessage::AuthResponse { signature } => Msg::AuthResponse(signature.clone()),
+        };
+        Self { msg: Some(msg) }
+    }
+}
+
+impl TryFrom<pb::NetworkMessage> for NetworkMessage {
+    type Error = String;
+
+    fn try_from(message: pb::NetworkMessage) -> Result<Self, String> {
+        use pb::network_message::Msg;
+        Ok(match message.msg.ok_or("network message missing msg")? {
+            Msg::Handshake(handshake) => Self::Handshake {
+                network_id: handshake.network_id,
+                genesis_hash: handshake.genesis_hash,
+                node_id: handshake.node_id,
+                listen_address: handshake.listen_address,
+                height: handshake.height,
+                codecs: handshake.codecs,
+            },
+            Msg::Block(block) => Self::Block(block.try_into()?),
+            Msg::Transaction(tx) => Self::Transaction(tx.into()),
+            Msg::Consensus(consensus) => Self::Consensus(consensus.try_into()?),
+            Msg::HeaderRequest(height) => Self::HeaderRequest { height },
+            Msg::HeaderResponse(response) => Self::HeaderResponse {
+                height: response.height,
+                header: response
+                    .header
+                    .map(|h| h.try_into().map(Box::new))
+                    .transpose()?,
+            },
+            Msg::ValidatorSetRequest(height) => Self::ValidatorSetRequest { height },
+            Msg::ValidatorSetResponse(response) => Self::ValidatorSetResponse {
+                height: response.height,
+                validators: response.validators.map(Into::into),
+            },
+            Msg::Ping(nonce) => Self::Ping(nonce),
+            Msg::Pong(nonce) => Self::Pong(nonce),
+            Msg::Goodbye(reason) => Self::Goodbye { reason },
+            Msg::AuthChallenge(nonce) => Self::AuthChallenge { nonce },
+            Msg::AuthResponse(signature) => Self::AuthResponse { signature },
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::consensus::ConsensusMessage;
+    use crate::types::{Block, Transaction};
+
+    #[test]
+    fn protobuf_round_trips_match_bincode_semantics() {
+        let mut tx = Transaction::new("alice".into(), "bob".into(), 10, 1, 21000, 1, vec![9]);
+        tx.id = "tx-1".into();
+        tx.signature = vec![5; 64];
+        let block = Block::new(3, vec![1; 32], vec![2; 32], "val0".into(), vec![tx.clone()]);
+        let mut vote = crate::consensus::tendermint::Vote::new(
+            crate::consensus::tendermint::VoteType::Precommit,
+            3,
+            0,
+            block.hash(),
+            "val0".into(),
+        );
+        vote.bls_signature = vec![6; 96];
+        for message in [
+            NetworkMessage::Transaction(tx),
+            NetworkMessage::Block(block.clone()),
+            NetworkMessage::Consensus(ConsensusMessage::Vote(vote)),
+            NetworkMessage::HeaderRequest { height: 3 },
+            NetworkMessage::Ping(7),
+            NetworkMessage::AuthChallenge { nonce: vec![8; 32] },
+            NetworkMessage::AuthResponse {
+                signature: vec![4; 64],
+            },
+        ] {
+            let bytes = WireCodec::Protobuf.encode(&message).unwrap();
+            let decoded = WireCodec::Protobuf.decode(&bytes).unwrap();
+            // Compare via the bincode encoding, which covers every field.
+            assert_eq!(
+                bincode::serialize(&decoded).unwrap(),
+                bincode::serialize(&message).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn codec_negotiation_requires_both_sides() {
+        let both = WireCodec::negotiate(SUPPORTED_CODECS, &["protobuf".to_string()]);
+        assert_eq!(both, WireCodec::Protobuf);
+        let legacy = WireCodec::negotiate(SUPPORTED_CODECS, &[]);
+        assert_eq!(legacy, WireCodec::Bincode);
+    }
+}
@@ -0,0 +1,121 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use tokio::sync::{mpsc, Mutex};
+
+/// Scheduling class for queued messages. High-priority traffic (consensus
+/// votes and proposals) is always drained before normal traffic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    High,
+    Normal,
+}
+
+/// Counters describing a set of lanes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LaneStats {
+    pub dropped_high: u64,
+    pub dropped_normal: u64,
+}
+
+impl LaneStats {
+    pub fn dropped_total(&self) -> u64 {
+        self.dropped_high + self.dropped_normal
+    }
+}
+
+/// Bounded per-priority message lanes backed by `tokio::mpsc` channels.
+///
+/// `try_send` applies drop-on-full semantics (with drop counters) for paths
+/// that must not block, while `send` applies backpressure to the caller.
+/// `recv` always drains the high lane first.
+pub struct MessageLanes<T> {
+    high_tx: mpsc::Sender<T>,
+    high_rx: Mutex<mpsc::Receiver<T>>,
+    normal_tx: mpsc::Sender<T>,
+    normal_rx: Mutex<mpsc::Receiver<T>>,
+    dropped_high: AtomicU64,
+    dropped_normal: AtomicU64,
+}
+
+impl<T> MessageLanes<T> {
+    pub fn new(capacity: usize) -> Self {
+        let (high_tx, high_rx) = mpsc::channel(capacity);
+        let (normal_tx, normal_rx) = mpsc::channel(capacity);
+        Self {
+            high_tx,
+            high_rx: Mutex::new(high_rx),
+            normal_tx,
+            normal_rx: Mutex::new(normal_rx),
+            dropped_high: AtomicU64::new(0),
+            dropped_normal: AtomicU64::new(0),
+        }
+    }
+
+    /// Queue an item without blocking. Returns false (and counts a drop)
+    /// if the lane is full.
+    pub fn try_send(&self, priority: Priority, item: T) -> bool {
+        let (tx, dropped) = match priority {
+            Priority::High => (&self.high_tx, &self.dropped_high),
+            Priority::Normal => (&self.normal_tx, &self.dropped_normal),
+        };
+        if tx.try_send(item).is_err() {
+            dropped.fetch_add(1, Ordering::Relaxed);
+            return false;
+        }
+        true
+    }
+
+    /// Queue an item, waiting for capacity (backpressure).
+    pub async fn send(&self, priority: Priority, item: T) {
+        let tx = match priority {
+            Priority::High => &self.high_tx,
+            Priority::Normal => &self.normal_tx,
+        };
+        // Only fails if the receiver was dropped, which we own.
+        let _ = tx.send(item).await;
+    }
+
+    /// Receive the next item, preferring the high-priority lane.
+    pub async fn recv(&self) -> Option<T> {
+        let mut high = self.high_rx.lock().await;
+        if let Ok(item) = high.try_recv() {
+            return Some(item);
+        }
+        let mut normal = self.normal_rx.lock().await;
+        tokio::select! {
+            biased;
+            item = high.recv() => item,
+            item = normal.recv() => item,
+        }
+    }
+
+    pub fn stats(&self) -> LaneStats {
+        LaneStats {
+            dropped_high: self.dropped_high.load(Ordering::Relaxed),
+            dropped_normal: self.dropped_normal.load(Ordering::Relaxed),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn high_lane_drains_first() {
+        let lanes = MessageLanes::new(8);
+        lanes.try_send(Priority::Normal, "tx");
+        lanes.try_send(Priority::High, "vote");
+        assert_eq!(lanes.recv().await, Some("vote"));
+        assert_eq!(lanes.recv().await, Some("tx"));
+    }
+
+    #[tokio::test]
+    async fn full_lane_drops_and_counts() {
+        let lanes = MessageLanes::new(1);
+        assert!(lanes.try_send(Priority::Normal, 1));
+        assert!(!lanes.try_send(Priority::Normal, 2));
+        assert_eq!(lanes.stats().dropped_normal, 1);
+        assert_eq!(lanes.stats().dropped_high, 0);
+    }
+}
@@ -0,0 +1,126 @@
+use std::sync::Mutex;
+use std::time::Instant;
+
+/// Settings for a [`TokenBucket`]: sustained rates plus burst allowances
+/// for both message count and bytes.
+#[derive(Debug, Clone, Copy)]
+pub struct TokenBucketConfig {
+    pub messages_per_sec: f64,
+    pub bytes_per_sec: f64,
+    pub message_burst: f64,
+    pub byte_burst: f64,
+}
+
+impl Default for TokenBucketConfig {
+    fn default() -> Self {
+        Self {
+            messages_per_sec: 200.0,
+            bytes_per_sec: 1024.0 * 1024.0,
+            message_burst: 400.0,
+            byte_burst: 2.0 * 1024.0 * 1024.0,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BucketState {
+    message_tokens: f64,
+    byte_tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token-bucket rate limiter tracking messages and bytes with burst
+/// capacity. Shared by the connection read path, the network manager's
+/// ingress, and the network security layer.
+#[derive(Debug)]
+pub struct TokenBucket {
+    config: TokenBucketConfig,
+    state: Mutex<BucketState>,
+}
+
+impl TokenBucket {
+    pub fn new(config: TokenBucketConfig) -> Self {
+        Self {
+            config,
+            state: Mutex::new(BucketState {
+                message_tokens: config.message_burst,
+                byte_tokens: config.byte_burst,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    /// Whether one message of `bytes` is allowed now; consumes tokens if so.
+    pub fn allow(&self, bytes: usize) -> bool {
+        self.allow_at(bytes, Instant::now())
+    }
+
+    fn allow_at(&self, bytes: usize, now: Instant) -> bool {
+        let mut state = self.state.lock().expect("rate limit lock poisoned");
+        let elapsed = now.duration_since(state.last_refill).as_secs_f64();
+        state.last_refill = now;
+        state.message_tokens = (state.message_tokens + elapsed * self.config.messages_per_sec)
+            .min(self.config.message_burst);
+        state.byte_tokens =
+            (state.byte_tokens + elapsed * self.config.bytes_per_sec).min(self.config.byte_burst);
+        if state.message_tokens < 1.0 || state.byte_tokens < bytes as f64 {
+            return false;
+        }
+        state.message_tokens -= 1.0;
+        state.byte_tokens -= bytes as f64;
+        true
+    }
+}
+
+impl Default for TokenBucket {
+    fn default() -> Self {
+        Self::new(TokenBucketConfig::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn burst_is_bounded() {
+        let bucket = TokenBucket::new(TokenBucketConfig {
+            messages_per_sec: 1.0,
+            bytes_per_sec: 1000.0,
+            message_burst: 2.0,
+            byte_burst: 1000.0,
+        });
+        let now = Instant::now();
+        assert!(bucket.allow_at(10, now));
+        assert!(bucket.allow_at(10, now));
+        assert!(!bucket.allow_at(10, now));
+    }
+
+    #[test]
+    fn tokens_refill_over_time() {
+        let bucket = TokenBucket::new(TokenBucketConfig {
+            messages_per_sec: 10.0,
+            bytes_per_sec: 1000.0,
+            message_burst: 1.0,
+            byte_burst: 1000.0,
+        });
+        let now = Instant::now();
+        assert!(bucket.allow_at(10, now));
+        assert!(!bucket.allow_at(10, now));
+        assert!(bucket.allow_at(10, now + Duration::from_millis(200)));
+    }
+
+    #[test]
+    fn byte_budget_is_enforced() {
+        let bucket = TokenBucket::new(TokenBucketConfig {
+            messages_per_sec: 100.0,
+            bytes_per_sec: 100.0,
+            message_burst: 100.0,
+            byte_burst: 100.0,
+        });
+        let now = Instant::now();
+        assert!(!bucket.allow_at(200, now));
+        assert!(bucket.allow_at(50, now));
+    }
+}
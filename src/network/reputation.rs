@@ -0,0 +1,163 @@
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::RwLock;
+
+use crate::types::transaction::now_unix;
+
+/// Scoring inputs fed into the reputation tracker by the network,
+/// consensus, and security layers.
+#[derive(Debug, Clone, Copy)]
+pub enum ScoreInput {
+    /// A measured round-trip latency to the peer.
+    Latency(Duration),
+    /// The peer delivered a useful, valid message.
+    ValidMessage,
+    /// The peer sent something malformed or protocol-violating.
+    InvalidMessage,
+    /// The peer was banned by the security layer.
+    Banned,
+}
+
+/// Aggregated per-peer reputation components.
+#[derive(Debug, Clone)]
+pub struct PeerScore {
+    /// EWMA of round-trip latency, in milliseconds.
+    pub latency_ms: f64,
+    pub valid_messages: u64,
+    pub invalid_messages: u64,
+    pub bans: u32,
+    pub last_update: u64,
+}
+
+impl Default for PeerScore {
+    fn default() -> Self {
+        Self {
+            latency_ms: 100.0,
+            valid_messages: 0,
+            invalid_messages: 0,
+            bans: 0,
+            last_update: 0,
+        }
+    }
+}
+
+impl PeerScore {
+    /// Combined score in `[0, 1]`: latency and message validity weighted,
+    /// zeroed while the peer carries a ban.
+    pub fn score(&self) -> f64 {
+        if self.bans > 0 {
+            return 0.0;
+        }
+        let latency_component = (1.0 - self.latency_ms / 1000.0).clamp(0.0, 1.0);
+        let total = self.valid_messages + self.invalid_messages;
+        let validity_component = if total == 0 {
+            0.5
+        } else {
+            self.valid_messages as f64 / total as f64
+        };
+        0.4 * latency_component + 0.6 * validity_component
+    }
+}
+
+/// The single source of truth for peer quality, consumed by gossip peer
+/// selection and connection eviction. Replaces the per-module quality
+/// fields that used to live on the various `PeerInfo` structs.
+pub struct ReputationTracker {
+    peers: RwLock<HashMap<String, PeerScore>>,
+}
+
+impl ReputationTracker {
+    pub fn new() -> Self {
+        Self {
+            peers: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn record(&self, peer_id: &str, input: ScoreInput) {
+        let mut peers = self.peers.write().await;
+        let entry = peers.entry(peer_id.to_string()).or_default();
+        match input {
+            ScoreInput::Latency(rtt) => {
+                let ms = rtt.as_secs_f64() * 1000.0;
+                entry.latency_ms = 0.8 * entry.latency_ms + 0.2 * ms;
+            }
+            ScoreInput::ValidMessage => entry.valid_messages += 1,
+            ScoreInput::InvalidMessage => entry.invalid_messages += 1,
+            ScoreInput::Banned => entry.bans += 1,
+        }
+        entry.last_update = now_unix();
+    }
+
+    pub async fn score(&self, peer_id: &str) -> f64 {
+        self.peers
+            .read()
+            .await
+            .get(peer_id)
+            .map(|p| p.score())
+            .unwrap_or(0.5)
+    }
+
+    pub async fn get(&self, peer_id: &str) -> Option<PeerScore> {
+        self.peers.read().await.get(peer_id).cloned()
+    }
+
+    /// The `n` best-scored peers among `candidates`, used for gossip
+    /// target selection.
+    pub async fn best_peers(&self, candidates: &[String], n: usize) -> Vec<String> {
+        let peers = self.peers.read().await;
+        let mut scored: Vec<(f64, &String)> = candidates
+            .iter()
+            .map(|id| (peers.get(id).map(|p| p.score()).unwrap_or(0.5), id))
+            .collect();
+        scored.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+        scored.into_iter().take(n).map(|(_, id)| id.clone()).collect()
+    }
+
+    /// The worst-scored peer among `candidates`, used for eviction.
+    pub async fn worst_peer(&self, candidates: &[String]) -> Option<String> {
+        let peers = self.peers.read().await;
+        candidates
+            .iter()
+            .map(|id| (peers.get(id).map(|p| p.score()).unwrap_or(0.5), id))
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal))
+            .map(|(_, id)| id.clone())
+    }
+
+    pub async fn remove(&self, peer_id: &str) {
+        self.peers.write().await.remove(peer_id);
+    }
+}
+
+impl Default for ReputationTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn invalid_messages_lower_the_score() {
+        let tracker = ReputationTracker::new();
+        tracker.record("good", ScoreInput::ValidMessage).await;
+        tracker.record("bad", ScoreInput::InvalidMessage).await;
+        assert!(tracker.score("good").await > tracker.score("bad").await);
+    }
+
+    #[tokio::test]
+    async fn bans_zero_the_score_and_eviction_picks_worst() {
+        let tracker = ReputationTracker::new();
+        tracker.record("a", ScoreInput::ValidMessage).await;
+        tracker.record("b", ScoreInput::Banned).await;
+        assert_eq!(tracker.score("b").await, 0.0);
+        let candidates = vec!["a".to_string(), "b".to_string()];
+        assert_eq!(tracker.worst_peer(&candidates).await, Some("b".to_string()));
+        assert_eq!(
+            tracker.best_peers(&candidates, 1).await,
+            vec!["a".to_string()]
+        );
+    }
+}
@@ -0,0 +1,111 @@
+//! BLS12-381 signatures (min-pk flavor: 48-byte public keys, 96-byte
+//! signatures). Unlike ed25519, signatures over the same message can be
+//! aggregated into one 96-byte proof verified against all signer keys
+//! at once, which keeps commit certificates small as the validator set
+//! grows.
+
+use blst::min_pk::{AggregateSignature, PublicKey, SecretKey, Signature};
+use blst::BLST_ERROR;
+use rand::RngCore;
+
+/// Domain separation tag, per the BLS signature hash-to-curve spec.
+const DST: &[u8] = b"ARTHA_BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_";
+
+/// A validator's BLS keypair, used alongside its ed25519 identity key.
+pub struct BlsKeypair {
+    secret: SecretKey,
+}
+
+impl BlsKeypair {
+    /// Generate a fresh random keypair.
+    pub fn generate() -> Self {
+        let mut ikm = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut ikm);
+        Self {
+            secret: SecretKey::key_gen(&ikm, &[]).expect("ikm is long enough"),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        SecretKey::from_bytes(bytes).ok().map(|secret| Self { secret })
+    }
+
+    /// The 48-byte compressed public key.
+    pub fn public_key(&self) -> Vec<u8> {
+        self.secret.sk_to_pk().compress().to_vec()
+    }
+
+    /// The 32-byte secret scalar, for persisting the key to disk.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.secret.to_bytes().to_vec()
+    }
+
+    /// Sign `message`, returning the 96-byte compressed signature.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.secret.sign(message, DST, &[]).compress().to_vec()
+    }
+}
+
+/// Verify a single BLS signature.
+pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let Ok(public_key) = PublicKey::uncompress(public_key) else {
+        return false;
+    };
+    let Ok(signature) = Signature::uncompress(signature) else {
+        return false;
+    };
+    signature.verify(true, message, DST, &[], &public_key, true) == BLST_ERROR::BLST_SUCCESS
+}
+
+/// Aggregate signatures over the same message into one proof. Returns
+/// `None` for an empty or malformed input.
+pub fn aggregate(signatures: &[Vec<u8>]) -> Option<Vec<u8>> {
+    let parsed: Vec<Signature> = signatures
+        .iter()
+        .map(|s| Signature::uncompress(s).ok())
+        .collect::<Option<_>>()?;
+    let refs: Vec<&Signature> = parsed.iter().collect();
+    AggregateSignature::aggregate(&refs, true)
+        .ok()
+        .map(|agg| agg.to_signature().compress().to_vec())
+}
+
+/// Verify an aggregated signature over one shared message against every
+/// signer's public key.
+pub fn verify_aggregate(public_keys: &[Vec<u8>], message: &[u8], signature: &[u8]) -> bool {
+    let Some(parsed) = public_keys
+        .iter()
+        .map(|k| PublicKey::uncompress(k).ok())
+        .collect::<Option<Vec<PublicKey>>>()
+    else {
+        return false;
+    };
+    if parsed.is_empty() {
+        return false;
+    }
+    let Ok(signature) = Signature::uncompress(signature) else {
+        return false;
+    };
+    let refs: Vec<&PublicKey> = parsed.iter().collect();
+    signature.fast_aggregate_verify(true, message, DST, &refs) == BLST_ERROR::BLST_SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aggregate_signature_verifies_against_all_keys() {
+        let keys: Vec<BlsKeypair> = (0..3).map(|_| BlsKeypair::generate()).collect();
+        let message = b"commit:5:0";
+        let signatures: Vec<Vec<u8>> = keys.iter().map(|k| k.sign(message)).collect();
+        assert!(verify(&keys[0].public_key(), message, &signatures[0]));
+
+        let aggregated = aggregate(&signatures).unwrap();
+        let public_keys: Vec<Vec<u8>> = keys.iter().map(|k| k.public_key()).collect();
+        assert!(verify_aggregate(&public_keys, message, &aggregated));
+        // A missing signer or a different message fails.
+        assert!(!verify_aggregate(&public_keys[..2], message, &aggregated));
+        assert!(!verify_aggregate(&public_keys, b"other", &aggregated));
+    }
+}
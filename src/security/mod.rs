@@ -0,0 +1,225 @@
+pub mod bls;
+pub mod network;
+pub mod scheme;
+pub mod signer;
+pub mod smt;
+pub mod state;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::rngs::OsRng;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum SecurityError {
+    #[error("invalid key material: {0}")]
+    InvalidKey(String),
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("account error: {0}")]
+    Account(String),
+    #[error("signer backend error: {0}")]
+    Backend(String),
+}
+
+/// Holds the node's signing key and performs signing/verification.
+pub struct SecurityManager {
+    signing_key: SigningKey,
+}
+
+impl SecurityManager {
+    /// Generate a fresh random keypair.
+    pub fn new() -> Self {
+        Self {
+            signing_key: SigningKey::generate(&mut OsRng),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Self {
+        Self {
+            signing_key: SigningKey::from_bytes(bytes),
+        }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.signing_key.sign(message).to_bytes().to_vec()
+    }
+
+    pub fn public_key(&self) -> Vec<u8> {
+        self.signing_key.verifying_key().to_bytes().to_vec()
+    }
+
+    /// Hex-encoded address derived from the public key.
+    pub fn address(&self) -> String {
+        address_from_public_key(&self.public_key())
+    }
+
+    pub fn verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        let Ok(key_bytes) = <[u8; 32]>::try_from(public_key) else {
+            return false;
+        };
+        let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+            return false;
+        };
+        let Ok(sig_bytes) = <[u8; 64]>::try_from(signature) else {
+            return false;
+        };
+        verifying_key
+            .verify(message, &Signature::from_bytes(&sig_bytes))
+            .is_ok()
+    }
+
+    /// Verify many (public key, message, signature) triples in one
+    /// batched operation, which amortizes the curve arithmetic and is
+    /// several times faster than verifying serially. Returns false if
+    /// any entry is malformed or any signature is invalid.
+    pub fn verify_batch(entries: &[(Vec<u8>, Vec<u8>, Vec<u8>)]) -> bool {
+        let mut keys = Vec::with_capacity(entries.len());
+        let mut messages = Vec::with_capacity(entries.len());
+        let mut signatures = Vec::with_capacity(entries.len());
+        for (public_key, message, signature) in entries {
+            let Ok(key_bytes) = <[u8; 32]>::try_from(public_key.as_slice()) else {
+                return false;
+            };
+            let Ok(verifying_key) = VerifyingKey::from_bytes(&key_bytes) else {
+                return false;
+            };
+            let Ok(sig_bytes) = <[u8; 64]>::try_from(signature.as_slice()) else {
+                return false;
+            };
+            keys.push(verifying_key);
+            messages.push(message.as_slice());
+            signatures.push(Signature::from_bytes(&sig_bytes));
+        }
+        ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok()
+    }
+}
+
+impl Default for SecurityManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// File in the keys directory holding a rotated-but-not-yet-active
+/// validator key.
+pub const PENDING_KEY_FILE: &str = "validator_next.json";
+
+/// A freshly generated validator key awaiting activation.
+///
+/// Rotation is two-phase: the key is generated and persisted here, the
+/// operator registers its public key with the validator set via a
+/// staking transaction, and the node activates it on the next restart.
+/// Swapping the live signing key immediately would leave the node
+/// signing with a key the validator set does not recognize.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct PendingKey {
+    pub address: String,
+    /// Hex-encoded public key, for the staking registration.
+    pub public_key: String,
+    /// Hex-encoded 32-byte signing seed.
+    pub private_key: String,
+}
+
+impl PendingKey {
+    /// Generate a fresh keypair for rotation.
+    pub fn generate() -> Self {
+        let key = SigningKey::generate(&mut OsRng);
+        let public_key = key.verifying_key().to_bytes().to_vec();
+        Self {
+            address: address_from_public_key(&public_key),
+            public_key: hex::encode(&public_key),
+            private_key: hex::encode(key.to_bytes()),
+        }
+    }
+
+    /// Build the signing manager for this key, if the stored seed is
+    /// well-formed.
+    pub fn to_manager(&self) -> Result<SecurityManager, SecurityError> {
+        let seed = hex::decode(&self.private_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or_else(|| SecurityError::InvalidKey("malformed pending key seed".into()))?;
+        Ok(SecurityManager::from_bytes(&seed))
+    }
+}
+
+/// Canonical signing preimage. Every signature on the chain covers a
+/// document built this way, so a signature from one network (or one
+/// message type) never verifies as another.
+///
+/// Each variable-length field is prefixed with its length as a
+/// big-endian u32, so no choice of chain id, message type, or body can
+/// produce the same bytes as a different choice — unlike a separator
+/// character, which a field could contain.
+pub fn sign_doc(chain_id: &str, msg_type: &str, body: &[u8]) -> Vec<u8> {
+    let mut doc = Vec::with_capacity(12 + chain_id.len() + msg_type.len() + body.len());
+    for field in [msg_type.as_bytes(), chain_id.as_bytes(), body] {
+        doc.extend_from_slice(&(field.len() as u32).to_be_bytes());
+        doc.extend_from_slice(field);
+    }
+    doc
+}
+
+/// Canonical sign-doc for consensus messages over a block id at
+/// (height, round): votes, commits, and proposals differ only in
+/// `msg_type`. Height and round are fixed-width big-endian, so the
+/// document needs no further framing.
+pub fn consensus_sign_doc(
+    chain_id: &str,
+    msg_type: &str,
+    height: u64,
+    round: u32,
+    block_id: &[u8],
+) -> Vec<u8> {
+    let mut body = Vec::with_capacity(12 + block_id.len());
+    body.extend_from_slice(&height.to_be_bytes());
+    body.extend_from_slice(&round.to_be_bytes());
+    body.extend_from_slice(block_id);
+    sign_doc(chain_id, msg_type, &body)
+}
+
+/// Derive an address from an ed25519 public key: first 20 bytes of its SHA-256.
+pub fn address_from_public_key(public_key: &[u8]) -> String {
+    let digest = Sha256::digest(public_key);
+    hex::encode(&digest[..20])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_docs_cannot_collide_across_field_boundaries() {
+        // With a separator these two would be the same bytes.
+        assert_ne!(sign_doc("chain-a", "x/y", b"z"), sign_doc("chain-a/x", "y", b"z"));
+        assert_ne!(
+            consensus_sign_doc("c", "consensus/prevote", 1, 2, &[3]),
+            consensus_sign_doc("c", "consensus/precommit", 1, 2, &[3]),
+        );
+        assert_ne!(
+            consensus_sign_doc("c", "consensus/commit", 1, 2, &[3]),
+            consensus_sign_doc("c", "consensus/commit", 1, 3, &[3]),
+        );
+    }
+
+    #[test]
+    fn sign_and_verify_roundtrip() {
+        let manager = SecurityManager::new();
+        let sig = manager.sign(b"hello");
+        assert!(SecurityManager::verify(&manager.public_key(), b"hello", &sig));
+        assert!(!SecurityManager::verify(&manager.public_key(), b"other", &sig));
+    }
+
+    #[test]
+    fn pending_key_activates_to_the_same_identity() {
+        let pending = PendingKey::generate();
+        let manager = pending.to_manager().unwrap();
+        assert_eq!(hex::encode(manager.public_key()), pending.public_key);
+        assert_eq!(manager.address(), pending.address);
+
+        let mut corrupt = PendingKey::generate();
+        corrupt.private_key = "not-hex".into();
+        assert!(corrupt.to_manager().is_err());
+    }
+}
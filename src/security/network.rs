@@ -0,0 +1,359 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::network::rate_limit::{TokenBucket, TokenBucketConfig};
+use crate::network::reputation::{ReputationTracker, ScoreInput};
+use crate::storage::{Column, KvStore};
+use crate::types::transaction::now_unix;
+
+/// Expiry timestamp used for operator-issued permanent bans; far enough
+/// out that the usual "still in the future" check never clears it.
+const PERMANENT: u64 = u64::MAX;
+
+/// Storage key (under [`Column::State`]) the ban set is persisted at.
+const BAN_SET_KEY: &[u8] = b"net_bans";
+
+/// Per-peer bookkeeping kept by the network security layer. Quality
+/// scoring lives in the shared [`ReputationTracker`].
+#[derive(Debug, Clone)]
+pub struct PeerInfo {
+    pub address: String,
+    pub violations: u32,
+    pub last_seen: u64,
+}
+
+/// A ban covering a whole CIDR range rather than one address.
+struct RangeBan {
+    /// The range as the operator wrote it, kept for persistence and logs.
+    cidr: String,
+    network: IpAddr,
+    prefix: u8,
+    /// Unix timestamp the ban expires; [`PERMANENT`] never does.
+    until: u64,
+}
+
+/// Ban set as written to storage, so bans survive restarts.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PersistedBans {
+    addresses: Vec<(String, u64)>,
+    ranges: Vec<(String, u64)>,
+}
+
+/// Tracks misbehaving peers and bans keyed by address or CIDR range.
+pub struct NetworkSecurityManager {
+    peers: RwLock<HashMap<String, PeerInfo>>,
+    /// Banned address -> unix timestamp the ban expires.
+    banned: RwLock<HashMap<String, u64>>,
+    /// Banned CIDR ranges, matched against the peer's IP.
+    banned_ranges: RwLock<Vec<RangeBan>>,
+    /// Set once the node's store is open; bans are mirrored into it.
+    store: RwLock<Option<Arc<dyn KvStore>>>,
+    /// Per-peer token buckets, created lazily.
+    rate_limits: RwLock<HashMap<String, Arc<TokenBucket>>>,
+    rate_config: TokenBucketConfig,
+    /// Shared reputation subsystem; violations and bans feed into it.
+    reputation: Arc<ReputationTracker>,
+    /// Reputation score below which a peer gets banned.
+    ban_threshold: f64,
+}
+
+impl NetworkSecurityManager {
+    pub fn new(reputation: Arc<ReputationTracker>) -> Self {
+        Self {
+            peers: RwLock::new(HashMap::new()),
+            banned: RwLock::new(HashMap::new()),
+            banned_ranges: RwLock::new(Vec::new()),
+            store: RwLock::new(None),
+            rate_limits: RwLock::new(HashMap::new()),
+            rate_config: TokenBucketConfig::default(),
+            reputation,
+            ban_threshold: 0.2,
+        }
+    }
+
+    /// Whether `address` may send one message of `bytes` right now.
+    pub async fn check_rate(&self, address: &str, bytes: usize) -> bool {
+        let bucket = {
+            let limits = self.rate_limits.read().await;
+            limits.get(address).cloned()
+        };
+        let bucket = match bucket {
+            Some(bucket) => bucket,
+            None => {
+                let mut limits = self.rate_limits.write().await;
+                Arc::clone(
+                    limits
+                        .entry(address.to_string())
+                        .or_insert_with(|| Arc::new(TokenBucket::new(self.rate_config))),
+                )
+            }
+        };
+        bucket.allow(bytes)
+    }
+
+    pub async fn observe_peer(&self, address: &str) {
+        let mut peers = self.peers.write().await;
+        let entry = peers.entry(address.to_string()).or_insert_with(|| PeerInfo {
+            address: address.to_string(),
+            violations: 0,
+            last_seen: now_unix(),
+        });
+        entry.last_seen = now_unix();
+    }
+
+    /// Record a protocol violation; bans the peer when its reputation
+    /// score drops far enough.
+    pub async fn record_violation(&self, address: &str) {
+        {
+            let mut peers = self.peers.write().await;
+            let entry = peers.entry(address.to_string()).or_insert_with(|| PeerInfo {
+                address: address.to_string(),
+                violations: 0,
+                last_seen: now_unix(),
+            });
+            entry.violations += 1;
+        }
+        self.reputation
+            .record(address, ScoreInput::InvalidMessage)
+            .await;
+        if self.reputation.score(address).await < self.ban_threshold {
+            self.ban_peer(address, 3600).await;
+        }
+    }
+
+    /// Load persisted bans from `store` and mirror future changes into
+    /// it, so operator bans survive restarts.
+    pub async fn attach_store(&self, store: Arc<dyn KvStore>) {
+        if let Ok(Some(bytes)) = store.get(&Column::State.key(BAN_SET_KEY)) {
+            match serde_json::from_slice::<PersistedBans>(&bytes) {
+                Ok(persisted) => {
+                    let now = now_unix();
+                    let mut banned = self.banned.write().await;
+                    for (address, until) in persisted.addresses {
+                        if until > now {
+                            banned.insert(address, until);
+                        }
+                    }
+                    drop(banned);
+                    let mut ranges = self.banned_ranges.write().await;
+                    for (cidr, until) in persisted.ranges {
+                        match parse_cidr(&cidr) {
+                            Ok((network, prefix)) if until > now => ranges.push(RangeBan {
+                                cidr,
+                                network,
+                                prefix,
+                                until,
+                            }),
+                            Ok(_) => {}
+                            Err(err) => log::warn!("dropping persisted ban {cidr}: {err}"),
+                        }
+                    }
+                }
+                Err(err) => log::warn!("could not decode persisted ban set: {err}"),
+            }
+        }
+        *self.store.write().await = Some(store);
+    }
+
+    /// Write the current ban set to the attached store, if any.
+    async fn persist_bans(&self) {
+        let store = match self.store.read().await.clone() {
+            Some(store) => store,
+            None => return,
+        };
+        let persisted = PersistedBans {
+            addresses: self
+                .banned
+                .read()
+                .await
+                .iter()
+                .map(|(address, &until)| (address.clone(), until))
+                .collect(),
+            ranges: self
+                .banned_ranges
+                .read()
+                .await
+                .iter()
+                .map(|ban| (ban.cidr.clone(), ban.until))
+                .collect(),
+        };
+        let bytes = match serde_json::to_vec(&persisted) {
+            Ok(bytes) => bytes,
+            Err(err) => {
+                log::warn!("could not encode ban set: {err}");
+                return;
+            }
+        };
+        if let Err(err) = store.put(&Column::State.key(BAN_SET_KEY), &bytes) {
+            log::warn!("could not persist ban set: {err}");
+        }
+    }
+
+    pub async fn ban_peer(&self, address: &str, duration_secs: u64) {
+        self.banned
+            .write()
+            .await
+            .insert(address.to_string(), now_unix() + duration_secs);
+        self.reputation.record(address, ScoreInput::Banned).await;
+        log::warn!("banned peer {address} for {duration_secs}s");
+        self.persist_bans().await;
+    }
+
+    /// Operator-issued ban that never expires and survives restarts.
+    pub async fn ban_peer_permanently(&self, address: &str) {
+        self.banned
+            .write()
+            .await
+            .insert(address.to_string(), PERMANENT);
+        self.reputation.record(address, ScoreInput::Banned).await;
+        log::warn!("permanently banned peer {address}");
+        self.persist_bans().await;
+    }
+
+    /// Ban every address inside `cidr` (e.g. `10.0.0.0/8`); a `None`
+    /// duration makes the ban permanent. Fails when the range does not
+    /// parse.
+    pub async fn ban_range(
+        &self,
+        cidr: &str,
+        duration_secs: Option<u64>,
+    ) -> Result<(), String> {
+        let (network, prefix) = parse_cidr(cidr)?;
+        let until = match duration_secs {
+            Some(secs) => now_unix() + secs,
+            None => PERMANENT,
+        };
+        let mut ranges = self.banned_ranges.write().await;
+        ranges.retain(|ban| ban.cidr != cidr);
+        ranges.push(RangeBan {
+            cidr: cidr.to_string(),
+            network,
+            prefix,
+            until,
+        });
+        drop(ranges);
+        log::warn!("banned range {cidr}");
+        self.persist_bans().await;
+        Ok(())
+    }
+
+    pub async fn is_banned(&self, address: &str) -> bool {
+        {
+            let mut banned = self.banned.write().await;
+            match banned.get(address) {
+                Some(&until) if until > now_unix() => return true,
+                Some(_) => {
+                    banned.remove(address);
+                }
+                None => {}
+            }
+        }
+        let Some(ip) = ip_of(address) else {
+            return false;
+        };
+        let now = now_unix();
+        self.banned_ranges
+            .read()
+            .await
+            .iter()
+            .any(|ban| ban.until > now && cidr_contains(&ban.network, ban.prefix, &ip))
+    }
+
+    pub async fn peer_info(&self, address: &str) -> Option<PeerInfo> {
+        self.peers.read().await.get(address).cloned()
+    }
+}
+
+/// Parse `a.b.c.d/len` (or the IPv6 equivalent) into its parts.
+fn parse_cidr(cidr: &str) -> Result<(IpAddr, u8), String> {
+    let (ip, len) = cidr
+        .split_once('/')
+        .ok_or_else(|| format!("not a CIDR range: {cidr}"))?;
+    let network: IpAddr = ip
+        .parse()
+        .map_err(|err| format!("bad network address in {cidr}: {err}"))?;
+    let prefix: u8 = len
+        .parse()
+        .map_err(|err| format!("bad prefix length in {cidr}: {err}"))?;
+    let max = match network {
+        IpAddr::V4(_) => 32,
+        IpAddr::V6(_) => 128,
+    };
+    if prefix > max {
+        return Err(format!("prefix /{prefix} too long for {ip}"));
+    }
+    Ok((network, prefix))
+}
+
+/// Whether `ip` falls inside the `network/prefix` range. Families must
+/// match; a v4 range never covers a v6 address.
+fn cidr_contains(network: &IpAddr, prefix: u8, ip: &IpAddr) -> bool {
+    match (network, ip) {
+        (IpAddr::V4(network), IpAddr::V4(ip)) => {
+            let mask = if prefix == 0 {
+                0
+            } else {
+                u32::MAX << (32 - u32::from(prefix))
+            };
+            (u32::from(*network) & mask) == (u32::from(*ip) & mask)
+        }
+        (IpAddr::V6(network), IpAddr::V6(ip)) => {
+            let mask = if prefix == 0 {
+                0
+            } else {
+                u128::MAX << (128 - u32::from(prefix))
+            };
+            (u128::from(*network) & mask) == (u128::from(*ip) & mask)
+        }
+        _ => false,
+    }
+}
+
+/// Extract the IP from an address that may carry a port.
+fn ip_of(address: &str) -> Option<IpAddr> {
+    if let Ok(socket) = address.parse::<std::net::SocketAddr>() {
+        return Some(socket.ip());
+    }
+    address.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::memory::MemoryStore;
+
+    fn manager() -> NetworkSecurityManager {
+        NetworkSecurityManager::new(Arc::new(ReputationTracker::new()))
+    }
+
+    #[tokio::test]
+    async fn range_bans_cover_member_addresses() {
+        let security = manager();
+        security.ban_range("10.1.0.0/16", None).await.unwrap();
+        assert!(security.is_banned("10.1.42.7").await);
+        assert!(security.is_banned("10.1.42.7:26656").await);
+        assert!(!security.is_banned("10.2.0.1").await);
+        assert!(security.ban_range("10.1.0.0", None).await.is_err());
+        assert!(security.ban_range("10.1.0.0/40", None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn operator_bans_survive_a_restart() {
+        let store: Arc<dyn KvStore> = Arc::new(MemoryStore::new());
+        let security = manager();
+        security.attach_store(Arc::clone(&store)).await;
+        security.ban_peer_permanently("192.0.2.9").await;
+        security.ban_range("198.51.100.0/24", None).await.unwrap();
+
+        // A fresh manager over the same store sees both bans.
+        let restarted = manager();
+        restarted.attach_store(store).await;
+        assert!(restarted.is_banned("192.0.2.9").await);
+        assert!(restarted.is_banned("198.51.100.77").await);
+        assert!(!restarted.is_banned("203.0.113.1").await);
+    }
+}
@@ -0,0 +1,105 @@
+//! Signature scheme abstraction for user accounts. Validator identity
+//! stays on ed25519, but account holders can sign transactions with
+//! secp256k1 keys, so existing Ethereum/Bitcoin tooling can produce
+//! valid signatures for this chain.
+
+use k256::ecdsa::signature::hazmat::PrehashVerifier;
+use k256::ecdsa::{Signature as EcdsaSignature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::signer::Signer;
+use super::{address_from_public_key, SecurityError, SecurityManager};
+
+/// The signature schemes accepted for account signatures.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SignatureScheme {
+    #[default]
+    Ed25519,
+    Secp256k1,
+}
+
+impl SignatureScheme {
+    /// Verify `signature` over `message` for a key of this scheme.
+    /// secp256k1 signs the SHA-256 of the message, as ECDSA tooling
+    /// expects a fixed-width digest.
+    pub fn verify(&self, public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        match self {
+            SignatureScheme::Ed25519 => SecurityManager::verify(public_key, message, signature),
+            SignatureScheme::Secp256k1 => {
+                let Ok(key) = VerifyingKey::from_sec1_bytes(public_key) else {
+                    return false;
+                };
+                let Ok(signature) = EcdsaSignature::from_slice(signature) else {
+                    return false;
+                };
+                key.verify_prehash(&Sha256::digest(message), &signature)
+                    .is_ok()
+            }
+        }
+    }
+}
+
+/// A secp256k1 account keypair. The public key is the 33-byte
+/// compressed SEC1 encoding; the address derives from it the same way
+/// ed25519 addresses derive from their keys.
+pub struct Secp256k1Keypair {
+    signing_key: SigningKey,
+}
+
+impl Secp256k1Keypair {
+    pub fn generate() -> Self {
+        Self {
+            signing_key: SigningKey::random(&mut rand::rngs::OsRng),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8; 32]) -> Option<Self> {
+        SigningKey::from_slice(bytes)
+            .ok()
+            .map(|signing_key| Self { signing_key })
+    }
+}
+
+impl Signer for Secp256k1Keypair {
+    fn public_key(&self) -> Result<Vec<u8>, SecurityError> {
+        Ok(self
+            .signing_key
+            .verifying_key()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec())
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SecurityError> {
+        use k256::ecdsa::signature::hazmat::PrehashSigner;
+        let signature: EcdsaSignature = self
+            .signing_key
+            .sign_prehash(&Sha256::digest(message))
+            .map_err(|err| SecurityError::Backend(err.to_string()))?;
+        Ok(signature.to_vec())
+    }
+
+    fn address(&self) -> Result<String, SecurityError> {
+        Ok(address_from_public_key(&self.public_key()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secp256k1_signs_and_verifies_through_the_scheme() {
+        let keypair = Secp256k1Keypair::generate();
+        let public_key = keypair.public_key().unwrap();
+        let signature = keypair.sign(b"hello").unwrap();
+        assert!(SignatureScheme::Secp256k1.verify(&public_key, b"hello", &signature));
+        assert!(!SignatureScheme::Secp256k1.verify(&public_key, b"other", &signature));
+        // The schemes are not interchangeable.
+        assert!(!SignatureScheme::Ed25519.verify(&public_key, b"hello", &signature));
+        assert_eq!(public_key.len(), 33);
+        assert_eq!(keypair.address().unwrap().len(), 40);
+    }
+}
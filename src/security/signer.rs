@@ -0,0 +1,102 @@
+use crate::security::{address_from_public_key, SecurityError, SecurityManager};
+
+/// A source of ed25519 signatures for this node's validator or account
+/// keys. The default backend holds the key in process memory; the
+/// `hsm` feature adds a YubiHSM backend so institutional operators can
+/// keep keys in hardware. Operations are fallible because a hardware
+/// backend can lose its session.
+pub trait Signer: Send + Sync {
+    /// The ed25519 public key this signer signs with.
+    fn public_key(&self) -> Result<Vec<u8>, SecurityError>;
+
+    /// Sign `message`, returning the 64-byte signature.
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SecurityError>;
+
+    /// The address derived from this signer's public key.
+    fn address(&self) -> Result<String, SecurityError> {
+        Ok(address_from_public_key(&self.public_key()?))
+    }
+}
+
+impl Signer for SecurityManager {
+    fn public_key(&self) -> Result<Vec<u8>, SecurityError> {
+        Ok(SecurityManager::public_key(self))
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SecurityError> {
+        Ok(SecurityManager::sign(self, message))
+    }
+}
+
+/// Signing backed by a YubiHSM 2, reached through the standard
+/// yubihsm-connector HTTP service. The private key never leaves the
+/// device; the node only holds a session.
+#[cfg(feature = "hsm")]
+pub mod hsm {
+    use yubihsm::{Client, Connector, Credentials, HttpConfig};
+
+    use super::Signer;
+    use crate::security::SecurityError;
+
+    pub struct YubiHsmSigner {
+        client: Client,
+        /// Object id of the ed25519 asymmetric key on the device.
+        key_id: u16,
+    }
+
+    impl YubiHsmSigner {
+        /// Open an authenticated session against a yubihsm-connector at
+        /// `addr:port`, signing with the device key `key_id`.
+        pub fn connect(
+            addr: &str,
+            port: u16,
+            auth_key_id: u16,
+            password: &str,
+            key_id: u16,
+        ) -> Result<Self, SecurityError> {
+            let config = HttpConfig {
+                addr: addr.to_string(),
+                port,
+                timeout_ms: 5_000,
+            };
+            let credentials = Credentials::from_password(auth_key_id, password.as_bytes());
+            let client = Client::open(Connector::http(&config), credentials, true)
+                .map_err(|err| SecurityError::Backend(err.to_string()))?;
+            Ok(Self { client, key_id })
+        }
+    }
+
+    impl Signer for YubiHsmSigner {
+        fn public_key(&self) -> Result<Vec<u8>, SecurityError> {
+            self.client
+                .get_public_key(self.key_id)
+                .map(|key| key.bytes)
+                .map_err(|err| SecurityError::Backend(err.to_string()))
+        }
+
+        fn sign(&self, message: &[u8]) -> Result<Vec<u8>, SecurityError> {
+            self.client
+                .sign_ed25519(self.key_id, message)
+                .map(|signature| signature.to_bytes().to_vec())
+                .map_err(|err| SecurityError::Backend(err.to_string()))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn in_memory_signer_matches_direct_signing() {
+        let manager = SecurityManager::new();
+        let signer: &dyn Signer = &manager;
+        assert_eq!(signer.address().unwrap(), manager.address());
+        let signature = signer.sign(b"hello").unwrap();
+        assert!(SecurityManager::verify(
+            &signer.public_key().unwrap(),
+            b"hello",
+            &signature
+        ));
+    }
+}
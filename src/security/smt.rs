@@ -0,0 +1,205 @@
+//! Sparse Merkle tree over the full 256-bit SHA-256 keyspace.
+//!
+//! Every key hashes to a fixed leaf position, so the root is
+//! deterministic regardless of insertion order, and both inclusion and
+//! exclusion can be proved: an exclusion proof shows the key's slot
+//! hashes up to the root as an empty subtree. Empty subtrees use a
+//! precomputed per-depth default hash, so only occupied paths cost
+//! anything to store or hash.
+
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Depth of the tree: one level per bit of the hashed key.
+const DEPTH: usize = 256;
+
+/// Hash marking an empty leaf slot.
+const EMPTY: [u8; 32] = [0u8; 32];
+
+fn leaf_hash(path: &[u8; 32], value_hash: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x00]);
+    hasher.update(path);
+    hasher.update(value_hash);
+    hasher.finalize().into()
+}
+
+fn node_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update([0x01]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Bit of `path` selecting the branch at `depth` (0 = left).
+fn bit(path: &[u8; 32], depth: usize) -> bool {
+    (path[depth / 8] >> (7 - depth % 8)) & 1 == 1
+}
+
+/// Root hashes of entirely empty subtrees, indexed by depth.
+fn defaults() -> &'static [[u8; 32]; DEPTH + 1] {
+    static DEFAULTS: OnceLock<[[u8; 32]; DEPTH + 1]> = OnceLock::new();
+    DEFAULTS.get_or_init(|| {
+        let mut table = [[0u8; 32]; DEPTH + 1];
+        table[DEPTH] = EMPTY;
+        for depth in (0..DEPTH).rev() {
+            table[depth] = node_hash(&table[depth + 1], &table[depth + 1]);
+        }
+        table
+    })
+}
+
+/// A sparse Merkle tree mapping hashed keys to hashed values.
+#[derive(Debug, Clone, Default)]
+pub struct SparseMerkleTree {
+    /// Occupied leaves: hashed key (the path) to hashed value.
+    leaves: HashMap<[u8; 32], [u8; 32]>,
+}
+
+impl SparseMerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set `key` to `value`, replacing any previous value.
+    pub fn insert(&mut self, key: &[u8], value: &[u8]) {
+        self.leaves
+            .insert(Sha256::digest(key).into(), Sha256::digest(value).into());
+    }
+
+    /// Clear `key`'s slot, restoring its exclusion proof.
+    pub fn remove(&mut self, key: &[u8]) {
+        self.leaves.remove::<[u8; 32]>(&Sha256::digest(key).into());
+    }
+
+    pub fn len(&self) -> usize {
+        self.leaves.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.leaves.is_empty()
+    }
+
+    fn sorted_leaves(&self) -> Vec<(&[u8; 32], &[u8; 32])> {
+        let mut leaves: Vec<_> = self.leaves.iter().collect();
+        leaves.sort_by_key(|(path, _)| *path);
+        leaves
+    }
+
+    fn subtree(depth: usize, leaves: &[(&[u8; 32], &[u8; 32])]) -> [u8; 32] {
+        if leaves.is_empty() {
+            return defaults()[depth];
+        }
+        if depth == DEPTH {
+            return leaf_hash(leaves[0].0, leaves[0].1);
+        }
+        let split = leaves.partition_point(|(path, _)| !bit(path, depth));
+        node_hash(
+            &Self::subtree(depth + 1, &leaves[..split]),
+            &Self::subtree(depth + 1, &leaves[split..]),
+        )
+    }
+
+    /// The deterministic root over all occupied leaves.
+    pub fn root(&self) -> Vec<u8> {
+        Self::subtree(0, &self.sorted_leaves()).to_vec()
+    }
+
+    /// Proof for `key`'s slot: inclusion if occupied, exclusion if not.
+    pub fn prove(&self, key: &[u8]) -> MerkleProof {
+        let path: [u8; 32] = Sha256::digest(key).into();
+        let mut siblings = Vec::with_capacity(DEPTH);
+        let mut leaves = self.sorted_leaves();
+        for depth in 0..DEPTH {
+            let split = leaves.partition_point(|(p, _)| !bit(p, depth));
+            let (taken, sibling) = if bit(&path, depth) {
+                (leaves.split_off(split), leaves)
+            } else {
+                let right = leaves.split_off(split);
+                (leaves, right)
+            };
+            siblings.push(Self::subtree(depth + 1, &sibling).to_vec());
+            leaves = taken;
+        }
+        MerkleProof { siblings }
+    }
+}
+
+/// Sibling path from the root down to one leaf slot.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    /// Sibling subtree roots, ordered from depth 0 downward.
+    pub siblings: Vec<Vec<u8>>,
+}
+
+impl MerkleProof {
+    /// Verify this proof against `root`: `Some(value)` proves the key
+    /// maps to that value, `None` proves the key is absent.
+    pub fn verify(&self, root: &[u8], key: &[u8], value: Option<&[u8]>) -> bool {
+        if self.siblings.len() != DEPTH {
+            return false;
+        }
+        let path: [u8; 32] = Sha256::digest(key).into();
+        let mut current = match value {
+            Some(value) => leaf_hash(&path, &Sha256::digest(value).into()),
+            None => EMPTY,
+        };
+        for (depth, sibling) in self.siblings.iter().enumerate().rev() {
+            let Ok(sibling) = <[u8; 32]>::try_from(sibling.as_slice()) else {
+                return false;
+            };
+            current = if bit(&path, depth) {
+                node_hash(&sibling, &current)
+            } else {
+                node_hash(&current, &sibling)
+            };
+        }
+        current == root
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn root_is_deterministic_and_order_independent() {
+        let mut a = SparseMerkleTree::new();
+        a.insert(b"alice", b"100");
+        a.insert(b"bob", b"50");
+        let mut b = SparseMerkleTree::new();
+        b.insert(b"bob", b"50");
+        b.insert(b"alice", b"100");
+        assert_eq!(a.root(), b.root());
+
+        b.insert(b"alice", b"99");
+        assert_ne!(a.root(), b.root());
+        b.remove(b"alice");
+        assert_eq!(b.len(), 1);
+        assert_ne!(a.root(), b.root());
+    }
+
+    #[test]
+    fn inclusion_and_exclusion_proofs_verify() {
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(b"alice", b"100");
+        tree.insert(b"bob", b"50");
+        let root = tree.root();
+
+        let proof = tree.prove(b"alice");
+        assert!(proof.verify(&root, b"alice", Some(b"100")));
+        // Wrong value, wrong key, and claimed absence all fail.
+        assert!(!proof.verify(&root, b"alice", Some(b"101")));
+        assert!(!proof.verify(&root, b"bob", Some(b"100")));
+        assert!(!proof.verify(&root, b"alice", None));
+
+        // An untouched key gets a verifying exclusion proof.
+        let absent = tree.prove(b"carol");
+        assert!(absent.verify(&root, b"carol", None));
+        assert!(!absent.verify(&root, b"carol", Some(b"1")));
+    }
+}
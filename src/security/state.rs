@@ -0,0 +1,768 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use tokio::sync::RwLock;
+
+use super::smt::SparseMerkleTree;
+use super::SecurityError;
+use crate::types::{Transaction, TransactionError};
+
+/// Capabilities an account may exercise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Permission {
+    Transfer,
+    DeployContract,
+    Vote,
+    Propose,
+}
+
+/// Parameters of a multisig account: any `threshold` of the listed keys
+/// must sign a transaction for it to spend from the account.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct MultisigParams {
+    pub public_keys: Vec<Vec<u8>>,
+    pub threshold: usize,
+}
+
+impl MultisigParams {
+    /// Deterministic address of the account these parameters define.
+    pub fn address(&self) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update((self.threshold as u64).to_be_bytes());
+        for key in &self.public_keys {
+            hasher.update(key);
+        }
+        hex::encode(&hasher.finalize()[..20])
+    }
+
+    /// Whether `signatures` contains valid signatures over `message`
+    /// from at least `threshold` distinct member keys. A key signing
+    /// twice counts once.
+    pub fn verify(&self, message: &[u8], signatures: &[Vec<u8>]) -> bool {
+        let mut signed = vec![false; self.public_keys.len()];
+        for signature in signatures {
+            for (index, key) in self.public_keys.iter().enumerate() {
+                if !signed[index] && super::SecurityManager::verify(key, message, signature) {
+                    signed[index] = true;
+                    break;
+                }
+            }
+        }
+        signed.iter().filter(|s| **s).count() >= self.threshold
+    }
+}
+
+/// Ledger state of a single account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccountState {
+    /// Balance in the chain's native asset.
+    pub balance: u64,
+    /// Balances in other denominations (fee tokens, user-issued
+    /// assets), ordered for deterministic encoding.
+    #[serde(default)]
+    pub balances: BTreeMap<String, u64>,
+    pub nonce: u64,
+    pub permissions: Vec<Permission>,
+    pub frozen: bool,
+    /// Root of the account's contract storage, if any.
+    pub storage_root: Vec<u8>,
+    /// Hash of the contract code this account runs; empty for plain
+    /// accounts.
+    #[serde(default)]
+    pub code_hash: Vec<u8>,
+    /// Fee allowances this account sponsors: grantee to remaining
+    /// native amount.
+    #[serde(default)]
+    pub fee_grants: BTreeMap<String, u64>,
+    /// Multisig parameters, for accounts controlled by several keys.
+    #[serde(default)]
+    pub multisig: Option<MultisigParams>,
+}
+
+impl AccountState {
+    /// Balance in `denom`; empty or the native denomination reads the
+    /// native balance.
+    pub fn balance_of(&self, denom: &str) -> u64 {
+        if is_native(denom) {
+            self.balance
+        } else {
+            self.balances.get(denom).copied().unwrap_or(0)
+        }
+    }
+}
+
+/// Whether `denom` names the chain's native asset.
+pub(crate) fn is_native(denom: &str) -> bool {
+    denom.is_empty() || denom == crate::types::fees::NATIVE_DENOM
+}
+
+impl Default for AccountState {
+    fn default() -> Self {
+        Self {
+            balance: 0,
+            balances: BTreeMap::new(),
+            nonce: 0,
+            permissions: vec![Permission::Transfer],
+            frozen: false,
+            storage_root: Vec::new(),
+            code_hash: Vec::new(),
+            fee_grants: BTreeMap::new(),
+            multisig: None,
+        }
+    }
+}
+
+/// Key/value slots of one contract account.
+type StorageSlots = HashMap<Vec<u8>, Vec<u8>>;
+
+/// Manages account state and applies transfers to it.
+pub struct StateSecurityManager {
+    accounts: RwLock<HashMap<String, AccountState>>,
+    /// Committed historical versions per account, ascending by height.
+    versions: RwLock<HashMap<String, Vec<(u64, AccountState)>>>,
+    /// Accounts mutated since the last committed version.
+    dirty: RwLock<HashSet<String>>,
+    /// Contract key/value storage per contract account. The Merkle root
+    /// over each map is mirrored into the account's `storage_root`, so
+    /// contract state folds into the global state root.
+    storage: RwLock<HashMap<String, StorageSlots>>,
+}
+
+impl StateSecurityManager {
+    pub fn new() -> Self {
+        Self {
+            accounts: RwLock::new(HashMap::new()),
+            versions: RwLock::new(HashMap::new()),
+            dirty: RwLock::new(HashSet::new()),
+            storage: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn get_account(&self, address: &str) -> Option<AccountState> {
+        self.accounts.read().await.get(address).cloned()
+    }
+
+    /// An account's state as of `height`: the latest version committed
+    /// at or below it. `None` if the account did not exist yet.
+    pub async fn get_account_at(&self, address: &str, height: u64) -> Option<AccountState> {
+        self.versions
+            .read()
+            .await
+            .get(address)?
+            .iter()
+            .rev()
+            .find(|(h, _)| *h <= height)
+            .map(|(_, state)| state.clone())
+    }
+
+    /// Record a version for every account mutated since the last commit.
+    /// Called once per block, after its transactions are applied.
+    pub async fn commit_version(&self, height: u64) {
+        let accounts = self.accounts.read().await;
+        let mut versions = self.versions.write().await;
+        for address in self.dirty.write().await.drain() {
+            if let Some(state) = accounts.get(&address) {
+                let entry = versions.entry(address).or_default();
+                // Re-commits at the same height overwrite, not append.
+                if entry.last().is_some_and(|(h, _)| *h == height) {
+                    entry.pop();
+                }
+                entry.push((height, state.clone()));
+            }
+        }
+    }
+
+    /// Drop account versions below `height`. The newest version below
+    /// the floor is kept per account so queries at the floor (and the
+    /// reconstruction of current state) still resolve.
+    pub async fn prune_versions_below(&self, height: u64) {
+        let mut versions = self.versions.write().await;
+        for history in versions.values_mut() {
+            let cut = history.partition_point(|(h, _)| *h < height);
+            if cut > 1 {
+                history.drain(..cut - 1);
+            }
+        }
+    }
+
+    async fn mark_dirty(&self, address: &str) {
+        self.dirty.write().await.insert(address.to_string());
+    }
+
+    pub async fn set_balance(&self, address: &str, balance: u64) {
+        let mut accounts = self.accounts.write().await;
+        accounts.entry(address.to_string()).or_default().balance = balance;
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Register a multisig account at its derived address. Returns the
+    /// address, or an error for unusable parameters.
+    pub async fn register_multisig(&self, params: MultisigParams) -> Result<String, SecurityError> {
+        if params.public_keys.is_empty() {
+            return Err(SecurityError::Account("multisig has no keys".into()));
+        }
+        if params.threshold == 0 || params.threshold > params.public_keys.len() {
+            return Err(SecurityError::Account(format!(
+                "threshold {} out of range for {} keys",
+                params.threshold,
+                params.public_keys.len()
+            )));
+        }
+        let address = params.address();
+        let mut accounts = self.accounts.write().await;
+        accounts.entry(address.clone()).or_default().multisig = Some(params);
+        drop(accounts);
+        self.mark_dirty(&address).await;
+        Ok(address)
+    }
+
+    /// Whether an account holds a capability.
+    pub async fn has_permission(&self, address: &str, permission: Permission) -> bool {
+        self.accounts
+            .read()
+            .await
+            .get(address)
+            .is_some_and(|account| account.permissions.contains(&permission))
+    }
+
+    /// Grant a capability to an account, creating it if needed.
+    pub async fn grant_permission(&self, address: &str, permission: Permission) {
+        let mut accounts = self.accounts.write().await;
+        let account = accounts.entry(address.to_string()).or_default();
+        if !account.permissions.contains(&permission) {
+            account.permissions.push(permission);
+        }
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Remove a capability from an account, if it holds one.
+    pub async fn revoke_permission(&self, address: &str, permission: Permission) {
+        let mut accounts = self.accounts.write().await;
+        if let Some(account) = accounts.get_mut(address) {
+            account.permissions.retain(|held| *held != permission);
+        }
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Create the account backing a deployed contract, pointing at its
+    /// code. Fails if the address is already taken.
+    pub async fn create_contract_account(
+        &self,
+        address: &str,
+        code_hash: &[u8],
+    ) -> Result<(), SecurityError> {
+        let mut accounts = self.accounts.write().await;
+        if accounts.contains_key(address) {
+            return Err(SecurityError::Account(format!(
+                "address {address} already exists"
+            )));
+        }
+        let account = accounts.entry(address.to_string()).or_default();
+        account.code_hash = code_hash.to_vec();
+        drop(accounts);
+        self.mark_dirty(address).await;
+        Ok(())
+    }
+
+    /// Write one contract storage slot and refresh the account's
+    /// `storage_root`. An empty value deletes the slot.
+    pub async fn set_storage(&self, address: &str, key: &[u8], value: &[u8]) {
+        let mut storage = self.storage.write().await;
+        let slots = storage.entry(address.to_string()).or_default();
+        if value.is_empty() {
+            slots.remove(key);
+        } else {
+            slots.insert(key.to_vec(), value.to_vec());
+        }
+        let mut tree = SparseMerkleTree::new();
+        for (slot, value) in slots.iter() {
+            tree.insert(slot, value);
+        }
+        let root = tree.root();
+        drop(storage);
+        let mut accounts = self.accounts.write().await;
+        accounts.entry(address.to_string()).or_default().storage_root = root;
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Read one contract storage slot.
+    pub async fn get_storage(&self, address: &str, key: &[u8]) -> Option<Vec<u8>> {
+        self.storage
+            .read()
+            .await
+            .get(address)
+            .and_then(|slots| slots.get(key).cloned())
+    }
+
+    /// Sparse Merkle tree over one contract's storage. Proofs from it
+    /// verify against the account's `storage_root`.
+    pub async fn storage_tree(&self, address: &str) -> SparseMerkleTree {
+        let storage = self.storage.read().await;
+        let mut tree = SparseMerkleTree::new();
+        if let Some(slots) = storage.get(address) {
+            for (slot, value) in slots.iter() {
+                tree.insert(slot, value);
+            }
+        }
+        tree
+    }
+
+    /// Freeze an account so it can no longer send transactions. Not
+    /// part of the public surface: freezing is a state transition, so
+    /// it only happens through a governance tx signed by the configured
+    /// freeze authority and executed inside a block.
+    pub(crate) async fn freeze_account(&self, address: &str) {
+        let mut accounts = self.accounts.write().await;
+        accounts.entry(address.to_string()).or_default().frozen = true;
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    pub(crate) async fn unfreeze_account(&self, address: &str) {
+        let mut accounts = self.accounts.write().await;
+        accounts.entry(address.to_string()).or_default().frozen = false;
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Remove `amount` from an account, failing on insufficient balance.
+    pub async fn debit(&self, address: &str, amount: u64) -> Result<(), TransactionError> {
+        let mut accounts = self.accounts.write().await;
+        let account = accounts.entry(address.to_string()).or_default();
+        if account.balance < amount {
+            return Err(TransactionError::InsufficientBalance {
+                have: account.balance,
+                need: amount,
+            });
+        }
+        account.balance -= amount;
+        drop(accounts);
+        self.mark_dirty(address).await;
+        Ok(())
+    }
+
+    /// Add `amount` to an account, creating it if needed.
+    pub async fn credit(&self, address: &str, amount: u64) {
+        let mut accounts = self.accounts.write().await;
+        accounts.entry(address.to_string()).or_default().balance += amount;
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Remove `amount` of `denom` from an account.
+    pub async fn debit_denom(
+        &self,
+        address: &str,
+        denom: &str,
+        amount: u64,
+    ) -> Result<(), TransactionError> {
+        if is_native(denom) {
+            return self.debit(address, amount).await;
+        }
+        let mut accounts = self.accounts.write().await;
+        let account = accounts.entry(address.to_string()).or_default();
+        let balance = account.balances.entry(denom.to_string()).or_default();
+        if *balance < amount {
+            return Err(TransactionError::InsufficientBalance {
+                have: *balance,
+                need: amount,
+            });
+        }
+        *balance -= amount;
+        drop(accounts);
+        self.mark_dirty(address).await;
+        Ok(())
+    }
+
+    /// Add `amount` of `denom` to an account, creating it if needed.
+    pub async fn credit_denom(&self, address: &str, denom: &str, amount: u64) {
+        if is_native(denom) {
+            return self.credit(address, amount).await;
+        }
+        let mut accounts = self.accounts.write().await;
+        let account = accounts.entry(address.to_string()).or_default();
+        *account.balances.entry(denom.to_string()).or_default() += amount;
+        drop(accounts);
+        self.mark_dirty(address).await;
+    }
+
+    /// Allow `grantee` to spend up to `allowance` of the granter's
+    /// native balance on fees. Re-granting replaces the allowance.
+    pub async fn grant_allowance(&self, granter: &str, grantee: &str, allowance: u64) {
+        let mut accounts = self.accounts.write().await;
+        let account = accounts.entry(granter.to_string()).or_default();
+        account.fee_grants.insert(grantee.to_string(), allowance);
+        drop(accounts);
+        self.mark_dirty(granter).await;
+    }
+
+    /// Remove any remaining allowance from `granter` to `grantee`.
+    pub async fn revoke_allowance(&self, granter: &str, grantee: &str) {
+        let mut accounts = self.accounts.write().await;
+        if let Some(account) = accounts.get_mut(granter) {
+            account.fee_grants.remove(grantee);
+        }
+        drop(accounts);
+        self.mark_dirty(granter).await;
+    }
+
+    /// Remaining allowance from `granter` to `grantee`.
+    pub async fn allowance(&self, granter: &str, grantee: &str) -> u64 {
+        self.accounts
+            .read()
+            .await
+            .get(granter)
+            .and_then(|account| account.fee_grants.get(grantee).copied())
+            .unwrap_or(0)
+    }
+
+    /// Consume `fee` from the allowance `granter` extends to `grantee`.
+    pub async fn use_allowance(
+        &self,
+        granter: &str,
+        grantee: &str,
+        fee: u64,
+    ) -> Result<(), TransactionError> {
+        let mut accounts = self.accounts.write().await;
+        let allowance = accounts
+            .get_mut(granter)
+            .and_then(|account| account.fee_grants.get_mut(grantee));
+        match allowance {
+            Some(remaining) if *remaining >= fee => *remaining -= fee,
+            _ => {
+                return Err(TransactionError::Invalid(format!(
+                    "no fee allowance from {granter} covers {fee}"
+                )))
+            }
+        }
+        drop(accounts);
+        self.mark_dirty(granter).await;
+        Ok(())
+    }
+
+    /// Bump an account's nonce without moving value, enforcing the same
+    /// strict ordering as `apply_transaction`. Used by batched
+    /// transactions that spend through per-message debits.
+    pub async fn advance_nonce(&self, address: &str, nonce: u64) -> Result<(), TransactionError> {
+        let mut accounts = self.accounts.write().await;
+        let account = accounts.entry(address.to_string()).or_default();
+        if nonce != account.nonce + 1 {
+            return Err(TransactionError::InvalidNonce {
+                expected: account.nonce + 1,
+                got: nonce,
+            });
+        }
+        account.nonce = nonce;
+        drop(accounts);
+        self.mark_dirty(address).await;
+        Ok(())
+    }
+
+    /// Apply a transfer: debit sender, credit recipient, bump nonce.
+    /// Admission-time validation for the mempool: the sender must be
+    /// unfrozen, afford the worst-case amount plus fee at current
+    /// balances, and use a nonce that is still in the future. Gapped
+    /// nonces are queueable, so only spent nonces are rejected. Mutates
+    /// nothing; execution re-checks everything at commit.
+    pub async fn check_admissible(&self, tx: &Transaction) -> Result<(), TransactionError> {
+        let accounts = self.accounts.read().await;
+        let sender = accounts.get(&tx.sender).cloned().unwrap_or_default();
+        drop(accounts);
+        if sender.frozen {
+            return Err(TransactionError::Invalid("sender account is frozen".into()));
+        }
+        if tx.nonce <= sender.nonce {
+            return Err(TransactionError::InvalidNonce {
+                expected: sender.nonce + 1,
+                got: tx.nonce,
+            });
+        }
+        let fee_cost = if tx.fee_payer.is_empty() {
+            tx.gas_limit.saturating_mul(tx.gas_price)
+        } else {
+            0
+        };
+        let native_need = if is_native(&tx.denom) {
+            fee_cost.saturating_add(tx.amount)
+        } else {
+            fee_cost
+        };
+        if sender.balance < native_need {
+            return Err(TransactionError::InsufficientBalance {
+                have: sender.balance,
+                need: native_need,
+            });
+        }
+        if sender.balance_of(&tx.denom) < tx.amount {
+            return Err(TransactionError::InsufficientBalance {
+                have: sender.balance_of(&tx.denom),
+                need: tx.amount,
+            });
+        }
+        Ok(())
+    }
+
+    pub async fn apply_transaction(&self, tx: &Transaction) -> Result<(), TransactionError> {
+        let mut accounts = self.accounts.write().await;
+        let sender = accounts.entry(tx.sender.clone()).or_default().clone();
+        if sender.frozen {
+            return Err(TransactionError::Invalid("sender account is frozen".into()));
+        }
+        if !sender.permissions.contains(&Permission::Transfer) {
+            return Err(TransactionError::Invalid(
+                "sender lacks transfer permission".into(),
+            ));
+        }
+        // A multisig sender must reach its signing threshold.
+        if let Some(multisig) = &sender.multisig {
+            if !multisig.verify(&tx.signing_bytes(), &tx.signatures) {
+                return Err(TransactionError::InvalidSignature);
+            }
+        }
+        // Solvency: the worst-case fee is always native; the amount is
+        // checked in its own denomination. A sponsored transaction's fee
+        // comes out of the payer's balance, not the sender's.
+        let fee_cost = if tx.fee_payer.is_empty() {
+            tx.gas_limit.saturating_mul(tx.gas_price)
+        } else {
+            0
+        };
+        let native_need = if is_native(&tx.denom) {
+            fee_cost.saturating_add(tx.amount)
+        } else {
+            fee_cost
+        };
+        if sender.balance < native_need {
+            return Err(TransactionError::InsufficientBalance {
+                have: sender.balance,
+                need: native_need,
+            });
+        }
+        if sender.balance_of(&tx.denom) < tx.amount {
+            return Err(TransactionError::InsufficientBalance {
+                have: sender.balance_of(&tx.denom),
+                need: tx.amount,
+            });
+        }
+        if tx.nonce != sender.nonce + 1 {
+            return Err(TransactionError::InvalidNonce {
+                expected: sender.nonce + 1,
+                got: tx.nonce,
+            });
+        }
+        // Only the amount moves here; the metered fee is charged
+        // separately by block execution.
+        {
+            let entry = accounts.get_mut(&tx.sender).expect("sender exists");
+            if is_native(&tx.denom) {
+                entry.balance -= tx.amount;
+            } else {
+                *entry.balances.entry(tx.denom.clone()).or_default() -= tx.amount;
+            }
+            entry.nonce = tx.nonce;
+        }
+        let recipient = accounts.entry(tx.recipient.clone()).or_default();
+        if is_native(&tx.denom) {
+            recipient.balance += tx.amount;
+        } else {
+            *recipient.balances.entry(tx.denom.clone()).or_default() += tx.amount;
+        }
+        drop(accounts);
+        self.mark_dirty(&tx.sender).await;
+        self.mark_dirty(&tx.recipient).await;
+        Ok(())
+    }
+
+    /// Sparse Merkle tree over account state as of `height`, for proofs
+    /// against historical state roots.
+    pub async fn state_tree_at(&self, height: u64) -> SparseMerkleTree {
+        let versions = self.versions.read().await;
+        let mut tree = SparseMerkleTree::new();
+        for (address, history) in versions.iter() {
+            if let Some((_, state)) = history.iter().rev().find(|(h, _)| *h <= height) {
+                tree.insert(
+                    address.as_bytes(),
+                    &serde_json::to_vec(state).unwrap_or_default(),
+                );
+            }
+        }
+        tree
+    }
+
+    /// Sparse Merkle tree over all accounts: address to encoded state.
+    /// Proofs from this tree verify against [`Self::state_root`].
+    pub async fn state_tree(&self) -> SparseMerkleTree {
+        let accounts = self.accounts.read().await;
+        let mut tree = SparseMerkleTree::new();
+        for (address, state) in accounts.iter() {
+            tree.insert(
+                address.as_bytes(),
+                &serde_json::to_vec(state).unwrap_or_default(),
+            );
+        }
+        tree
+    }
+
+    /// Root hash over all accounts.
+    pub async fn state_root(&self) -> Vec<u8> {
+        self.state_tree().await.root()
+    }
+}
+
+impl Default for StateSecurityManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Holds multisig transactions while their partial signatures are
+/// collected, keyed by transaction hash. Once enough members have
+/// signed, the caller broadcasts the assembled transaction.
+#[derive(Default)]
+pub struct MultisigCollector {
+    pending: RwLock<HashMap<String, Transaction>>,
+}
+
+impl MultisigCollector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stage an unsigned multisig transaction; returns its hash, the
+    /// handle members sign against.
+    pub async fn stage(&self, tx: Transaction) -> String {
+        let hash = tx.hash();
+        self.pending.write().await.insert(hash.clone(), tx);
+        hash
+    }
+
+    /// Append a partial signature, returning the updated transaction.
+    pub async fn add_signature(&self, hash: &str, signature: Vec<u8>) -> Option<Transaction> {
+        let mut pending = self.pending.write().await;
+        let tx = pending.get_mut(hash)?;
+        if !tx.signatures.contains(&signature) {
+            tx.signatures.push(signature);
+        }
+        Some(tx.clone())
+    }
+
+    pub async fn get(&self, hash: &str) -> Option<Transaction> {
+        self.pending.read().await.get(hash).cloned()
+    }
+
+    /// Remove a staged transaction, typically after broadcast.
+    pub async fn remove(&self, hash: &str) -> Option<Transaction> {
+        self.pending.write().await.remove(hash)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn account_proofs_verify_against_state_root() {
+        let state = StateSecurityManager::new();
+        state.set_balance("alice", 1000).await;
+        let tree = state.state_tree().await;
+        let root = state.state_root().await;
+        let account = state.get_account("alice").await.unwrap();
+        let value = serde_json::to_vec(&account).unwrap();
+        assert!(tree.prove(b"alice").verify(&root, b"alice", Some(&value)));
+        assert!(tree.prove(b"bob").verify(&root, b"bob", None));
+    }
+
+    #[tokio::test]
+    async fn historical_queries_see_state_as_of_height() {
+        let state = StateSecurityManager::new();
+        state.set_balance("alice", 100).await;
+        state.commit_version(1).await;
+        state.set_balance("alice", 250).await;
+        state.set_balance("bob", 10).await;
+        state.commit_version(2).await;
+
+        assert!(state.get_account_at("alice", 0).await.is_none());
+        assert_eq!(state.get_account_at("alice", 1).await.unwrap().balance, 100);
+        assert_eq!(state.get_account_at("alice", 2).await.unwrap().balance, 250);
+        // Heights past the head resolve to the latest version.
+        assert_eq!(state.get_account_at("alice", 9).await.unwrap().balance, 250);
+        assert!(state.get_account_at("bob", 1).await.is_none());
+
+        // Historical proofs verify against the historical tree's root.
+        let tree = state.state_tree_at(1).await;
+        let account = state.get_account_at("alice", 1).await.unwrap();
+        let value = serde_json::to_vec(&account).unwrap();
+        assert!(tree.prove(b"alice").verify(&tree.root(), b"alice", Some(&value)));
+        assert!(tree.prove(b"bob").verify(&tree.root(), b"bob", None));
+    }
+
+    #[tokio::test]
+    async fn multisig_spends_only_at_threshold() {
+        use crate::security::SecurityManager;
+
+        let (key_a, key_b, key_c) = (
+            SecurityManager::new(),
+            SecurityManager::new(),
+            SecurityManager::new(),
+        );
+        let params = MultisigParams {
+            public_keys: vec![key_a.public_key(), key_b.public_key(), key_c.public_key()],
+            threshold: 2,
+        };
+        let state = StateSecurityManager::new();
+        // Bad thresholds are rejected.
+        assert!(state
+            .register_multisig(MultisigParams {
+                public_keys: params.public_keys.clone(),
+                threshold: 4,
+            })
+            .await
+            .is_err());
+        let address = state.register_multisig(params).await.unwrap();
+        state.set_balance(&address, 1000).await;
+
+        let mut tx = Transaction::new(address.clone(), "bob".into(), 100, 1, 0, 0, vec![]);
+        let doc = tx.signing_bytes();
+        // One signature (even repeated) is below the threshold.
+        tx.signatures = vec![key_a.sign(&doc), key_a.sign(&doc)];
+        assert!(state.apply_transaction(&tx).await.is_err());
+        tx.signatures.push(key_c.sign(&doc));
+        state.apply_transaction(&tx).await.unwrap();
+        assert_eq!(state.get_account("bob").await.unwrap().balance, 100);
+    }
+
+    #[tokio::test]
+    async fn transfer_moves_balance_and_nonce() {
+        let state = StateSecurityManager::new();
+        state.set_balance("alice", 1000).await;
+        let tx = Transaction::new("alice".into(), "bob".into(), 100, 1, 0, 0, vec![]);
+        state.apply_transaction(&tx).await.unwrap();
+        assert_eq!(state.get_account("bob").await.unwrap().balance, 100);
+        let alice = state.get_account("alice").await.unwrap();
+        assert_eq!(alice.balance, 900);
+        assert_eq!(alice.nonce, 1);
+    }
+
+    #[tokio::test]
+    async fn non_native_transfers_move_through_the_denom_map() {
+        let state = StateSecurityManager::new();
+        state.credit_denom("alice", "usdx", 500).await;
+        let mut tx = Transaction::new("alice".into(), "bob".into(), 200, 1, 0, 0, vec![]);
+        tx.denom = "usdx".into();
+        state.apply_transaction(&tx).await.unwrap();
+        assert_eq!(state.get_account("bob").await.unwrap().balance_of("usdx"), 200);
+        let alice = state.get_account("alice").await.unwrap();
+        assert_eq!(alice.balance_of("usdx"), 300);
+        // The native balance is untouched by a foreign-denom transfer.
+        assert_eq!(alice.balance, 0);
+        // Spending more than the denom balance fails.
+        let mut over = Transaction::new("alice".into(), "bob".into(), 400, 2, 0, 0, vec![]);
+        over.denom = "usdx".into();
+        assert!(state.apply_transaction(&over).await.is_err());
+    }
+}
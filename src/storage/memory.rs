@@ -0,0 +1,59 @@
+//! In-memory [`KvStore`] backend for tests and throwaway devnets.
+
+use std::collections::BTreeMap;
+use std::sync::RwLock;
+
+use super::{KvEntry, KvStore, StorageError, WriteBatch};
+
+/// A volatile store over a sorted map; contents vanish on drop.
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: RwLock<BTreeMap<Vec<u8>, Vec<u8>>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KvStore for MemoryStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self.entries.read().unwrap().get(key).cloned())
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        self.entries
+            .write()
+            .unwrap()
+            .insert(key.to_vec(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), StorageError> {
+        self.entries.write().unwrap().remove(key);
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<KvEntry>, StorageError> {
+        Ok(self
+            .entries
+            .read()
+            .unwrap()
+            .range(prefix.to_vec()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+
+    fn apply(&self, batch: WriteBatch) -> Result<(), StorageError> {
+        let mut entries = self.entries.write().unwrap();
+        for (key, value) in batch.into_ops() {
+            match value {
+                Some(value) => entries.insert(key, value),
+                None => entries.remove(&key),
+            };
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+//! RocksDB [`KvStore`] backend for production deployments. Only built
+//! with the `rocksdb` feature, since it links a native library.
+
+use std::path::Path;
+
+use rocksdb::{Direction, IteratorMode, DB};
+
+use super::{KvEntry, KvStore, StorageError, WriteBatch};
+
+pub struct RocksStore {
+    db: DB,
+}
+
+impl RocksStore {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        DB::open_default(path)
+            .map(|db| Self { db })
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+}
+
+impl KvStore for RocksStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        self.db
+            .get(key)
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        self.db
+            .put(key, value)
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), StorageError> {
+        self.db
+            .delete(key)
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<KvEntry>, StorageError> {
+        let mut entries = Vec::new();
+        for item in self
+            .db
+            .iterator(IteratorMode::From(prefix, Direction::Forward))
+        {
+            let (key, value) = item.map_err(|e| StorageError::Backend(e.to_string()))?;
+            if !key.starts_with(prefix) {
+                break;
+            }
+            entries.push((key.to_vec(), value.to_vec()));
+        }
+        Ok(entries)
+    }
+
+    fn apply(&self, batch: WriteBatch) -> Result<(), StorageError> {
+        let mut rocks_batch = rocksdb::WriteBatch::default();
+        for (key, value) in batch.into_ops() {
+            match value {
+                Some(value) => rocks_batch.put(key, value),
+                None => rocks_batch.delete(key),
+            }
+        }
+        self.db
+            .write(rocks_batch)
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn flush(&self) -> Result<(), StorageError> {
+        self.db
+            .flush()
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+}
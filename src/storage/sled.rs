@@ -0,0 +1,71 @@
+//! Sled [`KvStore`] backend: embedded, pure Rust, the default.
+
+use std::path::Path;
+
+use super::{KvEntry, KvStore, StorageError, WriteBatch};
+
+pub struct SledStore {
+    db: sled::Db,
+}
+
+impl SledStore {
+    pub fn open(path: &Path) -> Result<Self, StorageError> {
+        sled::open(path)
+            .map(|db| Self { db })
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+}
+
+impl KvStore for SledStore {
+    fn get(&self, key: &[u8]) -> Result<Option<Vec<u8>>, StorageError> {
+        self.db
+            .get(key)
+            .map(|value| value.map(|v| v.to_vec()))
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn put(&self, key: &[u8], value: &[u8]) -> Result<(), StorageError> {
+        self.db
+            .insert(key, value)
+            .map(|_| ())
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn delete(&self, key: &[u8]) -> Result<(), StorageError> {
+        self.db
+            .remove(key)
+            .map(|_| ())
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn scan_prefix(&self, prefix: &[u8]) -> Result<Vec<KvEntry>, StorageError> {
+        self.db
+            .scan_prefix(prefix)
+            .map(|entry| {
+                entry
+                    .map(|(key, value)| (key.to_vec(), value.to_vec()))
+                    .map_err(|e| StorageError::Backend(e.to_string()))
+            })
+            .collect()
+    }
+
+    fn apply(&self, batch: WriteBatch) -> Result<(), StorageError> {
+        let mut sled_batch = sled::Batch::default();
+        for (key, value) in batch.into_ops() {
+            match value {
+                Some(value) => sled_batch.insert(key, value),
+                None => sled_batch.remove(key),
+            }
+        }
+        self.db
+            .apply_batch(sled_batch)
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn flush(&self) -> Result<(), StorageError> {
+        self.db
+            .flush()
+            .map(|_| ())
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+}
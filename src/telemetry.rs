@@ -0,0 +1,72 @@
+//! Tracing setup: structured spans across consensus, networking, and
+//! storage, with `log`-macro records bridged into the same subscriber.
+//!
+//! With the `otlp` feature enabled and `OTEL_EXPORTER_OTLP_ENDPOINT`
+//! set, spans are also exported over OTLP so a block's lifecycle can be
+//! followed across modules in an external trace backend.
+
+use std::sync::OnceLock;
+
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{reload, EnvFilter, Registry};
+
+/// Handle for swapping the filter at runtime (admin log-level change).
+static FILTER_HANDLE: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+/// Install the global subscriber. Filtering follows `RUST_LOG`, with
+/// `info` as the default level.
+pub fn init() {
+    let _ = tracing_log::LogTracer::init();
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info"));
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = FILTER_HANDLE.set(handle);
+    let registry = tracing_subscriber::registry()
+        .with(filter)
+        .with(tracing_subscriber::fmt::layer());
+    #[cfg(feature = "otlp")]
+    if let Some(layer) = otlp_layer() {
+        registry.with(layer).init();
+        return;
+    }
+    registry.init();
+}
+
+/// Swap the active filter for new directives (e.g. `"debug"` or
+/// `"info,artha_fs::consensus=trace"`) without a restart.
+pub fn set_log_level(directives: &str) -> Result<(), String> {
+    let filter = EnvFilter::try_new(directives).map_err(|err| err.to_string())?;
+    FILTER_HANDLE
+        .get()
+        .ok_or_else(|| "telemetry not initialized".to_string())?
+        .reload(filter)
+        .map_err(|err| err.to_string())
+}
+
+/// An OTLP export layer, when an endpoint is configured. Returning
+/// `None` keeps plain stdout logging for nodes without a collector.
+#[cfg(feature = "otlp")]
+fn otlp_layer<S>() -> Option<tracing_opentelemetry::OpenTelemetryLayer<S, opentelemetry_sdk::trace::Tracer>>
+where
+    S: tracing::Subscriber + for<'a> tracing_subscriber::registry::LookupSpan<'a>,
+{
+    use opentelemetry::trace::TracerProvider;
+
+    std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT").ok()?;
+    let exporter = opentelemetry_otlp::SpanExporter::builder()
+        .with_tonic()
+        .build()
+        .map_err(|err| log::warn!("otlp exporter unavailable: {err}"))
+        .ok()?;
+    let provider = opentelemetry_sdk::trace::SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(
+            opentelemetry_sdk::Resource::builder()
+                .with_service_name("artha_fs")
+                .build(),
+        )
+        .build();
+    let tracer = provider.tracer("artha_fs");
+    opentelemetry::global::set_tracer_provider(provider);
+    Some(tracing_opentelemetry::layer().with_tracer(tracer))
+}
@@ -0,0 +1,160 @@
+//! User-issued tokens: accounts create denominations, mint and burn
+//! within configured limits, and transfer them through the multi-asset
+//! ledger. This module tracks issuance metadata; balances live on the
+//! accounts themselves.
+
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::types::fees::NATIVE_DENOM;
+use crate::types::Transaction;
+
+/// Token operations carried in a transaction's data payload as JSON.
+/// A non-token payload simply fails to parse. Transfers of an issued
+/// token use the regular transfer path with the transaction's `denom`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TokenTx {
+    /// Register a new denomination issued by the sender. A zero
+    /// `max_supply` means unlimited.
+    CreateDenom { denom: String, max_supply: u64 },
+    /// Mint `amount` of the sender's denomination to `to`.
+    Mint {
+        denom: String,
+        to: String,
+        amount: u64,
+    },
+    /// Burn `amount` of the sender's denomination from its own balance.
+    Burn { denom: String, amount: u64 },
+}
+
+impl TokenTx {
+    /// Parse a token operation out of a transaction, if it carries one.
+    pub fn parse(tx: &Transaction) -> Option<Self> {
+        serde_json::from_slice(&tx.data).ok()
+    }
+}
+
+/// Issuance metadata of one user-created denomination.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TokenMeta {
+    pub denom: String,
+    /// Account that created the denomination; only it may mint or burn.
+    pub issuer: String,
+    /// Issuance cap; zero means unlimited.
+    pub max_supply: u64,
+    /// Amount currently in circulation.
+    pub supply: u64,
+}
+
+/// Registry of user-issued denominations.
+pub struct TokenRegistry {
+    tokens: RwLock<HashMap<String, TokenMeta>>,
+}
+
+impl TokenRegistry {
+    pub fn new() -> Self {
+        Self {
+            tokens: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Register a denomination. The native asset and existing denoms
+    /// are off limits.
+    pub async fn create(
+        &self,
+        issuer: &str,
+        denom: &str,
+        max_supply: u64,
+    ) -> Result<(), String> {
+        if denom.is_empty() || denom == NATIVE_DENOM {
+            return Err(format!("denom {denom:?} is reserved"));
+        }
+        let mut tokens = self.tokens.write().await;
+        if tokens.contains_key(denom) {
+            return Err(format!("denom {denom} already exists"));
+        }
+        tokens.insert(
+            denom.to_string(),
+            TokenMeta {
+                denom: denom.to_string(),
+                issuer: issuer.to_string(),
+                max_supply,
+                supply: 0,
+            },
+        );
+        Ok(())
+    }
+
+    /// Record a mint by the issuer, enforcing the supply cap. The
+    /// caller credits the recipient on success.
+    pub async fn mint(&self, issuer: &str, denom: &str, amount: u64) -> Result<(), String> {
+        let mut tokens = self.tokens.write().await;
+        let token = tokens
+            .get_mut(denom)
+            .ok_or_else(|| format!("unknown denom {denom}"))?;
+        if token.issuer != issuer {
+            return Err(format!("{issuer} is not the issuer of {denom}"));
+        }
+        let minted = token.supply.saturating_add(amount);
+        if token.max_supply != 0 && minted > token.max_supply {
+            return Err(format!(
+                "mint exceeds max supply {} of {denom}",
+                token.max_supply
+            ));
+        }
+        token.supply = minted;
+        Ok(())
+    }
+
+    /// Record a burn by the issuer. The caller debits the burned
+    /// balance first.
+    pub async fn burn(&self, issuer: &str, denom: &str, amount: u64) -> Result<(), String> {
+        let mut tokens = self.tokens.write().await;
+        let token = tokens
+            .get_mut(denom)
+            .ok_or_else(|| format!("unknown denom {denom}"))?;
+        if token.issuer != issuer {
+            return Err(format!("{issuer} is not the issuer of {denom}"));
+        }
+        token.supply = token.supply.saturating_sub(amount);
+        Ok(())
+    }
+
+    pub async fn get(&self, denom: &str) -> Option<TokenMeta> {
+        self.tokens.read().await.get(denom).cloned()
+    }
+
+    pub async fn all(&self) -> Vec<TokenMeta> {
+        let mut tokens: Vec<TokenMeta> = self.tokens.read().await.values().cloned().collect();
+        tokens.sort_by(|a, b| a.denom.cmp(&b.denom));
+        tokens
+    }
+}
+
+impl Default for TokenRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn issuance_respects_issuer_and_supply_cap() {
+        let registry = TokenRegistry::new();
+        registry.create("alice", "usdx", 1_000).await.unwrap();
+        assert!(registry.create("bob", "usdx", 0).await.is_err());
+        assert!(registry.create("bob", NATIVE_DENOM, 0).await.is_err());
+
+        registry.mint("alice", "usdx", 600).await.unwrap();
+        assert!(registry.mint("bob", "usdx", 1).await.is_err());
+        assert!(registry.mint("alice", "usdx", 500).await.is_err());
+        registry.burn("alice", "usdx", 100).await.unwrap();
+        assert_eq!(registry.get("usdx").await.unwrap().supply, 500);
+    }
+}
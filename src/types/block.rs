@@ -0,0 +1,118 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::transaction::{now_unix, Transaction};
+use crate::consensus::evidence::{compute_evidence_root, Evidence};
+use crate::consensus::Commit;
+
+/// Header committed to by consensus votes.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BlockHeader {
+    pub height: u64,
+    pub timestamp: u64,
+    /// Hash of the previous block.
+    pub prev_hash: Vec<u8>,
+    /// Root of the application state after executing the *previous*
+    /// block (deferred execution, like Tendermint's `app_hash`): block N
+    /// commits the result of block N-1's transactions, so proposers
+    /// never execute speculatively. Block 1 carries the genesis root.
+    pub state_root: Vec<u8>,
+    /// Merkle root of the transactions in this block.
+    pub tx_root: Vec<u8>,
+    /// Hash of the validator set that may sign this block.
+    pub validator_hash: Vec<u8>,
+    /// Hash of the consensus parameters in effect.
+    pub consensus_hash: Vec<u8>,
+    /// Root over the misbehavior evidence committed in this block.
+    #[serde(default)]
+    pub evidence_root: Vec<u8>,
+    /// Hash of the commit certificate that finalized the previous
+    /// block; empty for the first block.
+    #[serde(default)]
+    pub last_commit_hash: Vec<u8>,
+    /// Address of the proposer.
+    pub proposer: String,
+}
+
+/// A block of transactions.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Block {
+    pub header: BlockHeader,
+    pub transactions: Vec<Transaction>,
+    /// Misbehavior evidence committed alongside the transactions.
+    #[serde(default)]
+    pub evidence: Vec<Evidence>,
+    /// The +2/3 precommit certificate for the previous block, carried
+    /// so finality of historical blocks is verifiable from the chain
+    /// alone. `None` only for the first block. Boxed to keep the block
+    /// small in consensus message enums.
+    #[serde(default)]
+    pub last_commit: Option<Box<Commit>>,
+}
+
+impl Block {
+    pub fn new(
+        height: u64,
+        prev_hash: Vec<u8>,
+        state_root: Vec<u8>,
+        proposer: String,
+        transactions: Vec<Transaction>,
+    ) -> Self {
+        let tx_root = compute_tx_root(&transactions);
+        Self {
+            header: BlockHeader {
+                height,
+                timestamp: now_unix(),
+                prev_hash,
+                state_root,
+                tx_root,
+                validator_hash: vec![0u8; 32],
+                consensus_hash: vec![0u8; 32],
+                evidence_root: compute_evidence_root(&[]),
+                last_commit_hash: Vec::new(),
+                proposer,
+            },
+            transactions,
+            evidence: Vec::new(),
+            last_commit: None,
+        }
+    }
+
+    /// Attach committed evidence, updating the header's evidence root.
+    pub fn with_evidence(mut self, evidence: Vec<Evidence>) -> Self {
+        self.header.evidence_root = compute_evidence_root(&evidence);
+        self.evidence = evidence;
+        self
+    }
+
+    /// Attach the previous block's commit certificate, updating the
+    /// header's last-commit hash.
+    pub fn with_last_commit(mut self, commit: Option<Commit>) -> Self {
+        self.header.last_commit_hash = commit.as_ref().map(Commit::hash).unwrap_or_default();
+        self.last_commit = commit.map(Box::new);
+        self
+    }
+
+    /// Hash of the block header.
+    pub fn hash(&self) -> Vec<u8> {
+        self.header.hash()
+    }
+}
+
+impl BlockHeader {
+    /// Hash identifying the block. Computed from the header alone so
+    /// light clients can derive it without the block body.
+    pub fn hash(&self) -> Vec<u8> {
+        let bytes = bincode::serialize(self).unwrap_or_default();
+        Sha256::digest(&bytes).to_vec()
+    }
+}
+
+/// Naive transaction root: hash of concatenated transaction hashes.
+pub fn compute_tx_root(transactions: &[Transaction]) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    for tx in transactions {
+        hasher.update(tx.hash().as_bytes());
+    }
+    hasher.finalize().to_vec()
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::security::scheme::SignatureScheme;
+use crate::types::Transaction;
+
+/// Current envelope format version. Bumped whenever the canonical
+/// encoding changes shape, so signatures never verify across formats.
+pub const TX_ENVELOPE_VERSION: u32 = 1;
+
+/// What the transaction does: the fields every signer commits to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxBody {
+    pub sender: String,
+    pub recipient: String,
+    pub amount: u64,
+    pub denom: String,
+    pub nonce: u64,
+    pub data: Vec<u8>,
+    pub timestamp: u64,
+}
+
+/// How the transaction is paid for and authenticated.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AuthInfo {
+    pub gas_limit: u64,
+    pub gas_price: u64,
+    pub fee_denom: String,
+    pub fee_payer: String,
+    pub scheme: SignatureScheme,
+}
+
+/// A versioned transaction envelope: body, auth info, and signatures,
+/// with one canonical byte encoding used for both hashing and signing.
+///
+/// The canonical encoding is bincode over this struct. Field order is
+/// fixed by the struct definition, integers are little-endian and
+/// fixed-width, and no maps are involved, so the same envelope always
+/// produces the same bytes on every node — unlike the JSON encoding it
+/// replaces, which made no ordering guarantees.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TxEnvelope {
+    pub version: u32,
+    pub body: TxBody,
+    pub auth: AuthInfo,
+    /// The sender's signature, then any multisig partials, then the fee
+    /// payer's signature (empty entries are omitted).
+    pub signatures: Vec<Vec<u8>>,
+}
+
+impl TxEnvelope {
+    /// The canonical bytes of the full envelope, signatures included.
+    /// The transaction hash is the SHA-256 of these bytes.
+    pub fn canonical_bytes(&self) -> Vec<u8> {
+        bincode::serialize(self).unwrap_or_default()
+    }
+
+    /// The canonical bytes every signer commits to: the envelope with
+    /// its signatures stripped, so a signature never covers itself.
+    pub fn sign_bytes(&self) -> Vec<u8> {
+        let unsigned = Self {
+            version: self.version,
+            body: self.body.clone(),
+            auth: self.auth.clone(),
+            signatures: Vec::new(),
+        };
+        unsigned.canonical_bytes()
+    }
+
+    /// Hash of the canonical encoding, as lowercase hex.
+    pub fn hash(&self) -> String {
+        hex::encode(Sha256::digest(self.canonical_bytes()))
+    }
+}
+
+impl From<&Transaction> for TxEnvelope {
+    fn from(tx: &Transaction) -> Self {
+        let mut signatures = Vec::new();
+        if !tx.signature.is_empty() {
+            signatures.push(tx.signature.clone());
+        }
+        signatures.extend(tx.signatures.iter().filter(|s| !s.is_empty()).cloned());
+        if !tx.fee_payer_signature.is_empty() {
+            signatures.push(tx.fee_payer_signature.clone());
+        }
+        Self {
+            version: TX_ENVELOPE_VERSION,
+            body: TxBody {
+                sender: tx.sender.clone(),
+                recipient: tx.recipient.clone(),
+                amount: tx.amount,
+                denom: tx.denom.clone(),
+                nonce: tx.nonce,
+                data: tx.data.clone(),
+                timestamp: tx.timestamp,
+            },
+            auth: AuthInfo {
+                gas_limit: tx.gas_limit,
+                gas_price: tx.gas_price,
+                fee_denom: tx.fee_denom.clone(),
+                fee_payer: tx.fee_payer.clone(),
+                scheme: tx.scheme,
+            },
+            signatures,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn canonical_bytes_are_stable_and_signature_independent() {
+        let mut tx = Transaction::new("alice".into(), "bob".into(), 10, 1, 21000, 1, vec![7]);
+        tx.timestamp = 1_700_000_000;
+        let unsigned = TxEnvelope::from(&tx);
+        assert_eq!(unsigned.canonical_bytes(), unsigned.canonical_bytes());
+
+        // Signing changes the hash but not the bytes being signed.
+        tx.signature = vec![1; 64];
+        let signed = TxEnvelope::from(&tx);
+        assert_eq!(signed.sign_bytes(), unsigned.sign_bytes());
+        assert_ne!(signed.hash(), unsigned.hash());
+
+        // Any body change alters both.
+        tx.amount = 11;
+        let altered = TxEnvelope::from(&tx);
+        assert_ne!(altered.sign_bytes(), signed.sign_bytes());
+        assert_ne!(altered.hash(), signed.hash());
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::types::Transaction;
+
+/// Denomination of the chain's native asset. Transactions with an empty
+/// `fee_denom` pay fees in this denomination.
+pub const NATIVE_DENOM: &str = "artha";
+
+/// Rates are expressed in parts-per-million of the native asset so fee
+/// comparisons stay integer-only and deterministic across nodes.
+pub const RATE_SCALE: u64 = 1_000_000;
+
+/// Fee-grant operations carried in a transaction's data payload as
+/// JSON. The sender is the granter; a non-grant payload simply fails
+/// to parse.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum FeeGrantTx {
+    /// Allow `grantee` to spend up to `allowance` of the sender's
+    /// native balance on fees.
+    GrantAllowance { grantee: String, allowance: u64 },
+    /// Remove any remaining allowance for `grantee`.
+    RevokeAllowance { grantee: String },
+}
+
+impl FeeGrantTx {
+    /// Parse a fee-grant operation out of a transaction, if it carries
+    /// one.
+    pub fn parse(tx: &Transaction) -> Option<Self> {
+        serde_json::from_slice(&tx.data).ok()
+    }
+}
+
+/// Converts fee denominations into native-asset terms for prioritization.
+pub trait FeeOracle: Send + Sync {
+    /// Native value of one unit of `denom`, in parts-per-million of the
+    /// native asset. `None` means the denomination is unknown.
+    fn rate_to_native(&self, denom: &str) -> Option<u64>;
+}
+
+/// A fee oracle backed by a fixed rate table, suitable for devnets and
+/// as a default until a price feed module exists.
+pub struct FixedRateOracle {
+    rates: HashMap<String, u64>,
+}
+
+impl FixedRateOracle {
+    /// An oracle that only knows the native denomination at par.
+    pub fn native_only() -> Self {
+        let mut rates = HashMap::new();
+        rates.insert(NATIVE_DENOM.to_string(), RATE_SCALE);
+        Self { rates }
+    }
+
+    pub fn with_rate(mut self, denom: &str, rate_ppm: u64) -> Self {
+        self.rates.insert(denom.to_string(), rate_ppm);
+        self
+    }
+}
+
+impl FeeOracle for FixedRateOracle {
+    fn rate_to_native(&self, denom: &str) -> Option<u64> {
+        self.rates.get(denom).copied()
+    }
+}
+
+/// Consensus-level fee parameters: which denominations a block may pay
+/// fees in, and how they convert for mempool prioritization.
+pub struct FeePolicy {
+    /// Denominations acceptable for fees. The native denomination is
+    /// always acceptable.
+    accepted_denoms: Vec<String>,
+    oracle: Box<dyn FeeOracle>,
+}
+
+impl FeePolicy {
+    pub fn new(accepted_denoms: Vec<String>, oracle: Box<dyn FeeOracle>) -> Self {
+        Self {
+            accepted_denoms,
+            oracle,
+        }
+    }
+
+    /// A policy accepting only the native denomination.
+    pub fn native_only() -> Self {
+        Self::new(Vec::new(), Box::new(FixedRateOracle::native_only()))
+    }
+
+    /// Whether `denom` may be used to pay fees on this chain.
+    pub fn is_acceptable(&self, denom: &str) -> bool {
+        denom.is_empty()
+            || denom == NATIVE_DENOM
+            || self.accepted_denoms.iter().any(|d| d == denom)
+    }
+
+    /// Effective gas price in native-asset terms, used to order the
+    /// mempool. Unknown denominations sort last.
+    pub fn effective_gas_price(&self, tx: &Transaction) -> u64 {
+        let denom = if tx.fee_denom.is_empty() {
+            NATIVE_DENOM
+        } else {
+            tx.fee_denom.as_str()
+        };
+        match self.oracle.rate_to_native(denom) {
+            Some(rate) => tx.gas_price.saturating_mul(rate) / RATE_SCALE,
+            None => 0,
+        }
+    }
+}
+
+/// A denomination and amount, shared by fee and multi-asset handling.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Coin {
+    pub denom: String,
+    pub amount: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alternate_denoms_convert_for_prioritization() {
+        let oracle = FixedRateOracle::native_only().with_rate("usdx", RATE_SCALE / 2);
+        let policy = FeePolicy::new(vec!["usdx".into()], Box::new(oracle));
+        assert!(policy.is_acceptable(""));
+        assert!(policy.is_acceptable("usdx"));
+        assert!(!policy.is_acceptable("doge"));
+
+        let mut native = Transaction::new("a".into(), "b".into(), 1, 1, 21000, 10, vec![]);
+        let mut usdx = native.clone();
+        usdx.fee_denom = "usdx".into();
+        usdx.gas_price = 30;
+        native.gas_price = 10;
+        assert_eq!(policy.effective_gas_price(&native), 10);
+        assert_eq!(policy.effective_gas_price(&usdx), 15);
+    }
+}
@@ -0,0 +1,60 @@
+//! Gas accounting for transaction and contract execution.
+
+/// Base cost charged to every transaction.
+pub const TX_BASE_GAS: u64 = 21_000;
+/// Additional cost of moving balance to a recipient.
+pub const TRANSFER_GAS: u64 = 5_000;
+/// Cost per byte of deployed contract code.
+pub const CODE_BYTE_GAS: u64 = 10;
+
+/// Meters gas against a transaction's `gas_limit` and aborts execution
+/// on exhaustion.
+#[derive(Debug)]
+pub struct GasMeter {
+    limit: u64,
+    used: u64,
+}
+
+impl GasMeter {
+    pub fn new(limit: u64) -> Self {
+        Self { limit, used: 0 }
+    }
+
+    /// Charge `amount`; fails once the limit would be exceeded. The
+    /// meter is left saturated at the limit so `used` reflects the full
+    /// charge against the sender.
+    pub fn charge(&mut self, amount: u64) -> Result<(), String> {
+        if self.used.saturating_add(amount) > self.limit {
+            self.used = self.limit;
+            return Err(format!(
+                "out of gas: limit {} exceeded",
+                self.limit
+            ));
+        }
+        self.used += amount;
+        Ok(())
+    }
+
+    pub fn used(&self) -> u64 {
+        self.used
+    }
+
+    pub fn remaining(&self) -> u64 {
+        self.limit - self.used
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn meter_aborts_on_exhaustion() {
+        let mut meter = GasMeter::new(100);
+        meter.charge(60).unwrap();
+        assert_eq!(meter.remaining(), 40);
+        assert!(meter.charge(50).is_err());
+        // A failed charge saturates the meter at the limit.
+        assert_eq!(meter.used(), 100);
+    }
+}
@@ -0,0 +1,138 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::{broadcast, RwLock};
+
+use super::transaction::now_unix;
+
+/// Lifecycle states a transaction moves through on this node.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum TxStatus {
+    /// Seen by the node (API or gossip) but not yet validated.
+    Received,
+    /// Passed admission checks and entered the mempool.
+    Checked,
+    /// Broadcast to peers.
+    Gossiped,
+    /// Included in a proposed block.
+    Proposed,
+    /// Included in a committed block.
+    Committed { height: u64 },
+    /// Rejected or failed execution.
+    Failed { reason: String },
+    /// Dropped from the mempool without being committed.
+    Evicted,
+}
+
+/// A single status change, pushed to WebSocket subscribers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxStatusUpdate {
+    pub hash: String,
+    #[serde(flatten)]
+    pub status: TxStatus,
+    pub timestamp: u64,
+}
+
+/// Full lifecycle record for one transaction.
+#[derive(Debug, Clone, Serialize)]
+pub struct TxLifecycle {
+    pub hash: String,
+    #[serde(flatten)]
+    pub status: TxStatus,
+    /// Every transition with its timestamp, oldest first.
+    pub history: Vec<(TxStatus, u64)>,
+}
+
+/// Tracks transactions through their lifecycle and fans status changes out
+/// to subscribers.
+pub struct TxTracker {
+    entries: RwLock<HashMap<String, TxLifecycle>>,
+    updates: broadcast::Sender<TxStatusUpdate>,
+    max_entries: usize,
+}
+
+impl TxTracker {
+    pub fn new(max_entries: usize) -> Self {
+        let (updates, _) = broadcast::channel(256);
+        Self {
+            entries: RwLock::new(HashMap::new()),
+            updates,
+            max_entries,
+        }
+    }
+
+    /// Record a status transition for a transaction.
+    pub async fn record(&self, hash: &str, status: TxStatus) {
+        let timestamp = now_unix();
+        {
+            let mut entries = self.entries.write().await;
+            if !entries.contains_key(hash) && entries.len() >= self.max_entries {
+                // Evict the record that has been idle the longest.
+                if let Some(stalest) = entries
+                    .values()
+                    .min_by_key(|e| e.history.last().map(|(_, t)| *t).unwrap_or(0))
+                    .map(|e| e.hash.clone())
+                {
+                    entries.remove(&stalest);
+                }
+            }
+            let entry = entries
+                .entry(hash.to_string())
+                .or_insert_with(|| TxLifecycle {
+                    hash: hash.to_string(),
+                    status: status.clone(),
+                    history: Vec::new(),
+                });
+            entry.status = status.clone();
+            entry.history.push((status.clone(), timestamp));
+        }
+        let _ = self.updates.send(TxStatusUpdate {
+            hash: hash.to_string(),
+            status,
+            timestamp,
+        });
+    }
+
+    pub async fn get(&self, hash: &str) -> Option<TxLifecycle> {
+        self.entries.read().await.get(hash).cloned()
+    }
+
+    /// Subscribe to the stream of status updates.
+    pub fn subscribe(&self) -> broadcast::Receiver<TxStatusUpdate> {
+        self.updates.subscribe()
+    }
+}
+
+impl Default for TxTracker {
+    fn default() -> Self {
+        Self::new(10_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn tracker_records_transitions_in_order() {
+        let tracker = TxTracker::new(10);
+        tracker.record("abc", TxStatus::Received).await;
+        tracker.record("abc", TxStatus::Checked).await;
+        tracker.record("abc", TxStatus::Committed { height: 5 }).await;
+        let record = tracker.get("abc").await.unwrap();
+        assert_eq!(record.status, TxStatus::Committed { height: 5 });
+        assert_eq!(record.history.len(), 3);
+        assert_eq!(record.history[0].0, TxStatus::Received);
+    }
+
+    #[tokio::test]
+    async fn tracker_notifies_subscribers() {
+        let tracker = TxTracker::new(10);
+        let mut rx = tracker.subscribe();
+        tracker.record("abc", TxStatus::Received).await;
+        let update = rx.recv().await.unwrap();
+        assert_eq!(update.hash, "abc");
+        assert_eq!(update.status, TxStatus::Received);
+    }
+}
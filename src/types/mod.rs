@@ -0,0 +1,12 @@
+pub mod block;
+pub mod envelope;
+pub mod fees;
+pub mod gas;
+pub mod lifecycle;
+pub mod transaction;
+
+pub use block::{Block, BlockHeader};
+pub use envelope::{AuthInfo, TxBody, TxEnvelope};
+pub use fees::{Coin, FeeOracle, FeePolicy};
+pub use lifecycle::{TxStatus, TxTracker};
+pub use transaction::{Transaction, TransactionError, TransactionPool};
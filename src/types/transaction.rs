@@ -0,0 +1,601 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tokio::sync::RwLock;
+
+use crate::security::scheme::SignatureScheme;
+use crate::types::fees::FeePolicy;
+
+/// Errors produced while validating or pooling transactions.
+#[derive(Debug, Error)]
+pub enum TransactionError {
+    #[error("invalid signature")]
+    InvalidSignature,
+    #[error("invalid nonce: expected {expected}, got {got}")]
+    InvalidNonce { expected: u64, got: u64 },
+    #[error("duplicate transaction {0}")]
+    Duplicate(String),
+    #[error("transaction pool is full")]
+    PoolFull,
+    #[error("insufficient balance: have {have}, need {need}")]
+    InsufficientBalance { have: u64, need: u64 },
+    #[error("invalid transaction: {0}")]
+    Invalid(String),
+}
+
+/// One operation inside a batched transaction.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Msg {
+    /// Move `amount` of `denom` to `recipient`.
+    Transfer {
+        recipient: String,
+        amount: u64,
+        #[serde(default)]
+        denom: String,
+    },
+    /// A module payload (staking, governance, tokens, contracts),
+    /// executed as if it were the transaction's data.
+    Exec { data: Vec<u8> },
+}
+
+/// Envelope carried in a transaction's data when it batches several
+/// messages under one signature, one nonce, and one fee.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BatchTx {
+    Batch { msgs: Vec<Msg> },
+}
+
+impl BatchTx {
+    /// Parse a batch envelope out of a transaction, if it carries one.
+    pub fn parse(tx: &Transaction) -> Option<Self> {
+        serde_json::from_slice(&tx.data).ok()
+    }
+}
+
+/// A signed value transfer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Transaction {
+    /// Identifier assigned by the submitter.
+    pub id: String,
+    pub sender: String,
+    pub recipient: String,
+    pub amount: u64,
+    /// Denomination of `amount`; empty means the native asset.
+    #[serde(default)]
+    pub denom: String,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub gas_price: u64,
+    /// Denomination the fee is paid in; empty means the native asset.
+    #[serde(default)]
+    pub fee_denom: String,
+    /// Account sponsoring the fee instead of the sender; empty means
+    /// the sender pays. Requires a fee-grant allowance from the payer.
+    #[serde(default)]
+    pub fee_payer: String,
+    /// The fee payer's signature over the transaction.
+    #[serde(default)]
+    pub fee_payer_signature: Vec<u8>,
+    /// Arbitrary payload bytes.
+    pub data: Vec<u8>,
+    /// Scheme `signature` was produced with; defaults to ed25519.
+    #[serde(default)]
+    pub scheme: SignatureScheme,
+    pub signature: Vec<u8>,
+    /// Partial signatures collected for a multisig sender; ignored for
+    /// single-key accounts.
+    #[serde(default)]
+    pub signatures: Vec<Vec<u8>>,
+    pub timestamp: u64,
+}
+
+impl Transaction {
+    pub fn new(
+        sender: String,
+        recipient: String,
+        amount: u64,
+        nonce: u64,
+        gas_limit: u64,
+        gas_price: u64,
+        data: Vec<u8>,
+    ) -> Self {
+        Self {
+            id: String::new(),
+            sender,
+            recipient,
+            amount,
+            denom: String::new(),
+            nonce,
+            gas_limit,
+            gas_price,
+            fee_denom: String::new(),
+            fee_payer: String::new(),
+            fee_payer_signature: Vec::new(),
+            data,
+            scheme: SignatureScheme::default(),
+            signature: Vec::new(),
+            signatures: Vec::new(),
+            timestamp: now_unix(),
+        }
+    }
+
+    /// Hash of the canonical envelope encoding, as lowercase hex.
+    pub fn hash(&self) -> String {
+        crate::types::envelope::TxEnvelope::from(self).hash()
+    }
+
+    /// Bytes covered by the sender's signature: the canonical envelope
+    /// encoding with signatures stripped.
+    pub fn signing_bytes(&self) -> Vec<u8> {
+        crate::types::envelope::TxEnvelope::from(self).sign_bytes()
+    }
+
+    pub fn total_cost(&self) -> u64 {
+        self.amount.saturating_add(self.gas_limit.saturating_mul(self.gas_price))
+    }
+}
+
+pub(crate) fn now_unix() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Number of independently locked mempool shards.
+const SHARD_COUNT: usize = 16;
+
+/// Default ceiling on one encoded transaction; anything larger is
+/// rejected at ingress before it can be gossiped.
+pub const MAX_TX_BYTES: usize = 128 * 1024;
+
+/// Default cap on total encoded bytes across the pool. Admission evicts
+/// the lowest fee-per-byte entries to stay under it.
+pub const MAX_POOL_BYTES: usize = 32 * 1024 * 1024;
+
+/// Encoded size of a transaction, as counted against the byte limits
+/// (the same encoding blocks measure against their size limit).
+fn encoded_size(tx: &Transaction) -> usize {
+    serde_json::to_vec(tx).map(|b| b.len()).unwrap_or(0)
+}
+
+/// Total fee a transaction offers, for fee-per-byte comparisons.
+fn offered_fee(fee_policy: &FeePolicy, tx: &Transaction) -> u64 {
+    fee_policy
+        .effective_gas_price(tx)
+        .saturating_mul(tx.gas_limit)
+}
+
+/// One mempool shard: transactions and nonce watermarks for the senders
+/// that hash into it.
+struct PoolShard {
+    transactions: RwLock<HashMap<String, Transaction>>,
+    /// Highest nonce seen per sender, used for monotonicity checks.
+    nonces: RwLock<HashMap<String, u64>>,
+}
+
+/// In-memory pool of pending transactions awaiting inclusion in a block.
+///
+/// The pool is sharded by sender-address hash so admission for different
+/// senders does not serialize on one lock; block building merges the
+/// shards back into a deterministic fee order.
+pub struct TransactionPool {
+    shards: Vec<PoolShard>,
+    /// Total transactions across all shards.
+    len: AtomicUsize,
+    max_size: usize,
+    /// Which fee denominations are admitted and how they compare.
+    fee_policy: FeePolicy,
+    /// Set during shutdown; a sealed pool admits nothing new.
+    sealed: std::sync::atomic::AtomicBool,
+    /// Lowest effective gas price admitted, in native units. Zero
+    /// admits everything; operators raise it to shed fee spam.
+    min_gas_price: AtomicU64,
+    /// Ledger consulted for stateful admission (balance, spent nonces)
+    /// when attached; `None` keeps admission stateless, as in tests.
+    state: RwLock<Option<Arc<crate::security::state::StateSecurityManager>>>,
+    /// Total encoded bytes currently pooled.
+    bytes: AtomicUsize,
+    /// Largest encoded transaction admitted.
+    max_tx_bytes: AtomicUsize,
+    /// Cap on total pooled bytes; admission evicts the cheapest
+    /// fee-per-byte entries to stay under it.
+    max_pool_bytes: AtomicUsize,
+}
+
+impl TransactionPool {
+    pub fn new(max_size: usize) -> Self {
+        Self::with_fee_policy(max_size, FeePolicy::native_only())
+    }
+
+    pub fn with_fee_policy(max_size: usize, fee_policy: FeePolicy) -> Self {
+        let shards = (0..SHARD_COUNT)
+            .map(|_| PoolShard {
+                transactions: RwLock::new(HashMap::new()),
+                nonces: RwLock::new(HashMap::new()),
+            })
+            .collect();
+        Self {
+            shards,
+            len: AtomicUsize::new(0),
+            max_size,
+            fee_policy,
+            sealed: std::sync::atomic::AtomicBool::new(false),
+            min_gas_price: AtomicU64::new(0),
+            state: RwLock::new(None),
+            bytes: AtomicUsize::new(0),
+            max_tx_bytes: AtomicUsize::new(MAX_TX_BYTES),
+            max_pool_bytes: AtomicUsize::new(MAX_POOL_BYTES),
+        }
+    }
+
+    /// Override the byte-size limits, e.g. from node configuration.
+    pub fn set_byte_limits(&self, max_tx_bytes: usize, max_pool_bytes: usize) {
+        self.max_tx_bytes.store(max_tx_bytes, Ordering::Relaxed);
+        self.max_pool_bytes.store(max_pool_bytes, Ordering::Relaxed);
+    }
+
+    /// Raise or lower the admission floor on effective gas price.
+    pub fn set_min_gas_price(&self, price: u64) {
+        self.min_gas_price.store(price, Ordering::Relaxed);
+    }
+
+    /// Attach the ledger so admission can reject transactions the
+    /// chain state already makes unexecutable.
+    pub async fn attach_state(&self, state: Arc<crate::security::state::StateSecurityManager>) {
+        *self.state.write().await = Some(state);
+    }
+
+    /// Refuse all further admissions, e.g. while shutting down.
+    pub fn seal(&self) {
+        self.sealed.store(true, Ordering::Relaxed);
+    }
+
+    /// The fee policy admission and ordering decisions are made under.
+    pub fn fee_policy(&self) -> &FeePolicy {
+        &self.fee_policy
+    }
+
+    fn shard_for(&self, sender: &str) -> &PoolShard {
+        let digest = Sha256::digest(sender.as_bytes());
+        &self.shards[digest[0] as usize % SHARD_COUNT]
+    }
+
+    /// Admit a transaction into the pool. Only checks that the nonce is not
+    /// lower than one we have already seen from the sender.
+    ///
+    /// The id is forced to the canonical hash on the way in: an empty id
+    /// is assigned, and a submitted id that disagrees with the hash is
+    /// rejected so clients cannot collide with or spoof other entries.
+    pub async fn add_transaction(&self, mut tx: Transaction) -> Result<(), TransactionError> {
+        if self.sealed.load(Ordering::Relaxed) {
+            return Err(TransactionError::Invalid("node is shutting down".into()));
+        }
+        if self.len.load(Ordering::Relaxed) >= self.max_size {
+            return Err(TransactionError::PoolFull);
+        }
+        let canonical = tx.hash();
+        if tx.id.is_empty() {
+            tx.id = canonical;
+        } else if tx.id != canonical {
+            return Err(TransactionError::Invalid(format!(
+                "transaction id {} does not match canonical hash {canonical}",
+                tx.id
+            )));
+        }
+        if !self.fee_policy.is_acceptable(&tx.fee_denom) {
+            return Err(TransactionError::Invalid(format!(
+                "fee denomination {} not accepted",
+                tx.fee_denom
+            )));
+        }
+        let floor = self.min_gas_price.load(Ordering::Relaxed);
+        if self.fee_policy.effective_gas_price(&tx) < floor {
+            return Err(TransactionError::Invalid(format!(
+                "effective gas price below the node minimum of {floor}"
+            )));
+        }
+        // Obviously unexecutable transactions stop here, before gossip.
+        if let Some(state) = self.state.read().await.clone() {
+            state.check_admissible(&tx).await?;
+        }
+        let tx_size = encoded_size(&tx);
+        let max_tx = self.max_tx_bytes.load(Ordering::Relaxed);
+        if tx_size > max_tx {
+            return Err(TransactionError::Invalid(format!(
+                "encoded transaction is {tx_size} bytes, above the {max_tx}-byte limit"
+            )));
+        }
+        if self.bytes.load(Ordering::Relaxed) + tx_size
+            > self.max_pool_bytes.load(Ordering::Relaxed)
+        {
+            self.evict_for(tx_size, &tx).await?;
+        }
+        let shard = self.shard_for(&tx.sender);
+        let mut txs = shard.transactions.write().await;
+        if txs.contains_key(&tx.id) {
+            return Err(TransactionError::Duplicate(tx.id));
+        }
+        let mut nonces = shard.nonces.write().await;
+        if let Some(&last) = nonces.get(&tx.sender) {
+            if tx.nonce <= last {
+                return Err(TransactionError::InvalidNonce {
+                    expected: last + 1,
+                    got: tx.nonce,
+                });
+            }
+        }
+        nonces.insert(tx.sender.clone(), tx.nonce);
+        txs.insert(tx.id.clone(), tx);
+        self.len.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(tx_size, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub async fn get_transaction(&self, id: &str) -> Option<Transaction> {
+        for shard in &self.shards {
+            if let Some(tx) = shard.transactions.read().await.get(id) {
+                return Some(tx.clone());
+            }
+        }
+        None
+    }
+
+    pub async fn remove_transaction(&self, id: &str) -> Option<Transaction> {
+        for shard in &self.shards {
+            if let Some(tx) = shard.transactions.write().await.remove(id) {
+                self.len.fetch_sub(1, Ordering::Relaxed);
+                self.bytes.fetch_sub(encoded_size(&tx), Ordering::Relaxed);
+                return Some(tx);
+            }
+        }
+        None
+    }
+
+    /// Make room for an incoming transaction by evicting pooled entries
+    /// that pay less per byte, cheapest first. Fails with `PoolFull`
+    /// when the incoming transaction itself is the worst payer.
+    async fn evict_for(
+        &self,
+        incoming_size: usize,
+        incoming: &Transaction,
+    ) -> Result<(), TransactionError> {
+        let incoming_fee = offered_fee(&self.fee_policy, incoming) as u128;
+        let mut candidates: Vec<(String, usize, u64)> = Vec::new();
+        for shard in &self.shards {
+            for tx in shard.transactions.read().await.values() {
+                candidates.push((
+                    tx.id.clone(),
+                    encoded_size(tx),
+                    offered_fee(&self.fee_policy, tx),
+                ));
+            }
+        }
+        // Cheapest fee-per-byte first: a/b against c/d compared as
+        // cross products to stay in integers.
+        candidates
+            .sort_by(|a, b| (a.2 as u128 * b.1 as u128).cmp(&(b.2 as u128 * a.1 as u128)));
+        let cap = self.max_pool_bytes.load(Ordering::Relaxed);
+        for (id, size, fee) in candidates {
+            if self.bytes.load(Ordering::Relaxed) + incoming_size <= cap {
+                return Ok(());
+            }
+            // Never evict an entry paying at least as much per byte as
+            // the newcomer.
+            if fee as u128 * incoming_size as u128 >= incoming_fee * size as u128 {
+                break;
+            }
+            self.remove_transaction(&id).await;
+        }
+        if self.bytes.load(Ordering::Relaxed) + incoming_size <= cap {
+            Ok(())
+        } else {
+            Err(TransactionError::PoolFull)
+        }
+    }
+
+    /// All pending transactions, merged across shards in deterministic
+    /// fee order: highest effective (native-denominated) gas price first,
+    /// ties broken by sender, nonce, and id so every node builds the same
+    /// block from the same pool.
+    pub async fn pending(&self) -> Vec<Transaction> {
+        let mut pending = Vec::new();
+        for shard in &self.shards {
+            pending.extend(shard.transactions.read().await.values().cloned());
+        }
+        pending.sort_by(|a, b| {
+            self.fee_policy
+                .effective_gas_price(b)
+                .cmp(&self.fee_policy.effective_gas_price(a))
+                .then_with(|| a.sender.cmp(&b.sender))
+                .then_with(|| a.nonce.cmp(&b.nonce))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+        pending
+    }
+
+    /// Drop every pending transaction and sender nonce watermark,
+    /// returning how many were removed. Operator action via the admin
+    /// API; dropped transactions are simply gone and must be resubmitted.
+    pub async fn clear(&self) -> usize {
+        let mut removed = 0;
+        for shard in &self.shards {
+            let mut txs = shard.transactions.write().await;
+            removed += txs.len();
+            txs.clear();
+            shard.nonces.write().await.clear();
+        }
+        self.len.fetch_sub(removed, Ordering::Relaxed);
+        // The shards are empty, so the byte budget resets with them;
+        // a stale count would wedge admission under the pool byte cap.
+        self.bytes.store(0, Ordering::Relaxed);
+        removed
+    }
+
+    pub async fn len(&self) -> usize {
+        self.len.load(Ordering::Relaxed)
+    }
+
+    pub async fn is_empty(&self) -> bool {
+        self.len().await == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tx(sender: &str, nonce: u64) -> Transaction {
+        Transaction::new(sender.into(), "bob".into(), 10, nonce, 21000, 1, vec![])
+    }
+
+    #[tokio::test]
+    async fn pool_rejects_nonce_reuse() {
+        let pool = TransactionPool::new(16);
+        pool.add_transaction(tx("alice", 1)).await.unwrap();
+        // Different contents, same nonce: past the duplicate check but
+        // caught by the nonce watermark.
+        let mut replay = tx("alice", 1);
+        replay.amount = 11;
+        let err = pool.add_transaction(replay).await.unwrap_err();
+        assert!(matches!(err, TransactionError::InvalidNonce { .. }));
+        pool.add_transaction(tx("alice", 2)).await.unwrap();
+        assert_eq!(pool.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn pool_assigns_canonical_ids_and_rejects_spoofed_ones() {
+        let pool = TransactionPool::new(16);
+        let submitted = tx("alice", 1);
+        let hash = submitted.hash();
+        pool.add_transaction(submitted).await.unwrap();
+        assert_eq!(pool.get_transaction(&hash).await.unwrap().id, hash);
+
+        let mut spoofed = tx("bob", 1);
+        spoofed.id = hash;
+        let err = pool.add_transaction(spoofed).await.unwrap_err();
+        assert!(matches!(err, TransactionError::Invalid(_)));
+    }
+
+    #[tokio::test]
+    async fn stateful_admission_rejects_unexecutable_txs() {
+        let pool = TransactionPool::new(16);
+        let state = Arc::new(crate::security::state::StateSecurityManager::new());
+        state.set_balance("alice", 50_000).await;
+        pool.attach_state(Arc::clone(&state)).await;
+        // A nonce the chain has already consumed is dead on arrival.
+        let err = pool.add_transaction(tx("alice", 0)).await.unwrap_err();
+        assert!(matches!(err, TransactionError::InvalidNonce { .. }));
+        // An unknown sender cannot cover the transfer plus fee.
+        let err = pool.add_transaction(tx("bob", 1)).await.unwrap_err();
+        assert!(matches!(err, TransactionError::InsufficientBalance { .. }));
+        // A gapped-but-future nonce is queueable.
+        pool.add_transaction(tx("alice", 3)).await.unwrap();
+        assert_eq!(pool.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn pool_enforces_the_node_gas_price_floor() {
+        let pool = TransactionPool::new(16);
+        pool.set_min_gas_price(5);
+        let mut cheap = tx("alice", 1);
+        cheap.gas_price = 4;
+        let err = pool.add_transaction(cheap).await.unwrap_err();
+        assert!(matches!(err, TransactionError::Invalid(_)));
+        let mut priced = tx("alice", 1);
+        priced.gas_price = 5;
+        pool.add_transaction(priced).await.unwrap();
+        assert_eq!(pool.len().await, 1);
+    }
+
+    #[tokio::test]
+    async fn pool_enforces_byte_limits_by_fee_per_byte() {
+        let pool = TransactionPool::new(16);
+        let mut cheap = tx("alice", 1);
+        cheap.gas_price = 1;
+        let cheap_hash = cheap.hash();
+        // Measure with the canonical id in place, as the pool will.
+        cheap.id = cheap_hash.clone();
+        let unit = encoded_size(&cheap);
+        pool.set_byte_limits(unit, unit * 2);
+
+        // Over the per-transaction cap.
+        let mut oversized = tx("bob", 1);
+        oversized.data = vec![0; unit];
+        assert!(matches!(
+            pool.add_transaction(oversized).await.unwrap_err(),
+            TransactionError::Invalid(_)
+        ));
+
+        pool.add_transaction(cheap).await.unwrap();
+        let mut mid = tx("carol", 1);
+        mid.gas_price = 5;
+        pool.add_transaction(mid).await.unwrap();
+
+        // The pool is full in bytes; a better payer evicts the
+        // cheapest entry per byte.
+        let mut rich = tx("dave", 1);
+        rich.gas_price = 9;
+        pool.add_transaction(rich).await.unwrap();
+        assert_eq!(pool.len().await, 2);
+        assert!(pool.get_transaction(&cheap_hash).await.is_none());
+
+        // A worse payer than everything pooled is refused instead.
+        let mut pauper = tx("erin", 1);
+        pauper.gas_price = 1;
+        assert!(matches!(
+            pool.add_transaction(pauper).await.unwrap_err(),
+            TransactionError::PoolFull
+        ));
+    }
+
+    #[tokio::test]
+    async fn clear_resets_the_byte_budget() {
+        let pool = TransactionPool::new(16);
+        let mut probe = tx("alice", 1);
+        probe.gas_price = 1;
+        probe.id = probe.hash();
+        let unit = encoded_size(&probe);
+        pool.set_byte_limits(unit, unit * 2);
+
+        for (sender, gas_price) in [("alice", 1), ("bob", 5)] {
+            let mut t = tx(sender, 1);
+            t.gas_price = gas_price;
+            pool.add_transaction(t).await.unwrap();
+        }
+        assert_eq!(pool.clear().await, 2);
+
+        // The emptied pool admits a full byte budget again; a stale
+        // counter would report PoolFull with nothing left to evict.
+        for (sender, gas_price) in [("carol", 1), ("dave", 5)] {
+            let mut t = tx(sender, 1);
+            t.gas_price = gas_price;
+            pool.add_transaction(t).await.unwrap();
+        }
+        assert_eq!(pool.len().await, 2);
+    }
+
+    #[tokio::test]
+    async fn pending_merges_shards_in_fee_order() {
+        let pool = TransactionPool::new(16);
+        for (sender, gas_price) in [("alice", 5), ("bob", 9), ("carol", 5), ("dave", 1)] {
+            let mut t = tx(sender, 1);
+            t.gas_price = gas_price;
+            pool.add_transaction(t).await.unwrap();
+        }
+        let order: Vec<String> = pool
+            .pending()
+            .await
+            .into_iter()
+            .map(|t| t.sender)
+            .collect();
+        assert_eq!(order, vec!["bob", "alice", "carol", "dave"]);
+    }
+}
@@ -0,0 +1,119 @@
+use bip39::{Language, Mnemonic};
+use hmac::{Hmac, Mac};
+use sha2::Sha512;
+use thiserror::Error;
+
+use crate::security::SecurityManager;
+
+#[derive(Debug, Error)]
+pub enum WalletError {
+    #[error("invalid mnemonic: {0}")]
+    InvalidMnemonic(String),
+}
+
+/// BIP44 purpose constant for the derivation path.
+const PURPOSE: u32 = 44;
+/// Registered-style coin type for this chain's accounts.
+const COIN_TYPE: u32 = 7777;
+/// Offset marking a derivation index as hardened.
+const HARDENED: u32 = 0x8000_0000;
+
+/// A hierarchical-deterministic wallet: one BIP39 mnemonic phrase backs
+/// any number of ed25519 account keys, derived with SLIP-0010 along
+/// `m/44'/7777'/index'`. Recovering the phrase recovers every account.
+pub struct Wallet {
+    mnemonic: Mnemonic,
+}
+
+impl Wallet {
+    /// Generate a fresh 24-word mnemonic.
+    pub fn generate() -> Self {
+        Self {
+            mnemonic: Mnemonic::generate_in(Language::English, 24)
+                .expect("24 is a valid word count"),
+        }
+    }
+
+    /// Recover a wallet from its backup phrase.
+    pub fn from_phrase(phrase: &str) -> Result<Self, WalletError> {
+        Ok(Self {
+            mnemonic: Mnemonic::parse_in_normalized(Language::English, phrase)
+                .map_err(|err| WalletError::InvalidMnemonic(err.to_string()))?,
+        })
+    }
+
+    /// The backup phrase for this wallet.
+    pub fn phrase(&self) -> String {
+        self.mnemonic.to_string()
+    }
+
+    /// The signing key for account `index`.
+    pub fn account(&self, index: u32) -> SecurityManager {
+        let seed = self.mnemonic.to_seed("");
+        let key = derive_ed25519_key(&seed, &[PURPOSE, COIN_TYPE, index]);
+        SecurityManager::from_bytes(&key)
+    }
+
+    /// The address of account `index`.
+    pub fn address(&self, index: u32) -> String {
+        self.account(index).address()
+    }
+}
+
+/// SLIP-0010 ed25519 key derivation. Every step is hardened (ed25519
+/// has no usable non-hardened derivation), so `path` indices are given
+/// without the hardened bit.
+pub fn derive_ed25519_key(seed: &[u8], path: &[u32]) -> [u8; 32] {
+    let master = hmac_sha512(b"ed25519 seed", seed);
+    let (mut key, mut chain_code) = split_key(&master);
+    for index in path {
+        let mut data = vec![0u8];
+        data.extend_from_slice(&key);
+        data.extend_from_slice(&(index | HARDENED).to_be_bytes());
+        let child = hmac_sha512(&chain_code, &data);
+        (key, chain_code) = split_key(&child);
+    }
+    key
+}
+
+fn split_key(digest: &[u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&digest[..32]);
+    chain_code.copy_from_slice(&digest[32..]);
+    (key, chain_code)
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac =
+        Hmac::<Sha512>::new_from_slice(key).expect("hmac accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_slip10_test_vector() {
+        // SLIP-0010 ed25519 test vector 1, chain m/0'.
+        let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+        let key = derive_ed25519_key(&seed, &[0]);
+        assert_eq!(
+            hex::encode(key),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+    }
+
+    #[test]
+    fn phrase_recovers_every_account() {
+        let wallet = Wallet::generate();
+        let recovered = Wallet::from_phrase(&wallet.phrase()).unwrap();
+        assert_eq!(wallet.address(0), recovered.address(0));
+        assert_eq!(wallet.address(7), recovered.address(7));
+        // Accounts are independent keys.
+        assert_ne!(wallet.address(0), wallet.address(1));
+        assert!(Wallet::from_phrase("not a real phrase").is_err());
+    }
+}